@@ -0,0 +1,102 @@
+//! Benchmarks for Generator::process_buffer, the hot path of the engine.
+//!
+//! Run with `cargo bench` from the core directory. Combine with the
+//! fast_sine_* features to compare the sine implementations in context.
+
+extern crate baselisk_core;
+
+use baselisk_core::defs;
+use baselisk_core::engine::generator::Generator;
+use baselisk_core::shared::{
+    event::EngineEvent,
+    parameter::{
+        BaseliskPluginParameters,
+        ParameterId,
+    },
+};
+use std::hint::black_box;
+use std::time::Instant;
+
+const BUFSIZE: usize = 4096;
+const ITERATIONS: u32 = 500;
+const SAMPLE_RATE: defs::Sample = 48000.0;
+
+/// Buffers that are passed to the generator as its inputs.
+struct Inputs {
+    mod_buffer: Vec<defs::MonoFrame>,
+    sync_input: Option<Vec<defs::MonoFrame>>,
+}
+
+fn bench(name: &str, params: &BaseliskPluginParameters, inputs: &Inputs) {
+    let mut generator = Generator::new(0);
+    let mut buffer = vec![[0.0]; BUFSIZE];
+    let mut sync_output = vec![[0.0]; BUFSIZE];
+    let mut process = |generator: &mut Generator, events: &[(usize, EngineEvent)]| {
+        generator.process_buffer(
+            black_box(&mut buffer),
+            black_box(&inputs.mod_buffer),
+            inputs.sync_input.as_ref().map(|sync_input| black_box(&sync_input[..])),
+            black_box(&mut sync_output),
+            events.iter(),
+            SAMPLE_RATE,
+            params,
+        );
+    };
+
+    // Start a note, then warm up
+    let note_on = [(0, EngineEvent::NoteChange { note: Some(57), velocity: 127 })];
+    process(&mut generator, &note_on);
+    for _ in 0..ITERATIONS / 10 {
+        process(&mut generator, &[]);
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        process(&mut generator, &[]);
+    }
+    let elapsed = start.elapsed();
+
+    let ns_per_iter = elapsed.as_nanos() / u128::from(ITERATIONS);
+    let ns_per_sample = ns_per_iter as f64 / BUFSIZE as f64;
+    println!("{:<12} {:>10} ns/iter ({:.2} ns/sample, {} samples per iter)",
+             name, ns_per_iter, ns_per_sample, BUFSIZE);
+}
+
+fn main() {
+    let params = BaseliskPluginParameters::default();
+    let set = |param: ParameterId, value: &str| {
+        params.update_real_value_from_string(param, String::from(value)).unwrap();
+    };
+    set(ParameterId::GeneratorAWaveform, "sine");
+
+    let unmodulated = Inputs {
+        mod_buffer: vec![[0.0]; BUFSIZE],
+        sync_input: None,
+    };
+    bench("sine", &params, &unmodulated);
+
+    // Modulated by a 330 Hz sine
+    let modulated = Inputs {
+        mod_buffer: (0..BUFSIZE)
+            .map(|i| [defs::Sample::sin(defs::TWOPI * 330.0 * i as defs::Sample / SAMPLE_RATE)])
+            .collect(),
+        sync_input: None,
+    };
+    bench("fm", &params, &modulated);
+
+    // Hard synced to a source that wraps every 100 samples
+    let synced = Inputs {
+        mod_buffer: vec![[0.0]; BUFSIZE],
+        sync_input: Some((0..BUFSIZE)
+            .map(|i| if i % 100 == 99 { [0.5] } else { [0.0] })
+            .collect()),
+    };
+    bench("sync", &params, &synced);
+
+    set(ParameterId::GeneratorAFeedback, "0.5");
+    bench("feedback", &params, &unmodulated);
+    set(ParameterId::GeneratorAFeedback, "0.0");
+
+    set(ParameterId::GeneratorAWaveform, "saw");
+    bench("saw", &params, &unmodulated);
+}
@@ -0,0 +1,59 @@
+//! Benchmarks for the sine implementations used by the generators.
+//!
+//! Run with `cargo bench` from the core directory. This uses no benchmarking
+//! framework so that it builds on stable Rust.
+
+extern crate baselisk_core;
+
+use baselisk_core::defs;
+use baselisk_core::engine::sine;
+use std::hint::black_box;
+use std::time::Instant;
+
+const BUFSIZE: usize = 4096;
+const ITERATIONS: u32 = 2000;
+
+/// Evaluate a sine for each of a buffer of phases.
+/// The phases are prepared beforehand so that only the sine evaluation is timed.
+fn sine_buffer<F>(sine_fn: F, phases: &[defs::Sample], buffer: &mut [defs::Sample])
+    where F: Fn(defs::Sample) -> defs::Sample
+{
+    for (sample, phase) in buffer.iter_mut().zip(phases) {
+        *sample = sine_fn(*phase);
+    }
+}
+
+fn bench<F>(name: &str, sine_fn: F)
+    where F: Fn(defs::Sample) -> defs::Sample + Copy
+{
+    // A 100 Hz sine at 48 kHz
+    let step = 100.0 / 48000.0;
+    let phases: Vec<defs::Sample> = (0..BUFSIZE)
+        .map(|i| i as defs::Sample * step)
+        .map(|phase| phase - phase.floor())
+        .collect();
+    let mut buffer = [0.0; BUFSIZE];
+
+    // Warm up
+    for _ in 0..ITERATIONS / 10 {
+        sine_buffer(sine_fn, black_box(&phases), black_box(&mut buffer));
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        sine_buffer(sine_fn, black_box(&phases), black_box(&mut buffer));
+    }
+    let elapsed = start.elapsed();
+
+    let ns_per_iter = elapsed.as_nanos() / u128::from(ITERATIONS);
+    let ns_per_sample = ns_per_iter as f64 / BUFSIZE as f64;
+    println!("{:<12} {:>10} ns/iter ({:.2} ns/sample, {} samples per iter)",
+             name, ns_per_iter, ns_per_sample, BUFSIZE);
+}
+
+fn main() {
+    bench("std", sine::std_sine);
+    bench("table", sine::table_sine);
+    bench("polynomial", sine::polynomial_sine);
+    bench("selected", sine::sine);
+}
@@ -0,0 +1,29 @@
+//! Build script: generates the lookup table used by the table sine implementation.
+
+use std::env;
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Number of table entries per period of the sine wave.
+/// This must match SINE_TABLE_SIZE in src/engine/sine.rs.
+const SINE_TABLE_SIZE: usize = 1024;
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let path = Path::new(&out_dir).join("sine_table.rs");
+    let mut file = File::create(&path).unwrap();
+
+    // One extra entry (equal to the first) so that interpolation
+    // never needs to wrap around to the start of the table.
+    writeln!(file, "static SINE_TABLE: [defs::Sample; {}] = [", SINE_TABLE_SIZE + 1).unwrap();
+    for i in 0..=SINE_TABLE_SIZE {
+        // Debug formatting gives the shortest literal that reads back as the same f32
+        let value = (2.0 * PI * i as f64 / SINE_TABLE_SIZE as f64).sin() as f32;
+        writeln!(file, "    {:?},", value).unwrap();
+    }
+    writeln!(file, "];").unwrap();
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
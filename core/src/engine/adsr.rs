@@ -9,6 +9,59 @@ use shared::{
 };
 use std::slice;
 
+/// Steepness of a curved stage when its curve parameter is at -1.0 or 1.0.
+const CURVE_MAX_STEEPNESS: defs::Sample = 6.0;
+
+/// The note at which rate scaling leaves the envelope times unchanged (middle C).
+const RATE_SCALING_CENTRE_NOTE: defs::Sample = 60.0;
+
+/// Map linear progress through a stage (0 <= progress <= 1) onto a curve.
+/// A curve of 0.0 is linear. Positive curves are exponential: they move quickly
+/// at first and then settle, like an analog RC envelope. Negative curves are
+/// logarithmic: they move slowly at first and then accelerate. The magnitude of
+/// the curve sets the amount of curvature.
+/// The result is always 0.0 at the start of a stage and 1.0 at the end, so curved
+/// stages join up with each other without discontinuities.
+fn get_curve_progress(progress: defs::Sample, curve: defs::Sample) -> defs::Sample {
+    let progress = progress.max(0.0).min(1.0);
+    let steepness = curve * CURVE_MAX_STEEPNESS;
+    if steepness.abs() < 1.0e-3 {
+        return progress
+    }
+    (1.0 - (-steepness * progress).exp()) / (1.0 - (-steepness).exp())
+}
+
+/// Identifies the set of parameters that controls an ADSR instance.
+#[derive(Clone, Copy)]
+pub enum AdsrId {
+    Main,
+    Generator(usize),
+}
+
+/// The parameters used by an ADSR instance.
+#[derive(Clone, Copy)]
+enum AdsrParams {
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+    AttackCurve,
+    DecayCurve,
+    ReleaseCurve,
+    RateScaling,
+}
+
+const ALL_ADSR_PARAMS: [AdsrParams; 8] = [
+    AdsrParams::Attack,
+    AdsrParams::Decay,
+    AdsrParams::Sustain,
+    AdsrParams::Release,
+    AdsrParams::AttackCurve,
+    AdsrParams::DecayCurve,
+    AdsrParams::ReleaseCurve,
+    AdsrParams::RateScaling,
+];
+
 /// States that ADSR can be in
 enum AdsrStages {
     HeldAttack,  // Attack
@@ -26,16 +79,20 @@ struct AdsrState {
     sample_duration: f32,
     phase_time: f32,
     selected_note: Option<u8>,
+    // How much faster than the set times the envelope runs for the current note
+    rate_scale: f32,
 }
 
 /// An ADSR struct with all the bits plugged together:
 pub struct Adsr {
+    id: AdsrId,
     state: AdsrState,
 }
 
 impl Adsr {
-    pub fn new() -> Self {
+    pub fn new(id: AdsrId) -> Self {
         Self {
+            id,
             state: AdsrState {
                 stage: None,
                 notes_held_count: 0,
@@ -44,10 +101,103 @@ impl Adsr {
                 sample_duration: 1.0, // Needs to be set by update_sample_rate
                 phase_time: 0.0,
                 selected_note: None,
+                rate_scale: 1.0,
             },
         }
     }
 
+    fn get_parameter(&self, param: AdsrParams) -> Option<ParameterId> {
+        match self.id {
+            AdsrId::Main => match param {
+                AdsrParams::Attack => Some(ParameterId::AdsrAttack),
+                AdsrParams::Decay => Some(ParameterId::AdsrDecay),
+                AdsrParams::Sustain => Some(ParameterId::AdsrSustain),
+                AdsrParams::Release => Some(ParameterId::AdsrRelease),
+                AdsrParams::AttackCurve => Some(ParameterId::AdsrAttackCurve),
+                AdsrParams::DecayCurve => Some(ParameterId::AdsrDecayCurve),
+                AdsrParams::ReleaseCurve => Some(ParameterId::AdsrReleaseCurve),
+                // The main envelope isn't affected by the note played.
+                AdsrParams::RateScaling => None,
+            },
+            // Generator envelopes have linear stages, so no curve parameters,
+            // but their rates can be scaled across the keyboard.
+            AdsrId::Generator(0) => match param {
+                AdsrParams::Attack => Some(ParameterId::GeneratorAAttack),
+                AdsrParams::Decay => Some(ParameterId::GeneratorADecay),
+                AdsrParams::Sustain => Some(ParameterId::GeneratorASustain),
+                AdsrParams::Release => Some(ParameterId::GeneratorARelease),
+                AdsrParams::RateScaling => Some(ParameterId::GeneratorAKeyRateScaling),
+                _ => None,
+            },
+            AdsrId::Generator(1) => match param {
+                AdsrParams::Attack => Some(ParameterId::GeneratorBAttack),
+                AdsrParams::Decay => Some(ParameterId::GeneratorBDecay),
+                AdsrParams::Sustain => Some(ParameterId::GeneratorBSustain),
+                AdsrParams::Release => Some(ParameterId::GeneratorBRelease),
+                AdsrParams::RateScaling => Some(ParameterId::GeneratorBKeyRateScaling),
+                _ => None,
+            },
+            AdsrId::Generator(2) => match param {
+                AdsrParams::Attack => Some(ParameterId::GeneratorCAttack),
+                AdsrParams::Decay => Some(ParameterId::GeneratorCDecay),
+                AdsrParams::Sustain => Some(ParameterId::GeneratorCSustain),
+                AdsrParams::Release => Some(ParameterId::GeneratorCRelease),
+                AdsrParams::RateScaling => Some(ParameterId::GeneratorCKeyRateScaling),
+                _ => None,
+            },
+            AdsrId::Generator(3) => match param {
+                AdsrParams::Attack => Some(ParameterId::GeneratorDAttack),
+                AdsrParams::Decay => Some(ParameterId::GeneratorDDecay),
+                AdsrParams::Sustain => Some(ParameterId::GeneratorDSustain),
+                AdsrParams::Release => Some(ParameterId::GeneratorDRelease),
+                AdsrParams::RateScaling => Some(ParameterId::GeneratorDKeyRateScaling),
+                _ => None,
+            },
+            AdsrId::Generator(4) => match param {
+                AdsrParams::Attack => Some(ParameterId::GeneratorEAttack),
+                AdsrParams::Decay => Some(ParameterId::GeneratorEDecay),
+                AdsrParams::Sustain => Some(ParameterId::GeneratorESustain),
+                AdsrParams::Release => Some(ParameterId::GeneratorERelease),
+                AdsrParams::RateScaling => Some(ParameterId::GeneratorEKeyRateScaling),
+                _ => None,
+            },
+            AdsrId::Generator(5) => match param {
+                AdsrParams::Attack => Some(ParameterId::GeneratorFAttack),
+                AdsrParams::Decay => Some(ParameterId::GeneratorFDecay),
+                AdsrParams::Sustain => Some(ParameterId::GeneratorFSustain),
+                AdsrParams::Release => Some(ParameterId::GeneratorFRelease),
+                AdsrParams::RateScaling => Some(ParameterId::GeneratorFKeyRateScaling),
+                _ => None,
+            },
+            AdsrId::Generator(_) => panic!("Unknown generator ID"),
+        }
+    }
+
+    /// Get the value of a parameter for this ADSR.
+    /// Parameters that this ADSR doesn't have (such as curves for generator
+    /// envelopes) have the value 0.0.
+    fn get_value(&self, param: AdsrParams, params: &BaseliskPluginParameters) -> defs::Sample {
+        match self.get_parameter(param) {
+            Some(param_id) => params.get_real_value(param_id),
+            None => 0.0,
+        }
+    }
+
+    /// Get how much faster than the set times the envelope should run for a note.
+    /// At full rate scaling, the envelope runs twice as fast for each octave
+    /// above middle C, and half as fast for each octave below.
+    fn get_rate_scale(&self, note: u8, params: &BaseliskPluginParameters) -> defs::Sample {
+        let rate_scaling = self.get_value(AdsrParams::RateScaling, params);
+        (rate_scaling * (defs::Sample::from(note) - RATE_SCALING_CENTRE_NOTE) / 12.0).exp2()
+    }
+
+    /// There are multiple ADSRs, and some parameter changes correspond to
+    /// only one of them. This method returns true if a parameter change applies
+    /// to this ADSR.
+    fn should_trigger_keyframe_for_param(&self, param_id: ParameterId) -> bool {
+        ALL_ADSR_PARAMS.iter().any(|param| self.get_parameter(*param) == Some(param_id))
+    }
+
     pub fn update_state(&mut self,
                         any_notes_held: bool,
                         current_note_changed: bool,
@@ -81,19 +231,22 @@ impl Adsr {
             None => 0.0,
             Some(AdsrStages::HeldAttack) => {
                 self.state.gain_at_stage_start
-                + self.state.relative_gain_at_stage_end * (
-                    self.state.phase_time / params.get_real_value(ParameterId::AdsrAttack))
+                + self.state.relative_gain_at_stage_end * get_curve_progress(
+                    self.state.phase_time / self.get_value(AdsrParams::Attack, params),
+                    self.get_value(AdsrParams::AttackCurve, params))
             }
             Some(AdsrStages::HeldDecay) => {
                 self.state.gain_at_stage_start
-                + self.state.relative_gain_at_stage_end * (
-                    self.state.phase_time / params.get_real_value(ParameterId::AdsrDecay))
+                + self.state.relative_gain_at_stage_end * get_curve_progress(
+                    self.state.phase_time / self.get_value(AdsrParams::Decay, params),
+                    self.get_value(AdsrParams::DecayCurve, params))
             }
-            Some(AdsrStages::HeldSustain) => params.get_real_value(ParameterId::AdsrSustain),
+            Some(AdsrStages::HeldSustain) => self.get_value(AdsrParams::Sustain, params),
             Some(AdsrStages::Released) => {
                 self.state.gain_at_stage_start
-                + self.state.relative_gain_at_stage_end * (
-                    self.state.phase_time / params.get_real_value(ParameterId::AdsrRelease))
+                + self.state.relative_gain_at_stage_end * get_curve_progress(
+                    self.state.phase_time / self.get_value(AdsrParams::Release, params),
+                    self.get_value(AdsrParams::ReleaseCurve, params))
             }
         }
     }
@@ -119,15 +272,10 @@ impl Adsr {
 
             if let Some((frame_num, engine_event)) = next_event {
                 match engine_event {
-                    // All note changes and ADSR parameter changes will trigger keyframes
+                    // All note changes and parameter changes for this ADSR will trigger keyframes
                     EngineEvent::NoteChange{ .. } => (),
-                    EngineEvent::ModulateParameter { param_id, .. } => match *param_id {
-                        ParameterId::AdsrAttack |
-                        ParameterId::AdsrDecay |
-                        ParameterId::AdsrSustain |
-                        ParameterId::AdsrRelease => (),
-                        _ => continue,
-                    },
+                    EngineEvent::ModulateParameter { param_id, .. } =>
+                        if !self.should_trigger_keyframe_for_param(*param_id) { continue },
                     _ => continue,
                 }
                 next_keyframe = *frame_num;
@@ -157,7 +305,7 @@ impl Adsr {
                 // to update the current state.
                 let (_, event) = next_event.unwrap();
                 match event {
-                    EngineEvent::NoteChange{ note } => {
+                    EngineEvent::NoteChange{ note, .. } => {
                         let any_notes_held_next = note.is_some();
                         let current_note_changed_next = *note != self.state.selected_note;
 
@@ -169,14 +317,14 @@ impl Adsr {
                                           params);
 
                         self.state.selected_note = *note;
+                        if let Some(note) = note {
+                            self.state.rate_scale = self.get_rate_scale(*note, params);
+                        }
                     },
-                    EngineEvent::ModulateParameter { param_id, value } => match *param_id {
-                        ParameterId::AdsrAttack |
-                        ParameterId::AdsrDecay |
-                        ParameterId::AdsrSustain |
-                        ParameterId::AdsrRelease => params.set_parameter(*param_id, *value),
-                        _ => (),
-                    },
+                    EngineEvent::ModulateParameter { param_id, value } =>
+                        if self.should_trigger_keyframe_for_param(*param_id) {
+                            params.set_parameter(*param_id, *value);
+                        },
                     _ => (),
                 };
             }
@@ -187,27 +335,27 @@ impl Adsr {
     }
 
     fn advance(&mut self, params: &BaseliskPluginParameters) -> defs::Sample {
-        self.state.phase_time += self.state.sample_duration;
+        self.state.phase_time += self.state.sample_duration * self.state.rate_scale;
 
         // Handle attack -> decay advancing
         if let Some(AdsrStages::HeldAttack) = self.state.stage {
-            if self.state.phase_time >= params.get_real_value(ParameterId::AdsrAttack) {
+            if self.state.phase_time >= self.get_value(AdsrParams::Attack, params) {
                 self.state.stage = Some(AdsrStages::HeldDecay);
                 self.state.gain_at_stage_start = 1.0;
                 self.state.relative_gain_at_stage_end =
-                    params.get_real_value(ParameterId::AdsrSustain) - self.state.gain_at_stage_start;
-                self.state.phase_time -= params.get_real_value(ParameterId::AdsrAttack);
+                    self.get_value(AdsrParams::Sustain, params) - self.state.gain_at_stage_start;
+                self.state.phase_time -= self.get_value(AdsrParams::Attack, params);
             }
         }
         // Handle decay -> sustain advancing
         if let Some(AdsrStages::HeldDecay) = self.state.stage {
-            if self.state.phase_time >= params.get_real_value(ParameterId::AdsrDecay) {
+            if self.state.phase_time >= self.get_value(AdsrParams::Decay, params) {
                 self.state.stage = Some(AdsrStages::HeldSustain);
             }
         }
         // Handle release -> off advancing
         if let Some(AdsrStages::Released) = self.state.stage {
-            if self.state.phase_time >= params.get_real_value(ParameterId::AdsrRelease) {
+            if self.state.phase_time >= self.get_value(AdsrParams::Release, params) {
                 self.state.stage = None;
             }
         }
@@ -238,7 +386,7 @@ mod tests {
     /// reaching the sustain immediately.
     fn test_ar_impulse_sustain_zero_with_note_hold() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
 
         let comparison_buffer = vec![[0.0], [0.0], [0.0], [0.0]];
         _test(0.02, 0.02, 0.0, 0.02, engine_events, comparison_buffer);
@@ -250,7 +398,7 @@ mod tests {
     /// reaching the sustain immediately.
     fn test_ar_impulse_sustain_one_with_note_hold() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
 
         let comparison_buffer = vec![[1.0], [1.0], [1.0], [1.0]];
         _test(0.02, 0.02, 1.0, 0.02, engine_events, comparison_buffer);
@@ -262,7 +410,7 @@ mod tests {
     /// reaching the sustain immediately.
     fn test_ar_impulse_sustain_half_with_note_hold() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
 
         let comparison_buffer = vec![[0.5], [0.5], [0.5], [0.5]];
         _test(0.02, 0.02, 0.5, 0.02, engine_events, comparison_buffer);
@@ -274,7 +422,7 @@ mod tests {
     /// on the first sample, and the end of the decay on the second sample.
     fn test_ad_one_second_each_sustain_zero_with_note_held() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
 
         let comparison_buffer = vec![[1.0], [0.0], [0.0], [0.0]];
         _test(1.0, 1.0, 0.0, 0.02, engine_events, comparison_buffer);
@@ -287,7 +435,7 @@ mod tests {
     /// fourth sample.
     fn test_ad_two_seconds_each_sustain_zero_with_note_held() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
 
         let comparison_buffer = vec![[0.5], [1.0], [0.5], [0.0], [0.0], [0.0]];
         _test(2.0, 2.0, 0.0, 0.02, engine_events, comparison_buffer);
@@ -300,8 +448,8 @@ mod tests {
     /// before computing the first sample, reaching the sustain immediately.
     fn test_ar_impulse_sustain_one_with_note_press_and_release_impulse() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
-        engine_events.push((3, EngineEvent::NoteChange {note: None} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((3, EngineEvent::NoteChange {note: None, velocity: 0} ));
 
         let comparison_buffer = vec![[1.0], [1.0], [1.0], [0.0], [0.0], [0.0]];
         _test(0.02, 0.02, 1.0, 0.02, engine_events, comparison_buffer);
@@ -314,8 +462,8 @@ mod tests {
     /// before computing the first sample, reaching the sustain immediately.
     fn test_ar_impulse_sustain_one_with_note_press_and_release_two_seconds() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
-        engine_events.push((3, EngineEvent::NoteChange {note: None} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((3, EngineEvent::NoteChange {note: None, velocity: 0} ));
 
         let comparison_buffer = vec![[1.0], [1.0], [1.0], [0.5], [0.0], [0.0]];
         _test(0.02, 0.02, 1.0, 2.000, engine_events, comparison_buffer);
@@ -327,8 +475,8 @@ mod tests {
     /// start from the amplitude when the note was released (0.5).
     fn test_release_mid_attack() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
-        engine_events.push((2, EngineEvent::NoteChange {note: None} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((2, EngineEvent::NoteChange {note: None, velocity: 0} ));
 
         let comparison_buffer = vec![
             [0.25], [0.5], [0.375], [0.25], [0.125], [0.0], [0.0]];
@@ -341,8 +489,8 @@ mod tests {
     /// start from the amplitude when the note was released (0.5).
     fn test_release_mid_decay() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
-        engine_events.push((3, EngineEvent::NoteChange {note: None} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((3, EngineEvent::NoteChange {note: None, velocity: 0} ));
 
         let comparison_buffer = vec![
             [1.0], [0.75], [0.5], [0.375], [0.25], [0.125], [0.0], [0.0]];
@@ -355,9 +503,9 @@ mod tests {
     /// start from the amplitude when the note was released (0.5).
     fn test_retrigger_mid_release() {
         let mut engine_events = Vec::new();
-        engine_events.push((0, EngineEvent::NoteChange {note: Some(0)} ));
-        engine_events.push((4, EngineEvent::NoteChange {note: None} ));
-        engine_events.push((6, EngineEvent::NoteChange {note: Some(0)} ));
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((4, EngineEvent::NoteChange {note: None, velocity: 0} ));
+        engine_events.push((6, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
 
         let comparison_buffer = vec![
             [0.25], [0.5], [0.75], [1.0], // then note off
@@ -366,21 +514,179 @@ mod tests {
         _test(4.0, 1.0, 0.0, 4.0, engine_events, comparison_buffer);
     }
 
-    /// This function abstracts some test functionality around the Adsr.process_buffer method.
-    /// params: optional params where the Baselisk defaults need to be overridden
-    /// engine_events: a vector containing (frame_num, EngineEvent) pairs to iterate over.
-    /// comparison_buffer: a vector of MonoFrames to compare the results against.
-    ///                    the test will generate the same number of samples as the length
-    ///                    of the comparison buffer.
+    #[test]
+    /// Test a two-second exponential attack.
+    /// The midpoint of the attack should be above the linear ramp, and the end of
+    /// the attack should still reach full gain.
+    fn test_exponential_attack() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+
+        let midpoint = get_curve_progress(0.5, 1.0);
+        assert!(midpoint > 0.5);
+
+        let comparison_buffer = vec![[midpoint], [1.0], [1.0], [1.0]];
+        _test_with_curves(2.0, 0.02, 1.0, 0.02, (1.0, 0.0, 0.0),
+                          engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test a two-second logarithmic attack.
+    /// The midpoint of the attack should be below the linear ramp.
+    fn test_logarithmic_attack() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+
+        let midpoint = get_curve_progress(0.5, -1.0);
+        assert!(midpoint < 0.5);
+
+        let comparison_buffer = vec![[midpoint], [1.0], [1.0], [1.0]];
+        _test_with_curves(2.0, 0.02, 1.0, 0.02, (-1.0, 0.0, 0.0),
+                          engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test exponential decay and release.
+    /// A decay from full gain to half sustain, then a release to silence,
+    /// each falling faster than the linear ramp at their midpoints.
+    fn test_exponential_decay_and_release() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((4, EngineEvent::NoteChange {note: None, velocity: 0} ));
+
+        let curve = 0.5;
+        let progress = get_curve_progress(0.5, curve);
+
+        let comparison_buffer = vec![
+            [1.0], [1.0 - 0.5 * progress], [0.5], [0.5], // then note off
+            [0.5 - 0.5 * progress], [0.0], [0.0]];
+        _test_with_curves(1.0, 2.0, 0.5, 2.0, (0.0, curve, curve),
+                          engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test pressing a note during a long curved release.
+    /// The attack should start from the gain reached during the release,
+    /// without a discontinuity.
+    fn test_retrigger_mid_curved_release() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((2, EngineEvent::NoteChange {note: None, velocity: 0} ));
+        engine_events.push((4, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+
+        let curve = -0.5;
+        let released_gain = 1.0 - get_curve_progress(0.5, curve);
+        let attack_gain = released_gain + (1.0 - released_gain) * get_curve_progress(0.5, curve);
+
+        let comparison_buffer = vec![
+            [get_curve_progress(0.5, curve)], [1.0], // then note off
+            [1.0 - get_curve_progress(0.25, curve)], [released_gain], // then note on
+            [attack_gain], [1.0], [1.0]];
+        _test_with_curves(2.0, 0.02, 1.0, 4.0, (curve, 0.0, curve),
+                          engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Curves of any amount must start at zero and end at one.
+    fn test_curve_progress_endpoints() {
+        for curve in &[-1.0, -0.5, 0.0, 0.5, 1.0] {
+            assert!(get_curve_progress(0.0, *curve).abs() < 1.0e-6);
+            assert!((get_curve_progress(1.0, *curve) - 1.0).abs() < 1.0e-6);
+        }
+    }
+
     fn _test(attack_duration: defs::Sample,
              decay_duration: defs::Sample,
              sustain_level: defs::Sample,
              release_duration: defs::Sample,
              engine_events: Vec<(usize, EngineEvent)>,
              comparison_buffer: Vec<defs::MonoFrame>) {
+        _test_with_curves(attack_duration, decay_duration, sustain_level, release_duration,
+                          (0.0, 0.0, 0.0), engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test that a generator envelope uses its own linear parameters,
+    /// and ignores the parameters of the main ADSR.
+    fn test_generator_envelope_uses_own_parameters() {
+        let mut adsr = Adsr::new(AdsrId::Generator(1));
+        let sample_rate = 1.0;
+
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(ParameterId::GeneratorBAttack, String::from("2")).unwrap();
+        params.update_real_value_from_string(ParameterId::GeneratorBDecay, String::from("2")).unwrap();
+        params.update_real_value_from_string(ParameterId::GeneratorBSustain, String::from("0.5")).unwrap();
+        params.update_real_value_from_string(ParameterId::GeneratorBRelease, String::from("2")).unwrap();
+        params.update_real_value_from_string(ParameterId::AdsrAttack, String::from("4")).unwrap();
+        params.update_real_value_from_string(ParameterId::AdsrAttackCurve, String::from("1")).unwrap();
+
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((5, EngineEvent::NoteChange {note: None, velocity: 0} ));
+
+        let comparison_buffer = vec![[0.5], [1.0], [0.75], [0.5], [0.5], [0.25], [0.0]];
+        let mut buffer = vec![[0.0]; comparison_buffer.len()];
+
+        adsr.process_buffer(&mut buffer, engine_events.iter(), sample_rate, &params);
+
+        for i in 0..buffer.len() {
+            let error_abs = defs::Sample::abs(buffer[i][0] - comparison_buffer[i][0]);
+            if error_abs > std::f32::EPSILON {
+                panic!("For sample index {}, actual output == {}, expected == {}, absolute error = {}",
+                       i, buffer[i][0], comparison_buffer[i][0], error_abs);
+            }
+        }
+    }
+
+    #[test]
+    /// Test that rate scaling speeds up a generator envelope for higher notes.
+    /// One octave above middle C at full rate scaling, the envelope runs twice as fast.
+    fn test_generator_envelope_rate_scaling() {
+        let mut adsr = Adsr::new(AdsrId::Generator(1));
+        let sample_rate = 1.0;
+
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(ParameterId::GeneratorBAttack, String::from("4")).unwrap();
+        params.update_real_value_from_string(ParameterId::GeneratorBDecay, String::from("4")).unwrap();
+        params.update_real_value_from_string(ParameterId::GeneratorBSustain, String::from("0.5")).unwrap();
+        params.update_real_value_from_string(ParameterId::GeneratorBRelease, String::from("4")).unwrap();
+        params.update_real_value_from_string(ParameterId::GeneratorBKeyRateScaling, String::from("1")).unwrap();
+
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(72), velocity: 127} ));
+        engine_events.push((5, EngineEvent::NoteChange {note: None, velocity: 0} ));
+
+        let comparison_buffer = vec![[0.5], [1.0], [0.75], [0.5], [0.5], [0.25], [0.0]];
+        let mut buffer = vec![[0.0]; comparison_buffer.len()];
+
+        adsr.process_buffer(&mut buffer, engine_events.iter(), sample_rate, &params);
+
+        for i in 0..buffer.len() {
+            let error_abs = defs::Sample::abs(buffer[i][0] - comparison_buffer[i][0]);
+            if error_abs > std::f32::EPSILON {
+                panic!("For sample index {}, actual output == {}, expected == {}, absolute error = {}",
+                       i, buffer[i][0], comparison_buffer[i][0], error_abs);
+            }
+        }
+    }
+
+    /// This function abstracts some test functionality around the Adsr.process_buffer method.
+    /// params: optional params where the Baselisk defaults need to be overridden
+    /// engine_events: a vector containing (frame_num, EngineEvent) pairs to iterate over.
+    /// comparison_buffer: a vector of MonoFrames to compare the results against.
+    ///                    the test will generate the same number of samples as the length
+    ///                    of the comparison buffer.
+    /// curves: the (attack, decay, release) curve parameters.
+    fn _test_with_curves(attack_duration: defs::Sample,
+                         decay_duration: defs::Sample,
+                         sustain_level: defs::Sample,
+                         release_duration: defs::Sample,
+                         curves: (defs::Sample, defs::Sample, defs::Sample),
+                         engine_events: Vec<(usize, EngineEvent)>,
+                         comparison_buffer: Vec<defs::MonoFrame>) {
 
         // setup
-        let mut adsr = Adsr::new();
+        let mut adsr = Adsr::new(AdsrId::Main);
         let sample_rate = 1.0;
 
         let params = BaseliskPluginParameters::default();
@@ -392,6 +698,12 @@ mod tests {
             ParameterId::AdsrSustain, format!("{}", sustain_level)).unwrap();
         params.update_real_value_from_string(
             ParameterId::AdsrRelease, format!("{}", release_duration)).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::AdsrAttackCurve, format!("{}", curves.0)).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::AdsrDecayCurve, format!("{}", curves.1)).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::AdsrReleaseCurve, format!("{}", curves.2)).unwrap();
 
         let mut buffer = vec![[0.0]; comparison_buffer.len()];
 
@@ -0,0 +1,463 @@
+use defs;
+use shared::dx7;
+use shared::parameter::{
+    BaseliskPluginParameters,
+    ParameterId,
+};
+
+pub const NUM_GENERATORS: usize = 6;
+
+/// Describes how generators are connected: which generators modulate which,
+/// and how much each generator contributes to the output.
+#[derive(Clone, Copy)]
+pub struct Algorithm {
+    // modulation[source][target] is the amount of source's output
+    // that is sent to target's modulator input.
+    modulation: [[defs::Sample; NUM_GENERATORS]; NUM_GENERATORS],
+    // Generators with a nonzero output level are carriers.
+    output_levels: [defs::Sample; NUM_GENERATORS],
+    // The generator that each generator is hard synced to, if any.
+    sync_sources: [Option<usize>; NUM_GENERATORS],
+    // The generator that each generator's output is multiplied by, if any.
+    ring_sources: [Option<usize>; NUM_GENERATORS],
+}
+
+/// Preset routings, selected by the GeneratorRouting parameter.
+enum Routing {
+    /// A -> B -v
+    ///         |-> out
+    /// C -> D -^
+    ParallelStacks,
+    /// A -> B -> C -> D -> out
+    TripleModStack,
+    /// A -v
+    /// B -+-> D -> out
+    /// C -^
+    TripleModBranch,
+    /// Routing set by the RoutingXToY and RoutingXOutput parameters.
+    Custom,
+    /// One of the 32 classic six-operator algorithms, numbered from 1.
+    Dx7(usize),
+}
+
+impl Routing {
+    fn from_value(value: defs::Sample) -> Self {
+        match value as usize {
+            0 => Routing::ParallelStacks,
+            1 => Routing::TripleModStack,
+            2 => Routing::TripleModBranch,
+            3 => Routing::Custom,
+            value => Routing::Dx7(value - 3),
+        }
+    }
+}
+
+/// Get the parameter for the amount that one generator modulates another.
+/// Returns None if the source and target are the same generator,
+/// as generators modulate themselves using their feedback parameter.
+fn get_modulation_parameter(source: usize, target: usize) -> Option<ParameterId> {
+    match (source, target) {
+        (0, 1) => Some(ParameterId::RoutingAToB),
+        (0, 2) => Some(ParameterId::RoutingAToC),
+        (0, 3) => Some(ParameterId::RoutingAToD),
+        (0, 4) => Some(ParameterId::RoutingAToE),
+        (0, 5) => Some(ParameterId::RoutingAToF),
+        (1, 0) => Some(ParameterId::RoutingBToA),
+        (1, 2) => Some(ParameterId::RoutingBToC),
+        (1, 3) => Some(ParameterId::RoutingBToD),
+        (1, 4) => Some(ParameterId::RoutingBToE),
+        (1, 5) => Some(ParameterId::RoutingBToF),
+        (2, 0) => Some(ParameterId::RoutingCToA),
+        (2, 1) => Some(ParameterId::RoutingCToB),
+        (2, 3) => Some(ParameterId::RoutingCToD),
+        (2, 4) => Some(ParameterId::RoutingCToE),
+        (2, 5) => Some(ParameterId::RoutingCToF),
+        (3, 0) => Some(ParameterId::RoutingDToA),
+        (3, 1) => Some(ParameterId::RoutingDToB),
+        (3, 2) => Some(ParameterId::RoutingDToC),
+        (3, 4) => Some(ParameterId::RoutingDToE),
+        (3, 5) => Some(ParameterId::RoutingDToF),
+        (4, 0) => Some(ParameterId::RoutingEToA),
+        (4, 1) => Some(ParameterId::RoutingEToB),
+        (4, 2) => Some(ParameterId::RoutingEToC),
+        (4, 3) => Some(ParameterId::RoutingEToD),
+        (4, 5) => Some(ParameterId::RoutingEToF),
+        (5, 0) => Some(ParameterId::RoutingFToA),
+        (5, 1) => Some(ParameterId::RoutingFToB),
+        (5, 2) => Some(ParameterId::RoutingFToC),
+        (5, 3) => Some(ParameterId::RoutingFToD),
+        (5, 4) => Some(ParameterId::RoutingFToE),
+        _ => None,
+    }
+}
+
+/// Get the parameters for a generator's level in the mix: its output level when
+/// the routing sends it to the output, and its level when heard directly.
+fn get_mix_parameters(generator: usize) -> (ParameterId, ParameterId) {
+    match generator {
+        0 => (ParameterId::GeneratorAOutput, ParameterId::GeneratorADirect),
+        1 => (ParameterId::GeneratorBOutput, ParameterId::GeneratorBDirect),
+        2 => (ParameterId::GeneratorCOutput, ParameterId::GeneratorCDirect),
+        3 => (ParameterId::GeneratorDOutput, ParameterId::GeneratorDDirect),
+        4 => (ParameterId::GeneratorEOutput, ParameterId::GeneratorEDirect),
+        5 => (ParameterId::GeneratorFOutput, ParameterId::GeneratorFDirect),
+        _ => panic!("Unknown generator ID"),
+    }
+}
+
+/// Get the parameters for the generators that a generator is hard synced to
+/// and ring modulated by.
+fn get_interaction_parameters(generator: usize) -> (ParameterId, ParameterId) {
+    match generator {
+        0 => (ParameterId::GeneratorASyncSource, ParameterId::GeneratorARingSource),
+        1 => (ParameterId::GeneratorBSyncSource, ParameterId::GeneratorBRingSource),
+        2 => (ParameterId::GeneratorCSyncSource, ParameterId::GeneratorCRingSource),
+        3 => (ParameterId::GeneratorDSyncSource, ParameterId::GeneratorDRingSource),
+        4 => (ParameterId::GeneratorESyncSource, ParameterId::GeneratorERingSource),
+        5 => (ParameterId::GeneratorFSyncSource, ParameterId::GeneratorFRingSource),
+        _ => panic!("Unknown generator ID"),
+    }
+}
+
+/// Get the generator selected by a sync source or ring source parameter value.
+/// A generator can't be its own source.
+fn get_source_from_value(generator: usize, value: defs::Sample) -> Option<usize> {
+    match value as usize {
+        0 => None,
+        value if value - 1 == generator => None,
+        value => Some(value - 1),
+    }
+}
+
+fn get_output_parameter(generator: usize) -> ParameterId {
+    match generator {
+        0 => ParameterId::RoutingAOutput,
+        1 => ParameterId::RoutingBOutput,
+        2 => ParameterId::RoutingCOutput,
+        3 => ParameterId::RoutingDOutput,
+        4 => ParameterId::RoutingEOutput,
+        5 => ParameterId::RoutingFOutput,
+        _ => panic!("Unknown generator ID"),
+    }
+}
+
+/// Returns true if a parameter is used to build the algorithm, or to mix
+/// the generators. These are read once per buffer.
+pub fn is_algorithm_parameter(param_id: ParameterId) -> bool {
+    param_id == ParameterId::GeneratorRouting
+        || (0..NUM_GENERATORS).any(|source| {
+            let (output_param, direct_param) = get_mix_parameters(source);
+            let (sync_param, ring_param) = get_interaction_parameters(source);
+            get_output_parameter(source) == param_id
+                || output_param == param_id
+                || direct_param == param_id
+                || sync_param == param_id
+                || ring_param == param_id
+                || (0..NUM_GENERATORS).any(|target|
+                    get_modulation_parameter(source, target) == Some(param_id))
+        })
+}
+
+impl Algorithm {
+    /// An algorithm where no generator modulates another,
+    /// and none is sent to the output.
+    fn new() -> Self {
+        Self {
+            modulation: [[0.0; NUM_GENERATORS]; NUM_GENERATORS],
+            output_levels: [0.0; NUM_GENERATORS],
+            sync_sources: [None; NUM_GENERATORS],
+            ring_sources: [None; NUM_GENERATORS],
+        }
+    }
+
+    /// Build an algorithm from a list of (source, target) modulation
+    /// connections and a list of carriers, all at full level.
+    fn from_connections(connections: &[(usize, usize)], carriers: &[usize]) -> Self {
+        let mut algorithm = Self::new();
+        for (source, target) in connections {
+            algorithm.modulation[*source][*target] = 1.0;
+        }
+        for carrier in carriers {
+            algorithm.output_levels[*carrier] = 1.0;
+        }
+        algorithm
+    }
+
+    /// Build one of the classic six-operator algorithms, numbered from 1.
+    fn from_dx7_algorithm(number: usize) -> Self {
+        let (connections, carriers) = dx7::ALGORITHMS[number - 1];
+        // Operator numbers start from 1, and generator IDs start from 0
+        let mut algorithm = Self::new();
+        for (source, target) in connections.iter() {
+            algorithm.modulation[source - 1][target - 1] = 1.0;
+        }
+        for carrier in carriers.iter() {
+            algorithm.output_levels[carrier - 1] = 1.0;
+        }
+        algorithm
+    }
+
+    /// Get the algorithm selected by the GeneratorRouting parameter,
+    /// with the hard sync and ring modulation set for each generator.
+    pub fn from_params(params: &BaseliskPluginParameters) -> Self {
+        let mut algorithm = Self::from_routing_params(params);
+        for generator in 0..NUM_GENERATORS {
+            let (sync_param, ring_param) = get_interaction_parameters(generator);
+            algorithm.sync_sources[generator] = get_source_from_value(
+                generator, params.get_real_value(sync_param));
+            algorithm.ring_sources[generator] = get_source_from_value(
+                generator, params.get_real_value(ring_param));
+        }
+        algorithm
+    }
+
+    fn from_routing_params(params: &BaseliskPluginParameters) -> Self {
+        match Routing::from_value(params.get_real_value(ParameterId::GeneratorRouting)) {
+            Routing::ParallelStacks => Self::from_connections(&[(0, 1), (2, 3)], &[1, 3]),
+            Routing::TripleModStack => Self::from_connections(&[(0, 1), (1, 2), (2, 3)], &[3]),
+            Routing::TripleModBranch => Self::from_connections(&[(0, 3), (1, 3), (2, 3)], &[3]),
+            Routing::Dx7(number) => Self::from_dx7_algorithm(number),
+            Routing::Custom => {
+                let mut algorithm = Self::new();
+                for source in 0..NUM_GENERATORS {
+                    for target in 0..NUM_GENERATORS {
+                        if let Some(param_id) = get_modulation_parameter(source, target) {
+                            algorithm.modulation[source][target] = params.get_real_value(param_id);
+                        }
+                    }
+                    algorithm.output_levels[source] = params.get_real_value(
+                        get_output_parameter(source));
+                }
+                algorithm
+            },
+        }
+    }
+
+    /// The amount of source's output that is sent to target's modulator input.
+    pub fn get_modulation(&self, source: usize, target: usize) -> defs::Sample {
+        self.modulation[source][target]
+    }
+
+    /// The generator that a generator is hard synced to, if any.
+    pub fn get_sync_source(&self, generator: usize) -> Option<usize> {
+        self.sync_sources[generator]
+    }
+
+    /// The generator that a generator's output is multiplied by, if any.
+    pub fn get_ring_source(&self, generator: usize) -> Option<usize> {
+        self.ring_sources[generator]
+    }
+
+    /// Whether target uses the output of source, through modulation,
+    /// hard sync or ring modulation.
+    fn depends_on(&self, target: usize, source: usize) -> bool {
+        self.modulation[source][target] != 0.0
+            || self.sync_sources[target] == Some(source)
+            || self.ring_sources[target] == Some(source)
+    }
+
+    /// The level at which a generator is mixed into the output.
+    /// Carriers are scaled by the generator's output level, so that carriers
+    /// in parallel can be balanced, and any generator can also be heard directly.
+    pub fn get_mix_level(&self, generator: usize, params: &BaseliskPluginParameters) -> defs::Sample {
+        let (output_param, direct_param) = get_mix_parameters(generator);
+        self.output_levels[generator] * params.get_real_value(output_param)
+            + params.get_real_value(direct_param)
+    }
+
+    /// Get the order to process generators in, so that each generator is processed
+    /// after the generators that modulate it, or that it is synced to or ring
+    /// modulated by.
+    /// If generators modulate each other in a loop, the loop is broken at the lowest
+    /// numbered generator that remains, which will use the output of its modulators
+    /// from the previous buffer.
+    pub fn get_processing_order(&self) -> [usize; NUM_GENERATORS] {
+        let mut order = [0; NUM_GENERATORS];
+        let mut processed = [false; NUM_GENERATORS];
+
+        for position in order.iter_mut() {
+            let is_ready = |target: usize| {
+                !processed[target] && (0..NUM_GENERATORS).all(|source| {
+                    source == target
+                    || processed[source]
+                    || !self.depends_on(target, source)
+                })
+            };
+            let next = (0..NUM_GENERATORS).find(|target| is_ready(*target))
+                .or_else(|| (0..NUM_GENERATORS).find(|target| !processed[*target]))
+                .unwrap();
+            *position = next;
+            processed[next] = true;
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_algorithm_parameter() {
+        assert!(is_algorithm_parameter(ParameterId::GeneratorRouting));
+        assert!(is_algorithm_parameter(ParameterId::RoutingAToB));
+        assert!(is_algorithm_parameter(ParameterId::RoutingFToE));
+        assert!(is_algorithm_parameter(ParameterId::RoutingEOutput));
+        assert!(is_algorithm_parameter(ParameterId::GeneratorAOutput));
+        assert!(is_algorithm_parameter(ParameterId::GeneratorFDirect));
+        assert!(is_algorithm_parameter(ParameterId::GeneratorBSyncSource));
+        assert!(is_algorithm_parameter(ParameterId::GeneratorERingSource));
+        assert!(!is_algorithm_parameter(ParameterId::GeneratorAModIndex));
+    }
+
+    #[test]
+    fn test_presets() {
+        let params = BaseliskPluginParameters::default();
+
+        params.update_real_value_from_string(
+            ParameterId::GeneratorRouting, String::from("2simple")).unwrap();
+        let algorithm = Algorithm::from_params(&params);
+        assert_eq!(algorithm.get_modulation(0, 1), 1.0);
+        assert_eq!(algorithm.get_modulation(2, 3), 1.0);
+        assert_eq!(algorithm.get_modulation(1, 2), 0.0);
+        assert_eq!(algorithm.output_levels, [0.0, 1.0, 0.0, 1.0, 0.0, 0.0]);
+
+        params.update_real_value_from_string(
+            ParameterId::GeneratorRouting, String::from("3stack")).unwrap();
+        let algorithm = Algorithm::from_params(&params);
+        assert_eq!(algorithm.get_modulation(1, 2), 1.0);
+        assert_eq!(algorithm.output_levels, [0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(algorithm.get_processing_order(), [0, 1, 2, 3, 4, 5]);
+
+        params.update_real_value_from_string(
+            ParameterId::GeneratorRouting, String::from("3branch")).unwrap();
+        let algorithm = Algorithm::from_params(&params);
+        assert_eq!(algorithm.get_modulation(1, 3), 1.0);
+        assert_eq!(algorithm.get_modulation(1, 2), 0.0);
+        assert_eq!(algorithm.output_levels, [0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_mix_levels() {
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorRouting, String::from("2simple")).unwrap();
+        let algorithm = Algorithm::from_params(&params);
+
+        // By default, only carriers are heard
+        let levels: Vec<_> = (0..NUM_GENERATORS)
+            .map(|generator| algorithm.get_mix_level(generator, &params))
+            .collect();
+        assert_eq!(levels, vec![0.0, 1.0, 0.0, 1.0, 0.0, 0.0]);
+
+        // Carriers are balanced by their output level, and modulators can be heard directly
+        params.update_real_value_from_string(
+            ParameterId::GeneratorBOutput, String::from("0.5")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorADirect, String::from("0.25")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorDDirect, String::from("0.25")).unwrap();
+        let levels: Vec<_> = (0..NUM_GENERATORS)
+            .map(|generator| algorithm.get_mix_level(generator, &params))
+            .collect();
+        assert_eq!(levels, vec![0.25, 0.5, 0.0, 1.25, 0.0, 0.0]);
+    }
+
+    #[test]
+    /// Generators are processed after the generators they are synced to or
+    /// ring modulated by, in any routing.
+    fn test_sync_and_ring_sources() {
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorRouting, String::from("2simple")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorASyncSource, String::from("f")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorCRingSource, String::from("e")).unwrap();
+        // A generator can't be synced to itself
+        params.update_real_value_from_string(
+            ParameterId::GeneratorBSyncSource, String::from("b")).unwrap();
+
+        let algorithm = Algorithm::from_params(&params);
+        assert_eq!(algorithm.get_sync_source(0), Some(5));
+        assert_eq!(algorithm.get_sync_source(1), None);
+        assert_eq!(algorithm.get_ring_source(2), Some(4));
+        assert_eq!(algorithm.get_ring_source(0), None);
+        assert_eq!(algorithm.get_processing_order(), [4, 2, 3, 5, 0, 1]);
+    }
+
+    #[test]
+    fn test_custom_routing() {
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorRouting, String::from("custom")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::RoutingDToB, String::from("0.5")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::RoutingBToA, String::from("0.25")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::RoutingAOutput, String::from("0.75")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::RoutingDOutput, String::from("0")).unwrap();
+
+        let algorithm = Algorithm::from_params(&params);
+        assert_eq!(algorithm.get_modulation(3, 1), 0.5);
+        assert_eq!(algorithm.get_modulation(1, 0), 0.25);
+        assert_eq!(algorithm.get_modulation(0, 1), 0.0);
+        assert_eq!(algorithm.output_levels, [0.75, 0.0, 0.0, 0.0, 0.0, 0.0]);
+
+        // D -> B -> A, and C, E and F are independent.
+        assert_eq!(algorithm.get_processing_order(), [2, 3, 1, 0, 4, 5]);
+    }
+
+    #[test]
+    /// Loops are broken at the lowest numbered generator.
+    fn test_processing_order_with_loop() {
+        // B -> C -> B, and C -> D
+        let algorithm = Algorithm::from_connections(&[(1, 2), (2, 1), (2, 3)], &[3]);
+        assert_eq!(algorithm.get_processing_order(), [0, 4, 5, 1, 2, 3]);
+
+        // A -> D -> C -> A, and B -> C
+        let algorithm = Algorithm::from_connections(&[(0, 3), (3, 2), (2, 0), (1, 2)], &[0]);
+        assert_eq!(algorithm.get_processing_order(), [1, 4, 5, 0, 3, 2]);
+    }
+
+    #[test]
+    fn test_dx7_algorithms() {
+        let params = BaseliskPluginParameters::default();
+
+        // Algorithm 1: 2 -> 1, 6 -> 5 -> 4 -> 3, with carriers 1 and 3
+        params.update_real_value_from_string(
+            ParameterId::GeneratorRouting, String::from("dx7_1")).unwrap();
+        let algorithm = Algorithm::from_params(&params);
+        assert_eq!(algorithm.get_modulation(1, 0), 1.0);
+        assert_eq!(algorithm.get_modulation(5, 4), 1.0);
+        assert_eq!(algorithm.get_modulation(4, 3), 1.0);
+        assert_eq!(algorithm.get_modulation(3, 2), 1.0);
+        assert_eq!(algorithm.get_modulation(2, 1), 0.0);
+        assert_eq!(algorithm.output_levels, [1.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(algorithm.get_processing_order(), [1, 0, 5, 4, 3, 2]);
+
+        // Algorithm 32: all carriers
+        params.update_real_value_from_string(
+            ParameterId::GeneratorRouting, String::from("dx7_32")).unwrap();
+        let algorithm = Algorithm::from_params(&params);
+        assert_eq!(algorithm.output_levels, [1.0; NUM_GENERATORS]);
+        assert_eq!(algorithm.get_processing_order(), [0, 1, 2, 3, 4, 5]);
+
+        // Every algorithm has at least one carrier, and no loops,
+        // so operators are always processed after their modulators.
+        for (number, (connections, carriers)) in dx7::ALGORITHMS.iter().enumerate() {
+            assert!(!carriers.is_empty());
+            let algorithm = Algorithm::from_dx7_algorithm(number + 1);
+            let order = algorithm.get_processing_order();
+            for (source, target) in connections.iter() {
+                let source_position = order.iter().position(|id| *id == source - 1).unwrap();
+                let target_position = order.iter().position(|id| *id == target - 1).unwrap();
+                assert!(source_position < target_position,
+                        "Algorithm {}: operator {} is processed before its modulator {}",
+                        number + 1, target, source);
+            }
+        }
+    }
+}
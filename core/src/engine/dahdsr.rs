@@ -0,0 +1,462 @@
+use defs;
+use engine::traits;
+use shared::{
+    event::EngineEvent,
+    parameter::{
+        BaseliskPluginParameters,
+        ParameterId,
+    },
+};
+use std::slice;
+
+/// Upper bound on the number of stage transitions in a single sample.
+/// Looping envelopes with stages shorter than a sample could otherwise
+/// spend a long time cycling through stages.
+const MAX_TRANSITIONS_PER_SAMPLE: usize = 16;
+
+/// States that DAHDSR can be in
+#[derive(Clone, Copy)]
+enum DahdsrStages {
+    HeldDelay,   // Delay
+    HeldAttack,  // Attack
+    HeldHold,    // Hold
+    HeldDecay,   // Decay
+    HeldSustain, // Sustain
+    Released,    // Release
+}
+
+/// Where the envelope returns to after the decay stage, while a note is held.
+enum DahdsrLoop {
+    Off,
+    Attack,
+    Delay,
+}
+
+impl DahdsrLoop {
+    fn from_params(params: &BaseliskPluginParameters) -> Self {
+        match params.get_real_value(ParameterId::DahdsrLoop) as usize {
+            0 => DahdsrLoop::Off,
+            1 => DahdsrLoop::Attack,
+            2 => DahdsrLoop::Delay,
+            _ => panic!("Unknown DAHDSR loop mode"),
+        }
+    }
+}
+
+/// Struct to hold the current state of a DAHDSR processor
+struct DahdsrState {
+    stage: Option<DahdsrStages>,
+    gain_at_stage_start: f32,
+    relative_gain_at_stage_end: f32,
+    sample_duration: f32,
+    phase_time: f32,
+    selected_note: Option<u8>,
+}
+
+/// A delay, attack, hold, decay, sustain, release envelope.
+/// While a note is held, the envelope can optionally loop back to the delay or
+/// attack stage when the decay stage ends, instead of sustaining.
+/// The output is in the range 0.0 <= x <= 1.0, so it can be used as an
+/// amplitude envelope or as a modulation source.
+pub struct Dahdsr {
+    state: DahdsrState,
+}
+
+impl Dahdsr {
+    pub fn new() -> Self {
+        Self {
+            state: DahdsrState {
+                stage: None,
+                gain_at_stage_start: 0.0,
+                relative_gain_at_stage_end: 0.0,
+                sample_duration: 1.0, // Needs to be set by process_buffer
+                phase_time: 0.0,
+                selected_note: None,
+            },
+        }
+    }
+
+    pub fn update_state(&mut self,
+                        any_notes_held: bool,
+                        current_note_changed: bool,
+                        params: &BaseliskPluginParameters)
+    {
+        if any_notes_held && current_note_changed {
+            // Transition to delay stage, holding the current gain to avoid discontinuities
+            self.enter_stage(DahdsrStages::HeldDelay, params);
+        } else if !any_notes_held && self.state.stage.is_some() {
+            // Transition to release stage
+            self.enter_stage(DahdsrStages::Released, params);
+        }
+    }
+
+    fn get_stage_duration(stage: DahdsrStages,
+                          params: &BaseliskPluginParameters) -> Option<defs::Sample>
+    {
+        match stage {
+            DahdsrStages::HeldDelay => Some(params.get_real_value(ParameterId::DahdsrDelay)),
+            DahdsrStages::HeldAttack => Some(params.get_real_value(ParameterId::DahdsrAttack)),
+            DahdsrStages::HeldHold => Some(params.get_real_value(ParameterId::DahdsrHold)),
+            DahdsrStages::HeldDecay => Some(params.get_real_value(ParameterId::DahdsrDecay)),
+            DahdsrStages::HeldSustain => None,
+            DahdsrStages::Released => Some(params.get_real_value(ParameterId::DahdsrRelease)),
+        }
+    }
+
+    /// Begin a stage, starting from the current gain.
+    fn enter_stage(&mut self, stage: DahdsrStages, params: &BaseliskPluginParameters) {
+        let gain_at_stage_start = self.get_gain(params);
+        let gain_at_stage_end = match stage {
+            DahdsrStages::HeldDelay => gain_at_stage_start,
+            DahdsrStages::HeldAttack |
+            DahdsrStages::HeldHold => 1.0,
+            DahdsrStages::HeldDecay |
+            DahdsrStages::HeldSustain => params.get_real_value(ParameterId::DahdsrSustain),
+            DahdsrStages::Released => 0.0,
+        };
+        self.state.gain_at_stage_start = gain_at_stage_start;
+        self.state.relative_gain_at_stage_end = gain_at_stage_end - gain_at_stage_start;
+        self.state.phase_time = 0.0;
+        self.state.stage = Some(stage);
+    }
+
+    fn get_gain(&self, params: &BaseliskPluginParameters) -> f32 {
+        let stage = match self.state.stage {
+            None => return 0.0,
+            Some(DahdsrStages::HeldSustain) => return params.get_real_value(ParameterId::DahdsrSustain),
+            Some(stage) => stage,
+        };
+        let progress = match Dahdsr::get_stage_duration(stage, params) {
+            Some(duration) if duration > 0.0 => (self.state.phase_time / duration).min(1.0),
+            _ => 1.0,
+        };
+        self.state.gain_at_stage_start + self.state.relative_gain_at_stage_end * progress
+    }
+
+    // Process the buffer of audio.
+    // Return a bool indicating whether the output contains non-zero samples.
+    pub fn process_buffer(&mut self,
+                          buffer: &mut defs::MonoFrameBufferSlice,
+                          mut engine_event_iter: slice::Iter<(usize, EngineEvent)>,
+                          sample_rate: defs::Sample,
+                          params: &BaseliskPluginParameters) -> bool
+    {
+        self.state.sample_duration = 1.0 / sample_rate;
+
+        let mut any_nonzero_output = self.state.stage.is_some();
+
+        // Calculate the output values per-frame
+        let mut this_keyframe: usize = 0;
+        let mut next_keyframe: usize;
+        loop {
+            // Get next selected note, if there is one.
+            let next_event = engine_event_iter.next();
+
+            if let Some((frame_num, engine_event)) = next_event {
+                match engine_event {
+                    // All note changes and DAHDSR parameter changes will trigger keyframes
+                    EngineEvent::NoteChange{ .. } => (),
+                    EngineEvent::ModulateParameter { param_id, .. } => match *param_id {
+                        ParameterId::DahdsrDelay |
+                        ParameterId::DahdsrAttack |
+                        ParameterId::DahdsrHold |
+                        ParameterId::DahdsrDecay |
+                        ParameterId::DahdsrSustain |
+                        ParameterId::DahdsrRelease |
+                        ParameterId::DahdsrLoop => (),
+                        _ => continue,
+                    },
+                    _ => continue,
+                }
+                next_keyframe = *frame_num;
+            } else {
+                // No more note change events, so we'll process to the end of the buffer.
+                next_keyframe = buffer.len();
+            }
+
+            // Apply the old parameters up until next_keyframe.
+            if let Some(buffer_slice) = buffer.get_mut(this_keyframe..next_keyframe) {
+                for frame in buffer_slice {
+                    for sample in frame {
+                        *sample = self.advance(params);
+                    }
+                }
+            }
+
+            // We've reached the next_keyframe.
+            this_keyframe = next_keyframe;
+
+            // What we do now depends on whether we reached the end of the buffer.
+            if this_keyframe == buffer.len() {
+                // Loop exit condition: reached the end of the buffer.
+                break
+            } else {
+                // Before the next iteration, use the event at this keyframe
+                // to update the current state.
+                let (_, event) = next_event.unwrap();
+                match event {
+                    EngineEvent::NoteChange{ note, .. } => {
+                        let any_notes_held_next = note.is_some();
+                        let current_note_changed_next = *note != self.state.selected_note;
+
+                        // If any note is pressed, assume this means there will be some output.
+                        any_nonzero_output |= any_notes_held_next;
+
+                        self.update_state(any_notes_held_next,
+                                          current_note_changed_next,
+                                          params);
+
+                        self.state.selected_note = *note;
+                    },
+                    EngineEvent::ModulateParameter { param_id, value } => match *param_id {
+                        ParameterId::DahdsrDelay |
+                        ParameterId::DahdsrAttack |
+                        ParameterId::DahdsrHold |
+                        ParameterId::DahdsrDecay |
+                        ParameterId::DahdsrSustain |
+                        ParameterId::DahdsrRelease |
+                        ParameterId::DahdsrLoop => params.set_parameter(*param_id, *value),
+                        _ => (),
+                    },
+                    _ => (),
+                };
+            }
+        }
+        // Return a bool to tell the engine whether there's any nonzero output, to
+        // allow it to make any desired optimizations.
+        any_nonzero_output
+    }
+
+    fn advance(&mut self, params: &BaseliskPluginParameters) -> defs::Sample {
+        self.state.phase_time += self.state.sample_duration;
+
+        // Advance through as many stages as have elapsed.
+        for _ in 0..MAX_TRANSITIONS_PER_SAMPLE {
+            let stage = match self.state.stage {
+                Some(stage) => stage,
+                None => break,
+            };
+            let duration = match Dahdsr::get_stage_duration(stage, params) {
+                Some(duration) => duration,
+                None => break, // Sustain lasts until the note is released
+            };
+            if self.state.phase_time < duration {
+                break
+            }
+            let excess_time = self.state.phase_time - duration;
+
+            let next_stage = match stage {
+                DahdsrStages::HeldDelay => Some(DahdsrStages::HeldAttack),
+                DahdsrStages::HeldAttack => Some(DahdsrStages::HeldHold),
+                DahdsrStages::HeldHold => Some(DahdsrStages::HeldDecay),
+                DahdsrStages::HeldDecay => match DahdsrLoop::from_params(params) {
+                    DahdsrLoop::Off => Some(DahdsrStages::HeldSustain),
+                    DahdsrLoop::Attack => Some(DahdsrStages::HeldAttack),
+                    DahdsrLoop::Delay => Some(DahdsrStages::HeldDelay),
+                },
+                DahdsrStages::HeldSustain => Some(DahdsrStages::HeldSustain),
+                DahdsrStages::Released => None,
+            };
+
+            match next_stage {
+                Some(next_stage) => {
+                    // Make sure the next stage starts from the end of this stage.
+                    self.state.phase_time = duration;
+                    self.enter_stage(next_stage, params);
+                    self.state.phase_time = excess_time;
+                },
+                None => self.state.stage = None,
+            }
+        }
+
+        // Return the output
+        self.get_gain(params)
+    }
+}
+
+impl traits::Processor for Dahdsr {
+    fn panic(&mut self) {
+        // Release all notes and reset state to "Off"
+        self.state.stage = None;
+        self.state.selected_note = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // All tests use a sample rate of 1 second, so each sample is the result of
+    // advancing the simulation time by 1 second.
+
+    #[test]
+    /// Test each stage in turn with a held note.
+    /// The one-second delay ends on the first sample, the two-second attack
+    /// ends on the third, the one-second hold ends on the fourth and the
+    /// two-second decay ends on the sixth sample.
+    fn test_all_stages_with_note_held() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+
+        let comparison_buffer = vec![
+            [0.0], [0.5], [1.0], [1.0], [0.75], [0.5], [0.5], [0.5]];
+        _test(vec![(ParameterId::DahdsrDelay, "1.0"),
+                   (ParameterId::DahdsrAttack, "2.0"),
+                   (ParameterId::DahdsrHold, "1.0"),
+                   (ParameterId::DahdsrDecay, "2.0"),
+                   (ParameterId::DahdsrSustain, "0.5")],
+              engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test zero-length delay and hold stages behave like an ADSR.
+    fn test_no_delay_or_hold() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+
+        let comparison_buffer = vec![[0.5], [1.0], [0.5], [0.0], [0.0], [0.0]];
+        _test(vec![(ParameterId::DahdsrDelay, "0.0"),
+                   (ParameterId::DahdsrAttack, "2.0"),
+                   (ParameterId::DahdsrHold, "0.0"),
+                   (ParameterId::DahdsrDecay, "2.0"),
+                   (ParameterId::DahdsrSustain, "0.0")],
+              engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test a note release during the hold stage.
+    /// The release starts from the held gain.
+    fn test_release_mid_hold() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((2, EngineEvent::NoteChange {note: None, velocity: 0} ));
+
+        let comparison_buffer = vec![
+            [1.0], [1.0], // then note off
+            [0.5], [0.0], [0.0]];
+        _test(vec![(ParameterId::DahdsrAttack, "0.001"),
+                   (ParameterId::DahdsrHold, "4.0"),
+                   (ParameterId::DahdsrRelease, "2.0")],
+              engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test looping back to the attack stage after the decay.
+    /// The attack restarts from the sustain level, so there is no discontinuity.
+    fn test_loop_attack() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+
+        let comparison_buffer = vec![
+            [0.5], [1.0], [0.5], [0.0], // first cycle
+            [0.5], [1.0], [0.5], [0.0], // looped
+            [0.5]];
+        _test(vec![(ParameterId::DahdsrAttack, "2.0"),
+                   (ParameterId::DahdsrDecay, "2.0"),
+                   (ParameterId::DahdsrSustain, "0.0"),
+                   (ParameterId::DahdsrLoop, "attack")],
+              engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test looping back to the delay stage after the decay.
+    /// The delay holds the sustain level before the attack begins again.
+    fn test_loop_delay() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+
+        let comparison_buffer = vec![
+            [0.0], [1.0], [0.5], [0.0], // first cycle
+            [0.0], [1.0], [0.5], [0.0]]; // looped
+        _test(vec![(ParameterId::DahdsrDelay, "1.0"),
+                   (ParameterId::DahdsrAttack, "1.0"),
+                   (ParameterId::DahdsrDecay, "2.0"),
+                   (ParameterId::DahdsrSustain, "0.0"),
+                   (ParameterId::DahdsrLoop, "delay")],
+              engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test releasing a looping envelope mid-attack.
+    fn test_release_while_looping() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((5, EngineEvent::NoteChange {note: None, velocity: 0} ));
+
+        let comparison_buffer = vec![
+            [0.5], [1.0], [0.5], [0.0], // first cycle
+            [0.5], // looped, then note off
+            [0.25], [0.0], [0.0]];
+        _test(vec![(ParameterId::DahdsrAttack, "2.0"),
+                   (ParameterId::DahdsrDecay, "2.0"),
+                   (ParameterId::DahdsrSustain, "0.0"),
+                   (ParameterId::DahdsrRelease, "2.0"),
+                   (ParameterId::DahdsrLoop, "attack")],
+              engine_events, comparison_buffer);
+    }
+
+    #[test]
+    /// Test pressing a note during a long release.
+    /// The delay stage holds the released gain, then the attack
+    /// continues from there.
+    fn test_retrigger_mid_release() {
+        let mut engine_events = Vec::new();
+        engine_events.push((0, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+        engine_events.push((3, EngineEvent::NoteChange {note: None, velocity: 0} ));
+        engine_events.push((5, EngineEvent::NoteChange {note: Some(0), velocity: 127} ));
+
+        let comparison_buffer = vec![
+            [0.0], [0.5], [1.0], // then note off
+            [0.75], [0.5], // then note on
+            [0.5], [0.75], [1.0], [1.0]];
+        _test(vec![(ParameterId::DahdsrDelay, "1.0"),
+                   (ParameterId::DahdsrAttack, "2.0"),
+                   (ParameterId::DahdsrHold, "0.0"),
+                   (ParameterId::DahdsrSustain, "1.0"),
+                   (ParameterId::DahdsrRelease, "4.0")],
+              engine_events, comparison_buffer);
+    }
+
+    /// This function abstracts some test functionality around the Dahdsr.process_buffer method.
+    /// params_to_set: (parameter, value) pairs where the Baselisk defaults need to be overridden.
+    ///                Parameters not listed keep their minimum values.
+    /// engine_events: a vector containing (frame_num, EngineEvent) pairs to iterate over.
+    /// comparison_buffer: a vector of MonoFrames to compare the results against.
+    ///                    the test will generate the same number of samples as the length
+    ///                    of the comparison buffer.
+    fn _test(params_to_set: Vec<(ParameterId, &str)>,
+             engine_events: Vec<(usize, EngineEvent)>,
+             comparison_buffer: Vec<defs::MonoFrame>) {
+
+        // setup
+        let mut dahdsr = Dahdsr::new();
+        let sample_rate = 1.0;
+
+        let params = BaseliskPluginParameters::default();
+        for param in &[ParameterId::DahdsrDelay,
+                       ParameterId::DahdsrAttack,
+                       ParameterId::DahdsrHold,
+                       ParameterId::DahdsrDecay,
+                       ParameterId::DahdsrSustain,
+                       ParameterId::DahdsrRelease] {
+            params.set_parameter(*param, 0.0);
+        }
+        for (param, value) in params_to_set {
+            params.update_real_value_from_string(param, String::from(value)).unwrap();
+        }
+
+        let mut buffer = vec![[0.0]; comparison_buffer.len()];
+
+        // test
+        dahdsr.process_buffer(&mut buffer, engine_events.iter(), sample_rate, &params);
+
+        // verify results
+        for i in 0..buffer.len() {
+            let error_abs = defs::Sample::abs(buffer[i][0] - comparison_buffer[i][0]);
+            if error_abs > std::f32::EPSILON {
+                panic!("For sample index {}, actual output == {}, expected == {}, absolute error = {}",
+                       i, buffer[i][0], comparison_buffer[i][0], error_abs);
+            }
+        }
+    }
+}
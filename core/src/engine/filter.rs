@@ -1,7 +1,10 @@
 extern crate sample;
 
 use defs;
-use engine::traits;
+use engine::{
+    traits,
+    zdf_filter::{LadderFilter, StateVariableFilter},
+};
 use shared::{
     event::EngineEvent,
     parameter::{
@@ -12,16 +15,268 @@ use shared::{
 use sample::{Frame, slice};
 use std::default::Default;
 
-/// A low pass filter type that can be used for audio processing.
-/// This is to be a constant-peak-gain two-pole resonator with
-/// parameterized cutoff frequency and resonance.
+/// Compute the filter cutoff frequency.
+/// The base frequency is swept upwards by the envelope (0 <= x <= 1) across
+/// adsr_sweep_octaves, and offset by the key tracking octaves.
+fn get_cutoff_frequency(base_frequency_hz: defs::Sample,
+                        adsr_sweep_octaves: defs::Sample,
+                        adsr_input_sample: defs::Sample,
+                        key_tracking_octaves: defs::Sample) -> defs::Sample
+{
+    base_frequency_hz * defs::Sample::exp2(
+        adsr_sweep_octaves * adsr_input_sample + key_tracking_octaves)
+}
+
+/// Compute the key tracking offset in octaves for a note.
+/// At 100% key tracking the cutoff follows the played note exactly,
+/// relative to key_tracking_center_note.
+fn get_key_tracking_octaves(note: u8,
+                            key_tracking: defs::Sample,
+                            key_tracking_center_note: defs::Sample) -> defs::Sample
+{
+    key_tracking * (defs::Sample::from(note) - key_tracking_center_note) / 12.0
+}
+
+/// Drive gain (6 dB) from which the input of the filter is fully saturated.
+const FULL_SATURATION_DRIVE_GAIN: defs::Sample = 2.0;
+
+/// Drive the input of the filter into saturation.
+/// With no drive (a gain of 1), the input passes through unchanged. Up to
+/// FULL_SATURATION_DRIVE_GAIN, the saturated input is crossfaded in, so that
+/// loud input doesn't drop in level as soon as the drive is raised.
+fn saturate_input(sample: defs::Sample, drive_gain: defs::Sample) -> defs::Sample {
+    if drive_gain <= 1.0 {
+        return sample
+    }
+    let saturated = (drive_gain * sample).tanh();
+    let mix = defs::Sample::min(
+        (drive_gain - 1.0) / (FULL_SATURATION_DRIVE_GAIN - 1.0), 1.0);
+    sample + mix * (saturated - sample)
+}
+
+/// How long the filter of the previous type, model or slope keeps running after
+/// it changes, while it is crossfaded out.
+const TYPE_CROSSFADE_TIME: defs::Sample = 0.01;
+
+/// Responses that the filter can have.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterType {
+    Lowpass,
+    Highpass,
+    Bandpass,
+    Notch,
+    Peaking,
+    Allpass,
+}
+
+impl FilterType {
+    /// Get the filter type corresponding to the value of the FilterType parameter.
+    pub fn from_value(value: defs::Sample) -> Self {
+        match value as usize {
+            0 => FilterType::Lowpass,
+            1 => FilterType::Highpass,
+            2 => FilterType::Bandpass,
+            3 => FilterType::Notch,
+            4 => FilterType::Peaking,
+            5 => FilterType::Allpass,
+            _ => panic!("Unknown filter type"),
+        }
+    }
+
+    /// Compute the biquad coefficients for this filter type.
+    /// gain_db is only used by the peaking filter.
+    fn set_biquad_consts(self,
+                         frequency_hz: defs::Sample,
+                         quality_factor: defs::Sample,
+                         gain_db: defs::Sample,
+                         sample_rate: defs::Sample,
+                         coeffs: &mut BiquadCoefficients)
+    {
+        match self {
+            FilterType::Lowpass => get_lowpass_second_order_biquad_consts(
+                frequency_hz, quality_factor, sample_rate, coeffs),
+            FilterType::Highpass => get_highpass_second_order_biquad_consts(
+                frequency_hz, quality_factor, sample_rate, coeffs),
+            FilterType::Bandpass => get_bandpass_second_order_biquad_consts(
+                frequency_hz, quality_factor, sample_rate, coeffs),
+            FilterType::Notch => get_notch_second_order_biquad_consts(
+                frequency_hz, quality_factor, sample_rate, coeffs),
+            FilterType::Peaking => get_peaking_second_order_biquad_consts(
+                frequency_hz, quality_factor, gain_db, sample_rate, coeffs),
+            FilterType::Allpass => get_allpass_second_order_biquad_consts(
+                frequency_hz, quality_factor, sample_rate, coeffs),
+        }
+    }
+}
+
+/// Structures that the filter can be modelled with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterModel {
+    Biquad,
+    StateVariable,
+    Ladder,
+}
+
+impl FilterModel {
+    /// Get the filter model corresponding to the value of the FilterModel parameter.
+    pub fn from_value(value: defs::Sample) -> Self {
+        match value as usize {
+            0 => FilterModel::Biquad,
+            1 => FilterModel::StateVariable,
+            2 => FilterModel::Ladder,
+            _ => panic!("Unknown filter model"),
+        }
+    }
+}
+
+/// Most second-order stages that can be cascaded, for a 36 dB per octave slope.
+const MAX_STAGES: usize = 3;
+
+/// Get the number of cascaded second-order stages for the FilterSlope parameter.
+fn get_num_stages(value: defs::Sample) -> usize {
+    match value as usize {
+        0 => 1,
+        1 => 2,
+        2 => 3,
+        _ => panic!("Unknown filter slope"),
+    }
+}
+
+/// Get the quality factor of one stage in a cascade of num_stages second-order stages.
+/// The stages take the pole pairs of a Butterworth filter of the combined order,
+/// scaled so that the resonance parameter affects the cascade as it does a single stage.
+/// At the default quality factor of 0.707 the cascade is maximally flat.
+fn get_stage_quality_factor(stage: usize,
+                            num_stages: usize,
+                            quality_factor: defs::Sample) -> defs::Sample
+{
+    let theta = 0.5 * defs::PI * (2 * stage + 1) as defs::Sample / (2 * num_stages) as defs::Sample;
+    let butterworth_quality_factor = 0.5 / theta.cos();
+    butterworth_quality_factor * quality_factor * defs::Sample::sqrt(2.0)
+}
+
+/// A filter of one type, model and slope, with its state.
+#[derive(Clone)]
+struct FilterSection {
+    model: FilterModel,
+    filter_type: FilterType,
+    num_stages: usize,
+    histories: [BiquadSampleHistory; MAX_STAGES],
+    coeffs: [BiquadCoefficients; MAX_STAGES],
+    svfs: [StateVariableFilter; MAX_STAGES],
+    ladder: LadderFilter,
+    saturate_feedback: bool,
+}
+
+impl FilterSection {
+    fn new() -> Self {
+        Self {
+            model: FilterModel::Biquad,
+            filter_type: FilterType::Lowpass,
+            num_stages: 1,
+            histories: [BiquadSampleHistory::new(), BiquadSampleHistory::new(),
+                        BiquadSampleHistory::new()],
+            coeffs: [BiquadCoefficients::new(), BiquadCoefficients::new(),
+                     BiquadCoefficients::new()],
+            svfs: [StateVariableFilter::new(), StateVariableFilter::new(),
+                   StateVariableFilter::new()],
+            ladder: LadderFilter::new(),
+            saturate_feedback: false,
+        }
+    }
+
+    /// Enable saturation in the feedback path of every model.
+    fn set_feedback_saturation(&mut self, enabled: bool) {
+        self.saturate_feedback = enabled;
+        for svf in self.svfs.iter_mut() {
+            svf.set_feedback_saturation(enabled);
+        }
+        self.ladder.set_feedback_saturation(enabled);
+    }
+
+    fn reset(&mut self) {
+        for history in self.histories.iter_mut() {
+            history.reset();
+        }
+        for svf in self.svfs.iter_mut() {
+            svf.reset();
+        }
+        self.ladder.reset();
+    }
+
+    /// Compute the coefficients of the current model.
+    /// The ladder model is always a four-pole low pass filter, so ignores the
+    /// filter type and slope. The peaking gain is shared between the stages.
+    fn set_coefficients(&mut self,
+                        frequency_hz: defs::Sample,
+                        quality_factor: defs::Sample,
+                        gain_db: defs::Sample,
+                        sample_rate: defs::Sample)
+    {
+        let num_stages = self.num_stages;
+        let stage_gain_db = gain_db / num_stages as defs::Sample;
+        match self.model {
+            FilterModel::Biquad => {
+                for (stage, coeffs) in self.coeffs[..num_stages].iter_mut().enumerate() {
+                    self.filter_type.set_biquad_consts(
+                        frequency_hz,
+                        get_stage_quality_factor(stage, num_stages, quality_factor),
+                        stage_gain_db, sample_rate, coeffs);
+                }
+            },
+            FilterModel::StateVariable => {
+                for (stage, svf) in self.svfs[..num_stages].iter_mut().enumerate() {
+                    svf.set_coefficients(
+                        self.filter_type, frequency_hz,
+                        get_stage_quality_factor(stage, num_stages, quality_factor),
+                        stage_gain_db, sample_rate);
+                }
+            },
+            FilterModel::Ladder => self.ladder.set_coefficients(
+                frequency_hz, quality_factor, sample_rate),
+        }
+    }
+
+    fn process(&mut self, sample: defs::Sample) -> defs::Sample {
+        let num_stages = self.num_stages;
+        let saturate_feedback = self.saturate_feedback;
+        match self.model {
+            FilterModel::Biquad => self.histories[..num_stages].iter_mut()
+                .zip(self.coeffs.iter())
+                .fold(sample, |sample, (history, coeffs)| {
+                    let output = process_biquad(history, coeffs, sample);
+                    // Saturate the output as it is fed back, but not as it is output.
+                    if saturate_feedback {
+                        history.y1 = output.tanh();
+                    }
+                    output
+                }),
+            FilterModel::StateVariable => self.svfs[..num_stages].iter_mut()
+                .fold(sample, |sample, svf| svf.process(sample)),
+            FilterModel::Ladder => self.ladder.process(sample),
+        }
+    }
+}
+
+/// A multimode filter type that can be used for audio processing.
+/// This is a two-pole biquad filter, state variable filter or four-pole
+/// ladder filter, with parameterized response type, cutoff frequency and resonance.
 pub struct Filter
 {
     sample_rate: defs::Sample,
+    note: Option<u8>,
     last_adsr_input_sample_bits: u32,
-    biquad_coefficient_func: Option<BiquadCoefficientGeneratorFunc>,
-    history: BiquadSampleHistory,
-    coeffs: BiquadCoefficients,
+    section: FilterSection,
+    // Gain applied to the input before it is saturated, or 1 for no saturation
+    drive_gain: defs::Sample,
+    // When the filter type, model or slope changes, the previous filter keeps
+    // running and is crossfaded out, so that switching doesn't click.
+    fade_out_section: Option<FilterSection>,
+    fade_out_samples: usize,
+    fade_out_samples_remaining: usize,
+    // A change made during a crossfade, which is started once the crossfade
+    // finishes. Replacing the section that is fading out would click.
+    pending_section: Option<(FilterModel, FilterType, usize)>,
 }
 
 impl Filter
@@ -30,11 +285,55 @@ impl Filter
     pub fn new() -> Self {
         Self {
             sample_rate: 0.0,
+            note: None,
             last_adsr_input_sample_bits: 0,
-            biquad_coefficient_func: Some(get_lowpass_second_order_biquad_consts),
-            history: BiquadSampleHistory::new(),
-            coeffs: BiquadCoefficients::new(),
+            section: FilterSection::new(),
+            drive_gain: 1.0,
+            fade_out_section: None,
+            fade_out_samples: 1,
+            fade_out_samples_remaining: 0,
+            pending_section: None,
+        }
+    }
+
+    /// Switch to a new filter type, model or slope, crossfading from the current one.
+    fn change_section(&mut self,
+                      model: FilterModel,
+                      filter_type: FilterType,
+                      num_stages: usize)
+    {
+        self.fade_out_section = Some(self.section.clone());
+        self.fade_out_samples = usize::max((TYPE_CROSSFADE_TIME * self.sample_rate) as usize, 1);
+        self.fade_out_samples_remaining = self.fade_out_samples;
+        // The state of a model or stage that wasn't running is stale,
+        // so start it afresh.
+        if model != self.section.model || num_stages != self.section.num_stages {
+            self.section.reset();
+        }
+        self.section.model = model;
+        self.section.filter_type = filter_type;
+        self.section.num_stages = num_stages;
+        // The new section needs coefficients for its model and type
+        self.last_adsr_input_sample_bits = u32::max_value();
+    }
+
+    /// Filter a sample, crossfading from the previous filter if the type, model
+    /// or slope changed recently.
+    fn process_sample(&mut self, sample: defs::Sample) -> defs::Sample {
+        let sample = saturate_input(sample, self.drive_gain);
+        let output = self.section.process(sample);
+        let fade_out_output = match self.fade_out_section {
+            Some(ref mut fade_out_section) => fade_out_section.process(sample),
+            None => return output,
+        };
+
+        let fade_out_gain = self.fade_out_samples_remaining as defs::Sample
+            / self.fade_out_samples as defs::Sample;
+        self.fade_out_samples_remaining -= 1;
+        if self.fade_out_samples_remaining == 0 {
+            self.fade_out_section = None;
         }
+        output + fade_out_gain * (fade_out_output - output)
     }
 
     pub fn process_buffer(&mut self,
@@ -55,11 +354,26 @@ impl Filter
             // This block continues on events that are unimportant to this processor.
             if let Some((frame_num, engine_event)) = next_event {
                 match engine_event {
+                    // New notes will trigger keyframes, for key tracking.
+                    // Note releases don't, so the cutoff stays put during the release.
+                    EngineEvent::NoteChange{ note, .. } => {
+                        if note.is_none() {
+                            continue
+                        }
+                    },
                     EngineEvent::ModulateParameter { param_id, .. } => match *param_id {
                         // All filter events will trigger keyframes
                         ParameterId::FilterFrequency |
                         ParameterId::FilterQuality |
-                        ParameterId::FilterSweepRange => (),
+                        ParameterId::FilterSweepRange |
+                        ParameterId::FilterKeyTracking |
+                        ParameterId::FilterKeyTrackingCenter |
+                        ParameterId::FilterType |
+                        ParameterId::FilterGain |
+                        ParameterId::FilterModel |
+                        ParameterId::FilterSlope |
+                        ParameterId::FilterDrive |
+                        ParameterId::FilterFeedbackSaturation => (),
                         _ => continue,
                     },
                     _ => continue,
@@ -77,44 +391,86 @@ impl Filter
                 let adsr_input_buffer_slice = adsr_input_buffer.get(
                         this_keyframe..next_keyframe).unwrap();
 
+                let model = FilterModel::from_value(
+                    params.get_real_value(ParameterId::FilterModel));
+                let filter_type = FilterType::from_value(
+                    params.get_real_value(ParameterId::FilterType));
+                let num_stages = get_num_stages(params.get_real_value(ParameterId::FilterSlope));
+                if model == self.section.model &&
+                    filter_type == self.section.filter_type &&
+                    num_stages == self.section.num_stages
+                {
+                    self.pending_section = None;
+                } else if self.fade_out_section.is_some() {
+                    self.pending_section = Some((model, filter_type, num_stages));
+                } else {
+                    self.change_section(model, filter_type, num_stages);
+                }
+
+                let saturate_feedback =
+                    params.get_real_value(ParameterId::FilterFeedbackSaturation) > 0.0;
+                self.section.set_feedback_saturation(saturate_feedback);
+                if let Some(ref mut fade_out_section) = self.fade_out_section {
+                    fade_out_section.set_feedback_saturation(saturate_feedback);
+                }
+                self.drive_gain = defs::Sample::powf(
+                    10.0, params.get_real_value(ParameterId::FilterDrive) / 20.0);
+
                 let quality_factor = params.get_real_value(ParameterId::FilterQuality);
+                let gain_db = params.get_real_value(ParameterId::FilterGain);
                 let base_frequency_hz = params.get_real_value(ParameterId::FilterFrequency);
                 let adsr_sweep_octaves = params.get_real_value(ParameterId::FilterSweepRange);
+                let key_tracking_octaves = match self.note {
+                    Some(note) => get_key_tracking_octaves(
+                        note,
+                        params.get_real_value(ParameterId::FilterKeyTracking),
+                        params.get_real_value(ParameterId::FilterKeyTrackingCenter)),
+                    None => 0.0,
+                };
 
                 // This forces the biquad coefficients to be computed at least once this slice:
                 self.last_adsr_input_sample_bits = u32::max_value();
 
-                if let Some(biquad_coefficient_func) = self.biquad_coefficient_func {
-                    // Iterate over two buffer slices at once using a zip method
-                    slice::zip_map_in_place(output_buffer_slice, adsr_input_buffer_slice,
-                                            |output_frame, adsr_input_frame|
+                // Iterate over two buffer slices at once using a zip method
+                slice::zip_map_in_place(output_buffer_slice, adsr_input_buffer_slice,
+                                        |output_frame, adsr_input_frame|
+                {
+                    // Iterate over the samples in each frame using a zip method
+                    output_frame.zip_map(adsr_input_frame,
+                                         |sample, adsr_input_sample|
                     {
-                        // Iterate over the samples in each frame using a zip method
-                        output_frame.zip_map(adsr_input_frame,
-                                             |sample, adsr_input_sample|
-                        {
-                            // Optimization: don't recompute the coefficients if they haven't changed
-                            // since last iteration.
-                            let adsr_input_sample_bits = adsr_input_sample.to_bits();
-                            if self.last_adsr_input_sample_bits != adsr_input_sample_bits {
-                                self.last_adsr_input_sample_bits = adsr_input_sample_bits;
-
-                                // Use adsr_input (0 <= x <= 1) to determine the influence
-                                // of params.adsr_sweep_octaves on the filter frequency.
-                                let frequency_hz = base_frequency_hz
-                                    * defs::Sample::exp2(adsr_sweep_octaves * adsr_input_sample);
-
-                                (biquad_coefficient_func)(
-                                        frequency_hz, quality_factor, self.sample_rate, &mut self.coeffs);
+                        // Start a change made during the previous crossfade
+                        if self.fade_out_section.is_none() {
+                            if let Some((model, filter_type, num_stages)) = self.pending_section.take() {
+                                self.change_section(model, filter_type, num_stages);
                             }
+                        }
 
-                            process_biquad(
-                                &mut self.history,
-                                &self.coeffs,
-                                sample)
-                        })
-                    });
-                }
+                        // Optimization: don't recompute the coefficients if they haven't changed
+                        // since last iteration.
+                        let adsr_input_sample_bits = adsr_input_sample.to_bits();
+                        if self.last_adsr_input_sample_bits != adsr_input_sample_bits {
+                            self.last_adsr_input_sample_bits = adsr_input_sample_bits;
+
+                            // Use adsr_input (0 <= x <= 1) to determine the influence
+                            // of params.adsr_sweep_octaves on the filter frequency.
+                            let frequency_hz = get_cutoff_frequency(
+                                base_frequency_hz,
+                                adsr_sweep_octaves,
+                                adsr_input_sample,
+                                key_tracking_octaves);
+
+                            self.section.set_coefficients(
+                                frequency_hz, quality_factor, gain_db, self.sample_rate);
+                            if let Some(ref mut fade_out_section) = self.fade_out_section {
+                                fade_out_section.set_coefficients(
+                                    frequency_hz, quality_factor, gain_db, self.sample_rate);
+                            }
+                        }
+
+                        self.process_sample(sample)
+                    })
+                });
             } // output_buffer_slice exits scope
 
             // We've reached the next_keyframe.
@@ -128,15 +484,29 @@ impl Filter
                 // Before the next iteration, use the event at this keyframe
                 // to update the current state.
                 let (_, event) = next_event.unwrap();
-                if let EngineEvent::ModulateParameter { param_id, value } = event {
-                   match *param_id {
+                match event {
+                    EngineEvent::NoteChange{ note, .. } => {
+                        if note.is_some() {
+                            self.note = *note;
+                        }
+                    },
+                    EngineEvent::ModulateParameter { param_id, value } => match *param_id {
                         ParameterId::FilterFrequency |
                         ParameterId::FilterQuality |
-                        ParameterId::FilterSweepRange => {
+                        ParameterId::FilterSweepRange |
+                        ParameterId::FilterKeyTracking |
+                        ParameterId::FilterKeyTrackingCenter |
+                        ParameterId::FilterType |
+                        ParameterId::FilterGain |
+                        ParameterId::FilterModel |
+                        ParameterId::FilterSlope |
+                        ParameterId::FilterDrive |
+                        ParameterId::FilterFeedbackSaturation => {
                             params.set_parameter(*param_id, *value);
                         },
                         _ => (),
-                    }
+                    },
+                    _ => (),
                 };
             }
         }
@@ -145,12 +515,18 @@ impl Filter
 
 impl traits::Processor for Filter {
     fn panic(&mut self) {
-        self.history.reset();
+        self.section.reset();
+        self.fade_out_section = None;
+        if let Some((model, filter_type, num_stages)) = self.pending_section.take() {
+            self.section.model = model;
+            self.section.filter_type = filter_type;
+            self.section.num_stages = num_stages;
+        }
     }
 
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct BiquadCoefficients {
     b0: defs::Sample,
     b1: defs::Sample,
@@ -166,7 +542,7 @@ impl BiquadCoefficients {
 }
 
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct BiquadSampleHistory {
     x0: defs::Sample,
     x1: defs::Sample,
@@ -226,13 +602,6 @@ pub fn process_biquad(history: &mut BiquadSampleHistory,
     output_sample
 }
 
-/// Accepts: frequency_hz, quality_factor, sample_rate.
-/// Returns a BiquadCoefficients.
-type BiquadCoefficientGeneratorFunc = fn (defs::Sample,
-                                          defs::Sample,
-                                          defs::Sample,
-                                          &mut BiquadCoefficients);
-
 pub fn get_lowpass_second_order_biquad_consts(frequency_hz: defs::Sample,
                                           quality_factor: defs::Sample,
                                           sample_rate: defs::Sample,
@@ -286,3 +655,472 @@ pub fn get_highpass_second_order_biquad_consts(frequency_hz: defs::Sample,
     coeffs.b1 = -(1.0 + cos_theta_c) / a0;
     coeffs.b2 = coeffs.b0;
 }
+
+/// Band pass filter with a peak gain of 0 dB at frequency_hz.
+pub fn get_bandpass_second_order_biquad_consts(frequency_hz: defs::Sample,
+                                               quality_factor: defs::Sample,
+                                               sample_rate: defs::Sample,
+                                               coeffs: &mut BiquadCoefficients)
+{
+    // Limit frequency_hz to just under half of the sample rate for stability.
+    let frequency_hz = frequency_hz.min(0.495 * sample_rate);
+
+    // Intermediate variables:
+    let theta_c = defs::TWOPI * frequency_hz / sample_rate;
+    let cos_theta_c = theta_c.cos();
+    let alpha = theta_c.sin() / (2.0 * quality_factor);
+
+    // Calculate the coefficients.
+    // a0 was divided off from each one to save on computation.
+    let a0_inv = 1.0 / (1.0 + alpha);
+
+    coeffs.negative_a1 = 2.0 * cos_theta_c * a0_inv;
+    coeffs.negative_a2 = (alpha - 1.0) * a0_inv;
+
+    coeffs.b0 = alpha * a0_inv;
+    coeffs.b1 = 0.0;
+    coeffs.b2 = -coeffs.b0;
+}
+
+/// Notch (band stop) filter, with zero gain at frequency_hz.
+pub fn get_notch_second_order_biquad_consts(frequency_hz: defs::Sample,
+                                            quality_factor: defs::Sample,
+                                            sample_rate: defs::Sample,
+                                            coeffs: &mut BiquadCoefficients)
+{
+    // Limit frequency_hz to just under half of the sample rate for stability.
+    let frequency_hz = frequency_hz.min(0.495 * sample_rate);
+
+    // Intermediate variables:
+    let theta_c = defs::TWOPI * frequency_hz / sample_rate;
+    let cos_theta_c = theta_c.cos();
+    let alpha = theta_c.sin() / (2.0 * quality_factor);
+
+    // Calculate the coefficients.
+    // a0 was divided off from each one to save on computation.
+    let a0_inv = 1.0 / (1.0 + alpha);
+
+    coeffs.negative_a1 = 2.0 * cos_theta_c * a0_inv;
+    coeffs.negative_a2 = (alpha - 1.0) * a0_inv;
+
+    coeffs.b0 = a0_inv;
+    coeffs.b1 = -coeffs.negative_a1; // b1 = a1
+    coeffs.b2 = coeffs.b0;
+}
+
+/// Peaking filter, which boosts (or cuts) frequencies around frequency_hz by gain_db.
+pub fn get_peaking_second_order_biquad_consts(frequency_hz: defs::Sample,
+                                              quality_factor: defs::Sample,
+                                              gain_db: defs::Sample,
+                                              sample_rate: defs::Sample,
+                                              coeffs: &mut BiquadCoefficients)
+{
+    // Limit frequency_hz to just under half of the sample rate for stability.
+    let frequency_hz = frequency_hz.min(0.495 * sample_rate);
+
+    // Intermediate variables:
+    let amplitude = defs::Sample::powf(10.0, gain_db / 40.0);
+    let theta_c = defs::TWOPI * frequency_hz / sample_rate;
+    let cos_theta_c = theta_c.cos();
+    let alpha = theta_c.sin() / (2.0 * quality_factor);
+
+    // Calculate the coefficients.
+    // a0 was divided off from each one to save on computation.
+    let a0_inv = 1.0 / (1.0 + alpha / amplitude);
+
+    coeffs.negative_a1 = 2.0 * cos_theta_c * a0_inv;
+    coeffs.negative_a2 = (alpha / amplitude - 1.0) * a0_inv;
+
+    coeffs.b0 = (1.0 + alpha * amplitude) * a0_inv;
+    coeffs.b1 = -coeffs.negative_a1; // b1 = a1
+    coeffs.b2 = (1.0 - alpha * amplitude) * a0_inv;
+}
+
+/// All pass filter, which passes all frequencies at unity gain
+/// but shifts their phase, by 180 degrees at frequency_hz.
+pub fn get_allpass_second_order_biquad_consts(frequency_hz: defs::Sample,
+                                              quality_factor: defs::Sample,
+                                              sample_rate: defs::Sample,
+                                              coeffs: &mut BiquadCoefficients)
+{
+    // Limit frequency_hz to just under half of the sample rate for stability.
+    let frequency_hz = frequency_hz.min(0.495 * sample_rate);
+
+    // Intermediate variables:
+    let theta_c = defs::TWOPI * frequency_hz / sample_rate;
+    let cos_theta_c = theta_c.cos();
+    let alpha = theta_c.sin() / (2.0 * quality_factor);
+
+    // Calculate the coefficients.
+    // a0 was divided off from each one to save on computation.
+    let a0_inv = 1.0 / (1.0 + alpha);
+
+    coeffs.negative_a1 = 2.0 * cos_theta_c * a0_inv;
+    coeffs.negative_a2 = (alpha - 1.0) * a0_inv;
+
+    coeffs.b0 = (1.0 - alpha) * a0_inv;
+    coeffs.b1 = -coeffs.negative_a1; // b1 = a1
+    coeffs.b2 = 1.0; // b2 = a0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32;
+
+    // Utility method to allow error tolerance in float calcs
+    fn assert_float_eq(actual: f32, expected: f32) {
+        let error_abs = f32::abs(actual - expected);
+        assert!(error_abs < 1.0e-3,
+                "actual = {}, expected = {}, absolute error = {}",
+                actual, expected, error_abs);
+    }
+
+    #[test]
+    fn test_key_tracking_full() {
+        // At 100% key tracking, an octave above the center note doubles the cutoff
+        let octaves = get_key_tracking_octaves(72, 1.0, 60.0);
+        assert_float_eq(get_cutoff_frequency(1000.0, 0.0, 0.0, octaves), 2000.0);
+
+        let octaves = get_key_tracking_octaves(48, 1.0, 60.0);
+        assert_float_eq(get_cutoff_frequency(1000.0, 0.0, 0.0, octaves), 500.0);
+    }
+
+    #[test]
+    fn test_key_tracking_center_note() {
+        // The center note leaves the cutoff unchanged at any amount
+        let octaves = get_key_tracking_octaves(64, 2.0, 64.0);
+        assert_float_eq(get_cutoff_frequency(1000.0, 0.0, 0.0, octaves), 1000.0);
+    }
+
+    #[test]
+    fn test_key_tracking_with_sweep() {
+        // Half key tracking two octaves up, plus a one octave envelope sweep
+        let octaves = get_key_tracking_octaves(84, 0.5, 60.0);
+        assert_float_eq(get_cutoff_frequency(1000.0, 2.0, 0.5, octaves), 4000.0);
+    }
+
+    #[test]
+    fn test_key_tracking_off() {
+        let octaves = get_key_tracking_octaves(100, 0.0, 60.0);
+        assert_float_eq(get_cutoff_frequency(1000.0, 0.0, 0.0, octaves), 1000.0);
+    }
+
+    const SAMPLE_RATE: defs::Sample = 48000.0;
+
+    /// Evaluate the magnitude response of biquad coefficients at a frequency,
+    /// from their transfer function.
+    fn _biquad_magnitude(coeffs: &BiquadCoefficients, frequency_hz: defs::Sample) -> defs::Sample {
+        let w = defs::TWOPI * frequency_hz / SAMPLE_RATE;
+        let (cos_w, sin_w) = (w.cos(), w.sin());
+        let (cos_2w, sin_2w) = ((2.0 * w).cos(), (2.0 * w).sin());
+        let num_re = coeffs.b0 + coeffs.b1 * cos_w + coeffs.b2 * cos_2w;
+        let num_im = -coeffs.b1 * sin_w - coeffs.b2 * sin_2w;
+        let den_re = 1.0 - coeffs.negative_a1 * cos_w - coeffs.negative_a2 * cos_2w;
+        let den_im = coeffs.negative_a1 * sin_w + coeffs.negative_a2 * sin_2w;
+        ((num_re * num_re + num_im * num_im) / (den_re * den_re + den_im * den_im)).sqrt()
+    }
+
+    /// Evaluate the magnitude response of a filter type with a cutoff of 1 kHz
+    /// at a frequency.
+    fn _magnitude(filter_type: FilterType, frequency_hz: defs::Sample) -> defs::Sample {
+        let mut coeffs = BiquadCoefficients::new();
+        filter_type.set_biquad_consts(1000.0, 0.707, 6.0, SAMPLE_RATE, &mut coeffs);
+        _biquad_magnitude(&coeffs, frequency_hz)
+    }
+
+    /// Evaluate the magnitude response of a cascade of biquad stages with a cutoff
+    /// of 1 kHz and the default resonance at a frequency.
+    fn _cascade_magnitude(filter_type: FilterType,
+                          num_stages: usize,
+                          frequency_hz: defs::Sample) -> defs::Sample
+    {
+        let mut section = FilterSection::new();
+        section.filter_type = filter_type;
+        section.num_stages = num_stages;
+        section.set_coefficients(1000.0, f32::consts::FRAC_1_SQRT_2, 0.0, SAMPLE_RATE);
+        section.coeffs[..num_stages].iter()
+            .map(|coeffs| _biquad_magnitude(coeffs, frequency_hz))
+            .product()
+    }
+
+    /// Get the expected magnitude response of an analog Butterworth low pass filter,
+    /// at a frequency relative to the cutoff.
+    fn _butterworth_magnitude(order: i32, relative_frequency: defs::Sample) -> defs::Sample {
+        1.0 / (1.0 + relative_frequency.powi(2 * order)).sqrt()
+    }
+
+    fn assert_relative_eq(actual: defs::Sample, expected: defs::Sample) {
+        assert!((actual / expected - 1.0).abs() < 0.02,
+                "actual = {}, expected = {}", actual, expected);
+    }
+
+    #[test]
+    fn test_lowpass_response() {
+        assert_float_eq(_magnitude(FilterType::Lowpass, 10.0), 1.0);
+        assert!(_magnitude(FilterType::Lowpass, 20000.0) < 0.01);
+    }
+
+    #[test]
+    fn test_highpass_response() {
+        assert!(_magnitude(FilterType::Highpass, 10.0) < 0.01);
+        assert_float_eq(_magnitude(FilterType::Highpass, 20000.0), 1.0);
+    }
+
+    #[test]
+    fn test_bandpass_response() {
+        assert_float_eq(_magnitude(FilterType::Bandpass, 1000.0), 1.0);
+        assert!(_magnitude(FilterType::Bandpass, 1.0) < 0.01);
+        assert!(_magnitude(FilterType::Bandpass, 23000.0) < 0.01);
+    }
+
+    #[test]
+    fn test_notch_response() {
+        assert_float_eq(_magnitude(FilterType::Notch, 1000.0), 0.0);
+        assert_float_eq(_magnitude(FilterType::Notch, 10.0), 1.0);
+        assert_float_eq(_magnitude(FilterType::Notch, 23000.0), 1.0);
+    }
+
+    #[test]
+    fn test_peaking_response() {
+        // +6 dB at the center frequency, unity gain far from it
+        assert_float_eq(_magnitude(FilterType::Peaking, 1000.0), 1.9953);
+        assert_float_eq(_magnitude(FilterType::Peaking, 10.0), 1.0);
+        assert_float_eq(_magnitude(FilterType::Peaking, 23000.0), 1.0);
+    }
+
+    #[test]
+    fn test_allpass_response() {
+        for frequency_hz in &[10.0, 500.0, 1000.0, 5000.0, 23000.0] {
+            assert_float_eq(_magnitude(FilterType::Allpass, *frequency_hz), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_saturate_input() {
+        // No drive is clean, even for loud input,
+        assert_float_eq(saturate_input(2.0, 1.0), 2.0);
+        // and a little drive barely changes it.
+        let drive_gain = defs::Sample::powf(10.0, 0.1 / 20.0);
+        for sample in &[2.0, -2.0] {
+            let output = saturate_input(*sample, drive_gain);
+            assert!((output - sample).abs() < 0.02, "output == {}", output);
+        }
+        // Quiet input is amplified by the drive
+        assert_float_eq(saturate_input(0.001, 10.0), 0.01);
+        // Loud input is limited
+        assert_float_eq(saturate_input(-1.0, 10.0), -1.0);
+    }
+
+    #[test]
+    /// Feedback saturation keeps a highly resonant biquad from ringing
+    /// far above the level of its input.
+    fn test_biquad_feedback_saturation() {
+        let peak_amplitude = |saturate_feedback: bool| {
+            let mut section = FilterSection::new();
+            section.set_feedback_saturation(saturate_feedback);
+            section.set_coefficients(1000.0, 10.0, 0.0, SAMPLE_RATE);
+            (0..4800)
+                .map(|i| defs::TWOPI * 1000.0 * i as defs::Sample / SAMPLE_RATE)
+                .map(|phase| section.process(phase.sin()).abs())
+                .fold(0.0, defs::Sample::max)
+        };
+        let clean_peak = peak_amplitude(false);
+        let saturated_peak = peak_amplitude(true);
+        assert!(clean_peak > 9.0, "clean_peak == {}", clean_peak);
+        assert!(saturated_peak < 0.5 * clean_peak, "saturated_peak == {}", saturated_peak);
+    }
+
+    #[test]
+    fn test_stage_quality_factors() {
+        let expected: [&[defs::Sample]; 3] = [
+            &[f32::consts::FRAC_1_SQRT_2],
+            &[0.5412, 1.3066],
+            &[0.5176, f32::consts::FRAC_1_SQRT_2, 1.9319],
+        ];
+        for (num_stages, expected_quality_factors) in (1..=MAX_STAGES).zip(expected.iter()) {
+            for (stage, expected_quality_factor) in expected_quality_factors.iter().enumerate() {
+                assert_float_eq(
+                    get_stage_quality_factor(stage, num_stages, f32::consts::FRAC_1_SQRT_2),
+                    *expected_quality_factor);
+            }
+        }
+        // Resonance scales every stage
+        assert_float_eq(get_stage_quality_factor(0, 1, 5.0), 5.0);
+        assert_float_eq(get_stage_quality_factor(1, 2, 2.0), 2.0 * 1.3066 * f32::consts::SQRT_2);
+    }
+
+    #[test]
+    /// The cascades have Butterworth responses: -3 dB at the cutoff,
+    /// then falling at 12 dB per octave per stage.
+    fn test_slope_lowpass_response() {
+        for num_stages in 1..=MAX_STAGES {
+            let order = 2 * num_stages as i32;
+            assert_relative_eq(
+                _cascade_magnitude(FilterType::Lowpass, num_stages, 1000.0),
+                f32::consts::FRAC_1_SQRT_2);
+            assert_relative_eq(
+                _cascade_magnitude(FilterType::Lowpass, num_stages, 500.0),
+                _butterworth_magnitude(order, 0.5));
+            // Bilinear transform warps frequencies above the cutoff slightly,
+            // so compare with the analog response at the warped frequency.
+            let warped_frequency = (defs::PI * 2000.0 / SAMPLE_RATE).tan()
+                / (defs::PI * 1000.0 / SAMPLE_RATE).tan();
+            assert_relative_eq(
+                _cascade_magnitude(FilterType::Lowpass, num_stages, 2000.0),
+                _butterworth_magnitude(order, warped_frequency));
+
+            let octave_db = 20.0 * defs::Sample::log10(
+                _cascade_magnitude(FilterType::Lowpass, num_stages, 2000.0)
+                / _cascade_magnitude(FilterType::Lowpass, num_stages, 4000.0));
+            let expected_octave_db = 12.0 * num_stages as defs::Sample;
+            assert!(octave_db > expected_octave_db
+                        && octave_db < expected_octave_db + num_stages as defs::Sample,
+                    "{} stages: {} dB per octave", num_stages, octave_db);
+        }
+    }
+
+    #[test]
+    fn test_slope_highpass_response() {
+        for num_stages in 1..=MAX_STAGES {
+            let order = 2 * num_stages as i32;
+            assert_relative_eq(
+                _cascade_magnitude(FilterType::Highpass, num_stages, 1000.0),
+                f32::consts::FRAC_1_SQRT_2);
+            let warped_frequency = (defs::PI * 1000.0 / SAMPLE_RATE).tan()
+                / (defs::PI * 500.0 / SAMPLE_RATE).tan();
+            assert_relative_eq(
+                _cascade_magnitude(FilterType::Highpass, num_stages, 500.0),
+                _butterworth_magnitude(order, warped_frequency));
+            assert_relative_eq(
+                _cascade_magnitude(FilterType::Highpass, num_stages, 4000.0), 1.0);
+        }
+    }
+
+    #[test]
+    /// The state variable model cascades in the same way.
+    fn test_slope_state_variable_response() {
+        let peak_amplitude = |frequency_hz: defs::Sample| {
+            let mut section = FilterSection::new();
+            section.model = FilterModel::StateVariable;
+            section.num_stages = 2;
+            section.set_coefficients(1000.0, f32::consts::FRAC_1_SQRT_2, 0.0, SAMPLE_RATE);
+            let num_samples = 48000;
+            let output: Vec<defs::Sample> = (0..num_samples)
+                .map(|i| defs::TWOPI * frequency_hz * i as defs::Sample / SAMPLE_RATE)
+                .map(|phase| section.process(phase.sin()))
+                .collect();
+            output[num_samples / 2..].iter()
+                .fold(0.0, |peak: defs::Sample, sample| peak.max(sample.abs()))
+        };
+        // A flat passband, -3 dB at the cutoff and a four-pole rolloff,
+        // as for a Butterworth filter.
+        let passband = peak_amplitude(250.0);
+        assert!((passband - 1.0).abs() < 0.01, "passband == {}", passband);
+        let cutoff = peak_amplitude(1000.0);
+        assert!((cutoff - f32::consts::FRAC_1_SQRT_2).abs() < 0.01, "cutoff == {}", cutoff);
+        let warped_frequency = (defs::PI * 4000.0 / SAMPLE_RATE).tan()
+            / (defs::PI * 1000.0 / SAMPLE_RATE).tan();
+        let stopband = peak_amplitude(4000.0) / _butterworth_magnitude(4, warped_frequency);
+        assert!((stopband - 1.0).abs() < 0.1, "stopband == {}", stopband);
+    }
+
+    #[test]
+    /// Changing the filter type crossfades from the previous type,
+    /// rather than jumping straight to the output of the new one.
+    fn test_type_change_crossfade() {
+        let params = BaseliskPluginParameters::default();
+        let adsr_input = vec![[0.0]; 1000];
+        let input: Vec<defs::MonoFrame> = (0..1000)
+            .map(|i| [(defs::TWOPI * 440.0 * i as defs::Sample / SAMPLE_RATE).sin()])
+            .collect();
+        let events: Vec<(usize, EngineEvent)> = vec![];
+
+        // Reference filter that stays as a lowpass filter
+        let mut reference = Filter::new();
+        let mut reference_output = input.clone();
+        reference.process_buffer(
+            &adsr_input, &mut reference_output, events.iter(), SAMPLE_RATE, &params);
+
+        // Filter that changes type between buffers
+        let mut filter = Filter::new();
+        let mut output = input.clone();
+        filter.process_buffer(&adsr_input, &mut output, events.iter(), SAMPLE_RATE, &params);
+        assert_eq!(output, reference_output);
+
+        reference_output = input.clone();
+        reference.process_buffer(
+            &adsr_input, &mut reference_output, events.iter(), SAMPLE_RATE, &params);
+        params.update_real_value_from_string(
+            ParameterId::FilterType, String::from("highpass")).unwrap();
+        output = input.clone();
+        filter.process_buffer(&adsr_input, &mut output, events.iter(), SAMPLE_RATE, &params);
+
+        // The first sample after the change is entirely from the previous type
+        assert_float_eq(output[0][0], reference_output[0][0]);
+        // and the crossfade has finished within the buffer.
+        assert!(filter.fade_out_section.is_none());
+        assert_eq!(filter.section.filter_type, FilterType::Highpass);
+    }
+
+    #[test]
+    /// Changing the type again during a crossfade finishes the crossfade first,
+    /// rather than jumping to the output of the section that was fading in.
+    fn test_type_change_during_crossfade() {
+        let params = BaseliskPluginParameters::default();
+        let input: Vec<defs::MonoFrame> = (0..1200)
+            .map(|i| [(defs::TWOPI * 440.0 * i as defs::Sample / SAMPLE_RATE).sin()])
+            .collect();
+        let events: Vec<(usize, EngineEvent)> = vec![];
+        let mut filter = Filter::new();
+        let mut output = Vec::new();
+        let mut run = |filter: &mut Filter, range: std::ops::Range<usize>| {
+            let adsr_input = vec![[0.0]; range.len()];
+            let mut buffer = input[range].to_vec();
+            filter.process_buffer(&adsr_input, &mut buffer, events.iter(), SAMPLE_RATE, &params);
+            output.extend(buffer);
+        };
+
+        run(&mut filter, 0..100);
+        params.update_real_value_from_string(
+            ParameterId::FilterType, String::from("highpass")).unwrap();
+        run(&mut filter, 100..150);
+        params.update_real_value_from_string(
+            ParameterId::FilterType, String::from("bandpass")).unwrap();
+        run(&mut filter, 150..200);
+        // The crossfade to highpass is still going
+        assert_eq!(filter.section.filter_type, FilterType::Highpass);
+        run(&mut filter, 200..1200);
+        assert!(filter.fade_out_section.is_none());
+        assert_eq!(filter.section.filter_type, FilterType::Bandpass);
+
+        // No jumps larger than the steps of the input sine
+        let max_step = output.windows(2)
+            .fold(0.0, |max: defs::Sample, pair| max.max((pair[1][0] - pair[0][0]).abs()));
+        assert!(max_step < 0.1, "max_step == {}", max_step);
+    }
+
+    #[test]
+    /// Changing the filter model starts the new model afresh, and crossfades
+    /// from the previous one.
+    fn test_model_change() {
+        let params = BaseliskPluginParameters::default();
+        let adsr_input = vec![[0.0]; 9600];
+        let events: Vec<(usize, EngineEvent)> = vec![];
+        let mut filter = Filter::new();
+        let mut output = vec![[1.0]; 9600];
+        filter.process_buffer(&adsr_input, &mut output, events.iter(), SAMPLE_RATE, &params);
+
+        params.update_real_value_from_string(
+            ParameterId::FilterModel, String::from("ladder")).unwrap();
+        output = vec![[1.0]; 9600];
+        filter.process_buffer(&adsr_input, &mut output, events.iter(), SAMPLE_RATE, &params);
+
+        // The biquad had settled to unity gain, so the output starts there
+        assert_float_eq(output[0][0], 1.0);
+        assert!(filter.fade_out_section.is_none());
+        assert_eq!(filter.section.model, FilterModel::Ladder);
+        // and the ladder settles to unity gain too.
+        assert_float_eq(output[9599][0], 1.0);
+    }
+}
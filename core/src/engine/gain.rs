@@ -35,6 +35,23 @@ pub fn process_buffer_fixed_gain(
     })
 }
 
+/// Add an input buffer, scaled by a fixed gain, to the output buffer.
+pub fn add_buffer_with_fixed_gain(
+    gain: defs::Sample,
+    input_buffer: &defs::MonoFrameBufferSlice,
+    output_buffer: &mut defs::MonoFrameBufferSlice,
+)
+{
+    slice::zip_map_in_place(output_buffer, input_buffer,
+                            |output_frame, input_frame|
+    {
+        // Iterate over the samples in each frame using a zip method
+        output_frame.zip_map(input_frame, |output_sample, input_sample| {
+            output_sample + input_sample.mul_amp(gain)
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,4 +84,12 @@ mod tests {
     fn test_silence() {
         _test(0.0, [[0.0]; 5]);
     }
+
+    #[test]
+    fn test_add_buffer_with_fixed_gain() {
+        let input_buffer: [defs::MonoFrame; 3] = [[1.0], [-0.5], [0.25]];
+        let mut output_buffer: [defs::MonoFrame; 3] = [[0.5], [0.5], [0.0]];
+        add_buffer_with_fixed_gain(0.5, &input_buffer, &mut output_buffer);
+        assert_eq!(output_buffer, [[1.0], [0.25], [0.125]]);
+    }
 }
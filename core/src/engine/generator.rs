@@ -1,7 +1,16 @@
 use defs;
 use engine::{
-    pitch_bend,
+    adsr::{Adsr, AdsrId},
+    buffer::ResizableFrameBuffer,
+    gain,
+    pitch_bend::{self, PitchBendPortamento},
     traits,
+    waveform::{self, Waveform},
+};
+use rand::{
+    FromEntropy,
+    Rng,
+    rngs::SmallRng,
 };
 use shared::{
     event::EngineEvent,
@@ -9,45 +18,60 @@ use shared::{
         BaseliskPluginParameters,
         ParameterId,
     },
+    wavetable::{Wavetable, WavetableBank},
 };
 use std::slice;
+use std::sync::Arc;
 
 /// Convert a note number to a corresponding frequency,
 /// using 440 Hz as the pitch of the A above middle C.
-fn get_frequency(note: defs::Sample) -> defs::Sample {
+pub fn get_frequency(note: defs::Sample) -> defs::Sample {
     440.0 * ((note - 69.0) / 12.0).exp2()
 }
 
 /// Internal state used by generator types.
-pub struct State {
+struct State {
     sample_rate: defs::Sample,
     note: u8,
+    velocity: u8,
     pitch_bend_wheel_value: u16,
     base_frequency: defs::Sample,
     target_base_frequency: defs::Sample,
-    pitchbend_portamento_multiplier: defs::Sample,
+    pitchbend_portamento: PitchBendPortamento,
     mod_index: defs::Sample,
     target_mod_index: defs::Sample,
     phase: defs::Sample, // 0 <= phase <= 1
+    waveform: Waveform,
+    pulse_width: defs::Sample,
+    wavetable_position: defs::Sample,
+    feedback: defs::Sample,
+    previous_outputs: [defs::Sample; 2],
 }
 
 impl State {
     pub fn new() -> Self {
         Self {
             note: 69,
+            velocity: 127,
             pitch_bend_wheel_value: 8192,
             base_frequency: 1.0,
             target_base_frequency: 0.0,
-            pitchbend_portamento_multiplier: 1.0,
+            pitchbend_portamento: PitchBendPortamento::new(),
             mod_index: 4.0,
             target_mod_index: 4.0,
             phase: 0.0,
+            waveform: Waveform::Sine,
+            pulse_width: 0.5,
+            wavetable_position: 0.0,
+            feedback: 0.0,
+            previous_outputs: [0.0; 2],
             sample_rate: 0.0,
         }
     }
 
     pub fn panic(&mut self) {
         self.phase = 0.0;
+        self.previous_outputs = [0.0; 2];
     }
 }
 
@@ -55,11 +79,124 @@ impl State {
 pub struct Generator {
     id: usize,
     state: State,
+    envelope: Adsr,
+    envelope_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    wavetable: Option<Arc<Wavetable>>,
+    rng: SmallRng,
+}
+
+/// How a generator's frequency is set.
+enum FrequencyMode {
+    /// An offset in semitones from the note frequency.
+    Semitones,
+    /// A multiple of the note frequency.
+    Ratio,
+    /// A fixed frequency, independent of the note and pitch bend.
+    Fixed,
+}
+
+impl FrequencyMode {
+    fn from_value(value: defs::Sample) -> Self {
+        match value as usize {
+            0 => FrequencyMode::Semitones,
+            1 => FrequencyMode::Ratio,
+            2 => FrequencyMode::Fixed,
+            _ => panic!("Unknown frequency mode"),
+        }
+    }
+}
+
+/// What happens to a generator's phase when a new note starts.
+enum PhaseMode {
+    /// The phase continues from wherever it was.
+    Free,
+    /// The phase restarts from the start phase.
+    Reset,
+    /// The phase restarts from a random point.
+    Random,
+}
+
+impl PhaseMode {
+    fn from_value(value: defs::Sample) -> Self {
+        match value as usize {
+            0 => PhaseMode::Free,
+            1 => PhaseMode::Reset,
+            2 => PhaseMode::Random,
+            _ => panic!("Unknown phase mode"),
+        }
+    }
+}
+
+/// How keyboard scaling changes a generator's mod index with distance
+/// from the breakpoint, on one side of the breakpoint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum KeyScalingCurve {
+    NegativeLinear,
+    NegativeExponential,
+    PositiveExponential,
+    PositiveLinear,
+}
+
+impl KeyScalingCurve {
+    fn from_value(value: defs::Sample) -> Self {
+        match value as usize {
+            0 => KeyScalingCurve::NegativeLinear,
+            1 => KeyScalingCurve::NegativeExponential,
+            2 => KeyScalingCurve::PositiveExponential,
+            3 => KeyScalingCurve::PositiveLinear,
+            _ => panic!("Unknown key scaling curve"),
+        }
+    }
+
+    /// Get the multiplier for the mod index of a note some octaves from the
+    /// breakpoint. Both curves reach the full depth four octaves away, and stay
+    /// there further away; the linear curves change steadily, while the
+    /// exponential curves start slowly and then accelerate.
+    fn get_scale(self, octaves: defs::Sample, depth: defs::Sample) -> defs::Sample {
+        let amount = match self {
+            KeyScalingCurve::NegativeLinear | KeyScalingCurve::PositiveLinear =>
+                octaves / 4.0,
+            KeyScalingCurve::NegativeExponential | KeyScalingCurve::PositiveExponential =>
+                (octaves.exp2() - 1.0) / 15.0,
+        };
+        let amount = defs::Sample::min(amount, 1.0);
+        match self {
+            KeyScalingCurve::NegativeLinear | KeyScalingCurve::NegativeExponential =>
+                defs::Sample::max(1.0 - depth * amount, 0.0),
+            KeyScalingCurve::PositiveLinear | KeyScalingCurve::PositiveExponential =>
+                1.0 + depth * amount,
+        }
+    }
+}
+
+/// Get the multiplier for the mod index of a note played at a velocity.
+/// At full sensitivity, the mod index is in proportion to the velocity;
+/// at zero sensitivity, velocity has no effect.
+fn get_velocity_scale(velocity: u8, sensitivity: defs::Sample) -> defs::Sample {
+    1.0 - sensitivity * (1.0 - defs::Sample::from(velocity) / 127.0)
 }
 
 enum GeneratorParams {
     Pitch,
+    FrequencyMode,
+    RatioCoarse,
+    RatioFine,
+    FixedFrequency,
+    Detune,
     ModIndex,
+    Waveform,
+    PulseWidth,
+    WavetablePosition,
+    Feedback,
+    Level,
+    PhaseMode,
+    StartPhase,
+    VelocitySensitivity,
+    KeyBreakpoint,
+    KeyLeftCurve,
+    KeyRightCurve,
+    KeyLeftDepth,
+    KeyRightDepth,
 }
 
 impl Generator {
@@ -69,6 +206,10 @@ impl Generator {
         Self {
             id,
             state: State::new(),
+            envelope: Adsr::new(AdsrId::Generator(id)),
+            envelope_buffer: ResizableFrameBuffer::new(),
+            wavetable: None,
+            rng: SmallRng::from_entropy(),
         }
     }
 
@@ -76,19 +217,135 @@ impl Generator {
         match self.id {
             0 => match param {
                 GeneratorParams::Pitch => ParameterId::GeneratorAPitch,
+                GeneratorParams::FrequencyMode => ParameterId::GeneratorAFrequencyMode,
+                GeneratorParams::RatioCoarse => ParameterId::GeneratorARatioCoarse,
+                GeneratorParams::RatioFine => ParameterId::GeneratorARatioFine,
+                GeneratorParams::FixedFrequency => ParameterId::GeneratorAFixedFrequency,
+                GeneratorParams::Detune => ParameterId::GeneratorADetune,
                 GeneratorParams::ModIndex => ParameterId::GeneratorAModIndex,
+                GeneratorParams::Waveform => ParameterId::GeneratorAWaveform,
+                GeneratorParams::PulseWidth => ParameterId::GeneratorAPulseWidth,
+                GeneratorParams::WavetablePosition => ParameterId::GeneratorAWavetablePosition,
+                GeneratorParams::Feedback => ParameterId::GeneratorAFeedback,
+                GeneratorParams::Level => ParameterId::GeneratorALevel,
+                GeneratorParams::PhaseMode => ParameterId::GeneratorAPhaseMode,
+                GeneratorParams::StartPhase => ParameterId::GeneratorAStartPhase,
+                GeneratorParams::VelocitySensitivity => ParameterId::GeneratorAVelocitySensitivity,
+                GeneratorParams::KeyBreakpoint => ParameterId::GeneratorAKeyBreakpoint,
+                GeneratorParams::KeyLeftCurve => ParameterId::GeneratorAKeyLeftCurve,
+                GeneratorParams::KeyRightCurve => ParameterId::GeneratorAKeyRightCurve,
+                GeneratorParams::KeyLeftDepth => ParameterId::GeneratorAKeyLeftDepth,
+                GeneratorParams::KeyRightDepth => ParameterId::GeneratorAKeyRightDepth,
             },
             1 => match param {
                 GeneratorParams::Pitch => ParameterId::GeneratorBPitch,
+                GeneratorParams::FrequencyMode => ParameterId::GeneratorBFrequencyMode,
+                GeneratorParams::RatioCoarse => ParameterId::GeneratorBRatioCoarse,
+                GeneratorParams::RatioFine => ParameterId::GeneratorBRatioFine,
+                GeneratorParams::FixedFrequency => ParameterId::GeneratorBFixedFrequency,
+                GeneratorParams::Detune => ParameterId::GeneratorBDetune,
                 GeneratorParams::ModIndex => ParameterId::GeneratorBModIndex,
+                GeneratorParams::Waveform => ParameterId::GeneratorBWaveform,
+                GeneratorParams::PulseWidth => ParameterId::GeneratorBPulseWidth,
+                GeneratorParams::WavetablePosition => ParameterId::GeneratorBWavetablePosition,
+                GeneratorParams::Feedback => ParameterId::GeneratorBFeedback,
+                GeneratorParams::Level => ParameterId::GeneratorBLevel,
+                GeneratorParams::PhaseMode => ParameterId::GeneratorBPhaseMode,
+                GeneratorParams::StartPhase => ParameterId::GeneratorBStartPhase,
+                GeneratorParams::VelocitySensitivity => ParameterId::GeneratorBVelocitySensitivity,
+                GeneratorParams::KeyBreakpoint => ParameterId::GeneratorBKeyBreakpoint,
+                GeneratorParams::KeyLeftCurve => ParameterId::GeneratorBKeyLeftCurve,
+                GeneratorParams::KeyRightCurve => ParameterId::GeneratorBKeyRightCurve,
+                GeneratorParams::KeyLeftDepth => ParameterId::GeneratorBKeyLeftDepth,
+                GeneratorParams::KeyRightDepth => ParameterId::GeneratorBKeyRightDepth,
             },
             2 => match param {
                 GeneratorParams::Pitch => ParameterId::GeneratorCPitch,
+                GeneratorParams::FrequencyMode => ParameterId::GeneratorCFrequencyMode,
+                GeneratorParams::RatioCoarse => ParameterId::GeneratorCRatioCoarse,
+                GeneratorParams::RatioFine => ParameterId::GeneratorCRatioFine,
+                GeneratorParams::FixedFrequency => ParameterId::GeneratorCFixedFrequency,
+                GeneratorParams::Detune => ParameterId::GeneratorCDetune,
                 GeneratorParams::ModIndex => ParameterId::GeneratorCModIndex,
+                GeneratorParams::Waveform => ParameterId::GeneratorCWaveform,
+                GeneratorParams::PulseWidth => ParameterId::GeneratorCPulseWidth,
+                GeneratorParams::WavetablePosition => ParameterId::GeneratorCWavetablePosition,
+                GeneratorParams::Feedback => ParameterId::GeneratorCFeedback,
+                GeneratorParams::Level => ParameterId::GeneratorCLevel,
+                GeneratorParams::PhaseMode => ParameterId::GeneratorCPhaseMode,
+                GeneratorParams::StartPhase => ParameterId::GeneratorCStartPhase,
+                GeneratorParams::VelocitySensitivity => ParameterId::GeneratorCVelocitySensitivity,
+                GeneratorParams::KeyBreakpoint => ParameterId::GeneratorCKeyBreakpoint,
+                GeneratorParams::KeyLeftCurve => ParameterId::GeneratorCKeyLeftCurve,
+                GeneratorParams::KeyRightCurve => ParameterId::GeneratorCKeyRightCurve,
+                GeneratorParams::KeyLeftDepth => ParameterId::GeneratorCKeyLeftDepth,
+                GeneratorParams::KeyRightDepth => ParameterId::GeneratorCKeyRightDepth,
             },
             3 => match param {
                 GeneratorParams::Pitch => ParameterId::GeneratorDPitch,
+                GeneratorParams::FrequencyMode => ParameterId::GeneratorDFrequencyMode,
+                GeneratorParams::RatioCoarse => ParameterId::GeneratorDRatioCoarse,
+                GeneratorParams::RatioFine => ParameterId::GeneratorDRatioFine,
+                GeneratorParams::FixedFrequency => ParameterId::GeneratorDFixedFrequency,
+                GeneratorParams::Detune => ParameterId::GeneratorDDetune,
                 GeneratorParams::ModIndex => ParameterId::GeneratorDModIndex,
+                GeneratorParams::Waveform => ParameterId::GeneratorDWaveform,
+                GeneratorParams::PulseWidth => ParameterId::GeneratorDPulseWidth,
+                GeneratorParams::WavetablePosition => ParameterId::GeneratorDWavetablePosition,
+                GeneratorParams::Feedback => ParameterId::GeneratorDFeedback,
+                GeneratorParams::Level => ParameterId::GeneratorDLevel,
+                GeneratorParams::PhaseMode => ParameterId::GeneratorDPhaseMode,
+                GeneratorParams::StartPhase => ParameterId::GeneratorDStartPhase,
+                GeneratorParams::VelocitySensitivity => ParameterId::GeneratorDVelocitySensitivity,
+                GeneratorParams::KeyBreakpoint => ParameterId::GeneratorDKeyBreakpoint,
+                GeneratorParams::KeyLeftCurve => ParameterId::GeneratorDKeyLeftCurve,
+                GeneratorParams::KeyRightCurve => ParameterId::GeneratorDKeyRightCurve,
+                GeneratorParams::KeyLeftDepth => ParameterId::GeneratorDKeyLeftDepth,
+                GeneratorParams::KeyRightDepth => ParameterId::GeneratorDKeyRightDepth,
+            },
+            4 => match param {
+                GeneratorParams::Pitch => ParameterId::GeneratorEPitch,
+                GeneratorParams::FrequencyMode => ParameterId::GeneratorEFrequencyMode,
+                GeneratorParams::RatioCoarse => ParameterId::GeneratorERatioCoarse,
+                GeneratorParams::RatioFine => ParameterId::GeneratorERatioFine,
+                GeneratorParams::FixedFrequency => ParameterId::GeneratorEFixedFrequency,
+                GeneratorParams::Detune => ParameterId::GeneratorEDetune,
+                GeneratorParams::ModIndex => ParameterId::GeneratorEModIndex,
+                GeneratorParams::Waveform => ParameterId::GeneratorEWaveform,
+                GeneratorParams::PulseWidth => ParameterId::GeneratorEPulseWidth,
+                GeneratorParams::WavetablePosition => ParameterId::GeneratorEWavetablePosition,
+                GeneratorParams::Feedback => ParameterId::GeneratorEFeedback,
+                GeneratorParams::Level => ParameterId::GeneratorELevel,
+                GeneratorParams::PhaseMode => ParameterId::GeneratorEPhaseMode,
+                GeneratorParams::StartPhase => ParameterId::GeneratorEStartPhase,
+                GeneratorParams::VelocitySensitivity => ParameterId::GeneratorEVelocitySensitivity,
+                GeneratorParams::KeyBreakpoint => ParameterId::GeneratorEKeyBreakpoint,
+                GeneratorParams::KeyLeftCurve => ParameterId::GeneratorEKeyLeftCurve,
+                GeneratorParams::KeyRightCurve => ParameterId::GeneratorEKeyRightCurve,
+                GeneratorParams::KeyLeftDepth => ParameterId::GeneratorEKeyLeftDepth,
+                GeneratorParams::KeyRightDepth => ParameterId::GeneratorEKeyRightDepth,
+            },
+            5 => match param {
+                GeneratorParams::Pitch => ParameterId::GeneratorFPitch,
+                GeneratorParams::FrequencyMode => ParameterId::GeneratorFFrequencyMode,
+                GeneratorParams::RatioCoarse => ParameterId::GeneratorFRatioCoarse,
+                GeneratorParams::RatioFine => ParameterId::GeneratorFRatioFine,
+                GeneratorParams::FixedFrequency => ParameterId::GeneratorFFixedFrequency,
+                GeneratorParams::Detune => ParameterId::GeneratorFDetune,
+                GeneratorParams::ModIndex => ParameterId::GeneratorFModIndex,
+                GeneratorParams::Waveform => ParameterId::GeneratorFWaveform,
+                GeneratorParams::PulseWidth => ParameterId::GeneratorFPulseWidth,
+                GeneratorParams::WavetablePosition => ParameterId::GeneratorFWavetablePosition,
+                GeneratorParams::Feedback => ParameterId::GeneratorFFeedback,
+                GeneratorParams::Level => ParameterId::GeneratorFLevel,
+                GeneratorParams::PhaseMode => ParameterId::GeneratorFPhaseMode,
+                GeneratorParams::StartPhase => ParameterId::GeneratorFStartPhase,
+                GeneratorParams::VelocitySensitivity => ParameterId::GeneratorFVelocitySensitivity,
+                GeneratorParams::KeyBreakpoint => ParameterId::GeneratorFKeyBreakpoint,
+                GeneratorParams::KeyLeftCurve => ParameterId::GeneratorFKeyLeftCurve,
+                GeneratorParams::KeyRightCurve => ParameterId::GeneratorFKeyRightCurve,
+                GeneratorParams::KeyLeftDepth => ParameterId::GeneratorFKeyLeftDepth,
+                GeneratorParams::KeyRightDepth => ParameterId::GeneratorFKeyRightDepth,
             },
             _ => panic!("Unknown generator ID")
         }
@@ -101,25 +358,145 @@ impl Generator {
         match self.id {
             0 => match param_id {
                 ParameterId::GeneratorAPitch |
+                ParameterId::GeneratorAFrequencyMode |
+                ParameterId::GeneratorARatioCoarse |
+                ParameterId::GeneratorARatioFine |
+                ParameterId::GeneratorAFixedFrequency |
+                ParameterId::GeneratorADetune |
                 ParameterId::GeneratorAModIndex |
+                ParameterId::GeneratorAWaveform |
+                ParameterId::GeneratorAPulseWidth |
+                ParameterId::GeneratorAWavetablePosition |
+                ParameterId::GeneratorAFeedback |
+                ParameterId::GeneratorALevel |
+                ParameterId::GeneratorAPhaseMode |
+                ParameterId::GeneratorAStartPhase |
+                ParameterId::GeneratorAVelocitySensitivity |
+                ParameterId::GeneratorAKeyBreakpoint |
+                ParameterId::GeneratorAKeyLeftCurve |
+                ParameterId::GeneratorAKeyRightCurve |
+                ParameterId::GeneratorAKeyLeftDepth |
+                ParameterId::GeneratorAKeyRightDepth |
                 ParameterId::PitchBendRange => true,
                 _ => false,
             },
             1 => match param_id {
                 ParameterId::GeneratorBPitch |
+                ParameterId::GeneratorBFrequencyMode |
+                ParameterId::GeneratorBRatioCoarse |
+                ParameterId::GeneratorBRatioFine |
+                ParameterId::GeneratorBFixedFrequency |
+                ParameterId::GeneratorBDetune |
                 ParameterId::GeneratorBModIndex |
+                ParameterId::GeneratorBWaveform |
+                ParameterId::GeneratorBPulseWidth |
+                ParameterId::GeneratorBWavetablePosition |
+                ParameterId::GeneratorBFeedback |
+                ParameterId::GeneratorBLevel |
+                ParameterId::GeneratorBPhaseMode |
+                ParameterId::GeneratorBStartPhase |
+                ParameterId::GeneratorBVelocitySensitivity |
+                ParameterId::GeneratorBKeyBreakpoint |
+                ParameterId::GeneratorBKeyLeftCurve |
+                ParameterId::GeneratorBKeyRightCurve |
+                ParameterId::GeneratorBKeyLeftDepth |
+                ParameterId::GeneratorBKeyRightDepth |
                 ParameterId::PitchBendRange => true,
                 _ => false,
             },
             2 => match param_id {
                 ParameterId::GeneratorCPitch |
+                ParameterId::GeneratorCFrequencyMode |
+                ParameterId::GeneratorCRatioCoarse |
+                ParameterId::GeneratorCRatioFine |
+                ParameterId::GeneratorCFixedFrequency |
+                ParameterId::GeneratorCDetune |
                 ParameterId::GeneratorCModIndex |
+                ParameterId::GeneratorCWaveform |
+                ParameterId::GeneratorCPulseWidth |
+                ParameterId::GeneratorCWavetablePosition |
+                ParameterId::GeneratorCFeedback |
+                ParameterId::GeneratorCLevel |
+                ParameterId::GeneratorCPhaseMode |
+                ParameterId::GeneratorCStartPhase |
+                ParameterId::GeneratorCVelocitySensitivity |
+                ParameterId::GeneratorCKeyBreakpoint |
+                ParameterId::GeneratorCKeyLeftCurve |
+                ParameterId::GeneratorCKeyRightCurve |
+                ParameterId::GeneratorCKeyLeftDepth |
+                ParameterId::GeneratorCKeyRightDepth |
                 ParameterId::PitchBendRange => true,
                 _ => false,
             },
             3 => match param_id {
                 ParameterId::GeneratorDPitch |
+                ParameterId::GeneratorDFrequencyMode |
+                ParameterId::GeneratorDRatioCoarse |
+                ParameterId::GeneratorDRatioFine |
+                ParameterId::GeneratorDFixedFrequency |
+                ParameterId::GeneratorDDetune |
                 ParameterId::GeneratorDModIndex |
+                ParameterId::GeneratorDWaveform |
+                ParameterId::GeneratorDPulseWidth |
+                ParameterId::GeneratorDWavetablePosition |
+                ParameterId::GeneratorDFeedback |
+                ParameterId::GeneratorDLevel |
+                ParameterId::GeneratorDPhaseMode |
+                ParameterId::GeneratorDStartPhase |
+                ParameterId::GeneratorDVelocitySensitivity |
+                ParameterId::GeneratorDKeyBreakpoint |
+                ParameterId::GeneratorDKeyLeftCurve |
+                ParameterId::GeneratorDKeyRightCurve |
+                ParameterId::GeneratorDKeyLeftDepth |
+                ParameterId::GeneratorDKeyRightDepth |
+                ParameterId::PitchBendRange => true,
+                _ => false,
+            },
+            4 => match param_id {
+                ParameterId::GeneratorEPitch |
+                ParameterId::GeneratorEFrequencyMode |
+                ParameterId::GeneratorERatioCoarse |
+                ParameterId::GeneratorERatioFine |
+                ParameterId::GeneratorEFixedFrequency |
+                ParameterId::GeneratorEDetune |
+                ParameterId::GeneratorEModIndex |
+                ParameterId::GeneratorEWaveform |
+                ParameterId::GeneratorEPulseWidth |
+                ParameterId::GeneratorEWavetablePosition |
+                ParameterId::GeneratorEFeedback |
+                ParameterId::GeneratorELevel |
+                ParameterId::GeneratorEPhaseMode |
+                ParameterId::GeneratorEStartPhase |
+                ParameterId::GeneratorEVelocitySensitivity |
+                ParameterId::GeneratorEKeyBreakpoint |
+                ParameterId::GeneratorEKeyLeftCurve |
+                ParameterId::GeneratorEKeyRightCurve |
+                ParameterId::GeneratorEKeyLeftDepth |
+                ParameterId::GeneratorEKeyRightDepth |
+                ParameterId::PitchBendRange => true,
+                _ => false,
+            },
+            5 => match param_id {
+                ParameterId::GeneratorFPitch |
+                ParameterId::GeneratorFFrequencyMode |
+                ParameterId::GeneratorFRatioCoarse |
+                ParameterId::GeneratorFRatioFine |
+                ParameterId::GeneratorFFixedFrequency |
+                ParameterId::GeneratorFDetune |
+                ParameterId::GeneratorFModIndex |
+                ParameterId::GeneratorFWaveform |
+                ParameterId::GeneratorFPulseWidth |
+                ParameterId::GeneratorFWavetablePosition |
+                ParameterId::GeneratorFFeedback |
+                ParameterId::GeneratorFLevel |
+                ParameterId::GeneratorFPhaseMode |
+                ParameterId::GeneratorFStartPhase |
+                ParameterId::GeneratorFVelocitySensitivity |
+                ParameterId::GeneratorFKeyBreakpoint |
+                ParameterId::GeneratorFKeyLeftCurve |
+                ParameterId::GeneratorFKeyRightCurve |
+                ParameterId::GeneratorFKeyLeftDepth |
+                ParameterId::GeneratorFKeyRightDepth |
                 ParameterId::PitchBendRange => true,
                 _ => false,
             },
@@ -128,9 +505,127 @@ impl Generator {
 
     }
 
+    /// Get the frequency that this generator should play at, for the current note.
+    fn get_target_frequency(&self,
+                            params: &BaseliskPluginParameters,
+                            pitch_bend_semitones: defs::Sample) -> defs::Sample
+    {
+        let detune_semitones = params.get_real_value(
+            self.get_parameter(GeneratorParams::Detune)) / 100.0;
+        let note = defs::Sample::from(self.state.note);
+
+        match FrequencyMode::from_value(params.get_real_value(
+                self.get_parameter(GeneratorParams::FrequencyMode))) {
+            FrequencyMode::Semitones => get_frequency(
+                note
+                + params.get_real_value(self.get_parameter(GeneratorParams::Pitch))
+                + pitch_bend_semitones
+                + detune_semitones),
+            FrequencyMode::Ratio => {
+                let ratio = params.get_real_value(self.get_parameter(GeneratorParams::RatioCoarse))
+                    + params.get_real_value(self.get_parameter(GeneratorParams::RatioFine));
+                ratio * get_frequency(note + pitch_bend_semitones + detune_semitones)
+            },
+            FrequencyMode::Fixed =>
+                params.get_real_value(self.get_parameter(GeneratorParams::FixedFrequency))
+                * (detune_semitones / 12.0).exp2(),
+        }
+    }
+
+    /// Get the multiplier for the mod index of the current note,
+    /// from velocity sensitivity and keyboard scaling.
+    fn get_mod_index_scale(&self, params: &BaseliskPluginParameters) -> defs::Sample {
+        let velocity_scale = get_velocity_scale(
+            self.state.velocity,
+            params.get_real_value(self.get_parameter(GeneratorParams::VelocitySensitivity)));
+
+        let breakpoint = params.get_real_value(self.get_parameter(GeneratorParams::KeyBreakpoint));
+        let octaves = (defs::Sample::from(self.state.note) - breakpoint) / 12.0;
+        let (curve, depth) = if octaves < 0.0 {
+            (GeneratorParams::KeyLeftCurve, GeneratorParams::KeyLeftDepth)
+        } else {
+            (GeneratorParams::KeyRightCurve, GeneratorParams::KeyRightDepth)
+        };
+        let key_scale = KeyScalingCurve::from_value(
+                params.get_real_value(self.get_parameter(curve)))
+            .get_scale(octaves.abs(), params.get_real_value(self.get_parameter(depth)));
+
+        velocity_scale * key_scale
+    }
+
+    /// Set the phase at the start of a new note, according to the phase mode.
+    fn set_phase_for_new_note(&mut self, params: &BaseliskPluginParameters) {
+        let phase = match PhaseMode::from_value(params.get_real_value(
+                self.get_parameter(GeneratorParams::PhaseMode))) {
+            PhaseMode::Free => return,
+            PhaseMode::Reset => params.get_real_value(
+                self.get_parameter(GeneratorParams::StartPhase)),
+            PhaseMode::Random => self.rng.gen_range(0.0, 1.0),
+        };
+        self.state.phase = waveform::wrap_phase(phase);
+        // Forget the previous period, so that feedback starts the same way each time
+        self.state.previous_outputs = [0.0; 2];
+    }
+
+    /// Pick up any change to this generator's wavetable.
+    /// This doesn't block, so any change may take effect on a later buffer.
+    /// The bank keeps a reference to replaced wavetables, so dropping the previous
+    /// one here never frees it on the audio thread.
+    pub fn update_wavetable(&mut self, wavetables: &WavetableBank) {
+        if let Some(wavetable) = wavetables.try_get(self.id) {
+            self.wavetable = wavetable;
+        }
+    }
+
+    /// Keep the generator up to date through a buffer in which it isn't heard,
+    /// without generating any output. The envelope keeps running, so that it
+    /// carries on through its release and the next note starts its attack from
+    /// the level the envelope has reached.
+    pub fn process_silent_buffer(&mut self,
+                                 buffer_len: usize,
+                                 engine_event_iter: slice::Iter<(usize, EngineEvent)>,
+                                 sample_rate: defs::Sample,
+                                 params: &BaseliskPluginParameters)
+    {
+        self.state.sample_rate = sample_rate;
+        let envelope_buffer = self.envelope_buffer.get_sized_mut(buffer_len);
+        self.envelope.process_buffer(
+            envelope_buffer,
+            engine_event_iter.clone(),
+            sample_rate,
+            params,
+        );
+
+        // Notes can't start in a silent buffer, but pitch bends and parameter
+        // changes still apply to later buffers.
+        for (_, engine_event) in engine_event_iter {
+            match engine_event {
+                EngineEvent::PitchBend{ wheel_value } => {
+                    self.state.pitch_bend_wheel_value = *wheel_value;
+                },
+                EngineEvent::ModulateParameter { param_id, value } =>
+                    if self.should_trigger_keyframe_for_param(*param_id) {
+                        params.set_parameter(*param_id, *value);
+                    },
+                EngineEvent::NoteChange{ .. } => (),
+            }
+        }
+    }
+
+    /// Generate output into buffer.
+    /// mod_buffer: the sum of the modulators of this generator.
+    /// sync_input: the sync buffer of the generator that this generator is hard
+    ///             synced to, if any.
+    /// sync_output: filled with the points at which this generator's phase wraps.
+    ///              For each sample in which the phase wraps, this is the position
+    ///              within the sample at which it wrapped, from just above 0.0 (the
+    ///              start of the sample) to 1.0 (the end). Other samples are 0.0.
+    #[allow(clippy::too_many_arguments)]
     pub fn process_buffer(&mut self,
                buffer: &mut defs::MonoFrameBufferSlice,
                mod_buffer: &defs::MonoFrameBufferSlice,
+               sync_input: Option<&defs::MonoFrameBufferSlice>,
+               sync_output: &mut defs::MonoFrameBufferSlice,
                mut engine_event_iter: slice::Iter<(usize, EngineEvent)>,
                sample_rate: defs::Sample,
                params: &BaseliskPluginParameters,
@@ -139,6 +634,16 @@ impl Generator {
         // Store buffer len to avoid multiple mutable buffer accesses later on
         let buffer_len = buffer.len();
 
+        // Each generator has its own envelope, which controls its output level
+        // (and so the modulation depth of any generator it modulates).
+        let envelope_buffer = self.envelope_buffer.get_sized_mut(buffer_len);
+        self.envelope.process_buffer(
+            envelope_buffer,
+            engine_event_iter.clone(),
+            sample_rate,
+            params,
+        );
+
         // Generate the outputs per-frame
         let mut this_keyframe: usize = 0;
         let mut next_keyframe: usize;
@@ -150,7 +655,7 @@ impl Generator {
                 match engine_event {
                     // Note changes will trigger keyframes only if there is a new note
                     // (i.e. not None)
-                    EngineEvent::NoteChange{ note } => {
+                    EngineEvent::NoteChange{ note, .. } => {
                         if note.is_none() {
                             continue
                         }
@@ -170,45 +675,36 @@ impl Generator {
             let pitch_bend_semitones = pitch_bend::get_pitch_bend_semitones(
                 self.state.pitch_bend_wheel_value, params);
 
-            self.state.target_base_frequency = get_frequency(defs::Sample::from(self.state.note)
-                                                + params.get_real_value(
-                                                    self.get_parameter(GeneratorParams::Pitch))
-                                                + pitch_bend_semitones);
+            self.state.target_base_frequency = self.get_target_frequency(
+                params, pitch_bend_semitones);
 
             // Smoothing for pitch bends, to reduce audible stepping for wide pitch bends
-            // (e.g. 12+ semitones).
-            // The algorithm activates when the pitch wheel is used, and gently accelerates
-            // the actual base_frequency towards the target_base_frequency.
-            let diff = self.state.target_base_frequency - self.state.base_frequency;
-            if diff > 0.0 {
-                self.state.pitchbend_portamento_multiplier = defs::Sample::min(
-                    self.state.pitchbend_portamento_multiplier * 1.005,
-                    1.05,
-                );
-                self.state.base_frequency = defs::Sample::min(
-                    self.state.pitchbend_portamento_multiplier * self.state.base_frequency,
-                    self.state.target_base_frequency,
-                );
-            } else if diff < 0.0 {
-                self.state.pitchbend_portamento_multiplier = defs::Sample::max(
-                    self.state.pitchbend_portamento_multiplier * (1.0 / 1.005),
-                    1.0 / 1.05,
-                );
-                self.state.base_frequency = defs::Sample::max(
-                    self.state.pitchbend_portamento_multiplier * self.state.base_frequency,
-                    self.state.target_base_frequency,
-                );
-            } else {
-                self.state.pitchbend_portamento_multiplier = 1.0;
-            }
+            self.state.base_frequency = self.state.pitchbend_portamento.approach(
+                self.state.base_frequency, self.state.target_base_frequency);
 
             self.state.target_mod_index = params.get_real_value(
-                    self.get_parameter(GeneratorParams::ModIndex));
+                    self.get_parameter(GeneratorParams::ModIndex))
+                * self.get_mod_index_scale(params);
+            self.state.waveform = Waveform::from_value(params.get_real_value(
+                    self.get_parameter(GeneratorParams::Waveform)));
+            self.state.pulse_width = params.get_real_value(
+                    self.get_parameter(GeneratorParams::PulseWidth));
+            self.state.wavetable_position = params.get_real_value(
+                    self.get_parameter(GeneratorParams::WavetablePosition));
+            self.state.feedback = params.get_real_value(
+                    self.get_parameter(GeneratorParams::Feedback));
 
             // Generate all the samples for this buffer
             let buffer_slice = buffer.get_mut(this_keyframe..next_keyframe).unwrap();
             let mod_buffer_slice = mod_buffer.get(this_keyframe..next_keyframe).unwrap();
-            sine_generator(&mut self.state, &mod_buffer_slice, buffer_slice);
+            let sync_input_slice = sync_input.map(
+                |sync_input| sync_input.get(this_keyframe..next_keyframe).unwrap());
+            let sync_output_slice = sync_output.get_mut(this_keyframe..next_keyframe).unwrap();
+            fm_generator(&mut self.state, self.wavetable.as_ref(), &mod_buffer_slice,
+                         sync_input_slice, sync_output_slice, buffer_slice);
+            gain::process_buffer_fixed_gain(
+                params.get_real_value(self.get_parameter(GeneratorParams::Level)),
+                buffer_slice);
 
             // We've reached the next_keyframe.
             this_keyframe = next_keyframe;
@@ -222,16 +718,15 @@ impl Generator {
                 // to update the current state.
                 let (_, event) = next_event.unwrap();
                 match event {
-                    EngineEvent::NoteChange{ note } => {
+                    EngineEvent::NoteChange{ note, velocity } => {
                         if let Some(note) = note {
                             self.state.note = *note;
+                            self.state.velocity = *velocity;
                             // No portamento (set base frequency to what target
                             // frequency will be next iteration)
-                            self.state.base_frequency = get_frequency(
-                                    defs::Sample::from(self.state.note)
-                                    + params.get_real_value(
-                                        self.get_parameter(GeneratorParams::Pitch))
-                                    + pitch_bend_semitones);
+                            self.state.base_frequency = self.get_target_frequency(
+                                    params, pitch_bend_semitones);
+                            self.set_phase_for_new_note(params);
                         }
                     },
                     EngineEvent::PitchBend{ wheel_value } => {
@@ -244,25 +739,33 @@ impl Generator {
                 };
             }
         }
+
+        // Apply the envelope to the generated output
+        gain::process_buffer(&self.envelope_buffer.get_sized_mut(buffer_len), buffer);
     }
 }
 
 impl traits::Processor for Generator {
     fn panic(&mut self) {
         self.state.panic();
+        self.envelope.panic();
     }
 }
 
 /// Generator function that produces a frequency-modulated wave.
-fn sine_generator(
+/// If sync_input is given, the phase is reset whenever the sync input wraps.
+fn fm_generator(
     state: &mut State,
+    wavetable: Option<&Arc<Wavetable>>,
     mod_buffer: &defs::MonoFrameBufferSlice,
+    sync_input: Option<&defs::MonoFrameBufferSlice>,
+    sync_output: &mut defs::MonoFrameBufferSlice,
     buffer: &mut defs::MonoFrameBufferSlice,
 )
 {
     let mut phase = state.phase;
 
-    for (frame, mod_frame) in buffer.iter_mut().zip(mod_buffer.iter()) {
+    for (i, (frame, mod_frame)) in buffer.iter_mut().zip(mod_buffer.iter()).enumerate() {
         // Modulator influence is a function of modulator output value
         // and the mod_index of this oscillator (i.e. how much we want the value of
         // modulator oscillator to influence the freuqnecy of this oscillator)
@@ -271,19 +774,371 @@ fn sine_generator(
 
         // Advance carrier phase
         // Enforce range 0.0 <= phase < 1.0
+        // (a strongly modulated generator may run backwards)
         let step = (state.base_frequency + freq_offset) / state.sample_rate;
-        phase = phase + step;
-        if phase >= 1.0 {
-            // We should only update mod index after the end of a period
-            // to keep oscillators in sync so do that now
+        let sync = sync_input.map_or(0.0, |sync_input| sync_input[i][0]);
+        sync_output[i][0] = 0.0;
+        if sync > 0.0 {
+            // Hard sync: restart the period at the point where the sync input wrapped
             state.mod_index = state.target_mod_index;
-            phase %= 1.0;
+            phase = waveform::wrap_phase((1.0 - sync) * step);
+            sync_output[i][0] = sync;
+        } else {
+            phase = phase + step;
+            if !(0.0..1.0).contains(&phase) {
+                // We should only update mod index after the end of a period
+                // to keep oscillators in sync so do that now
+                state.mod_index = state.target_mod_index;
+                if phase >= 1.0 {
+                    sync_output[i][0] = defs::Sample::max(
+                        1.0 - (phase - 1.0) / step, std::f32::EPSILON);
+                }
+                phase -= phase.floor();
+            }
         }
 
-        frame[0] = defs::Sample::sin(2.0 as defs::Sample * defs::PI * phase);
+        // Self-feedback offsets the phase by the generator's recent output.
+        // Averaging the last two outputs damps the oscillation at the Nyquist
+        // frequency that would otherwise make high feedback amounts unstable.
+        let feedback_phase = state.feedback * 0.5
+            * (state.previous_outputs[0] + state.previous_outputs[1]);
+        let output_phase = waveform::wrap_phase(phase + feedback_phase);
+
+        let output = match (state.waveform, wavetable) {
+            (Waveform::Wavetable, Some(wavetable)) =>
+                wavetable.get_sample(state.wavetable_position, output_phase, step),
+            _ => waveform::get_sample(state.waveform, output_phase, step, state.pulse_width),
+        };
+        state.previous_outputs = [output, state.previous_outputs[0]];
+        frame[0] = output;
     }
 
     // Store the phases for next iteration
     state.phase = phase;
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use defs;
+
+    /// Run generator A for a number of samples from a note-on at frame 0,
+    /// with no modulator input.
+    /// params: (ParameterId, value) pairs where the Baselisk defaults need to be overridden
+    fn _run(params_to_set: Vec<(ParameterId, &str)>,
+            sample_rate: defs::Sample,
+            num_samples: usize) -> Vec<defs::MonoFrame>
+    {
+        let mut generator = Generator::new(0);
+        let params = BaseliskPluginParameters::default();
+        for (param_id, value) in params_to_set {
+            params.update_real_value_from_string(param_id, String::from(value)).unwrap();
+        }
+
+        let engine_events = vec![(0, EngineEvent::NoteChange { note: Some(69), velocity: 127 })];
+        let mod_buffer = vec![[0.0]; num_samples];
+        let mut sync_buffer = vec![[0.0]; num_samples];
+        let mut buffer = vec![[0.0]; num_samples];
+        generator.process_buffer(&mut buffer, &mod_buffer, None, &mut sync_buffer,
+                                 engine_events.iter(), sample_rate, &params);
+        buffer
+    }
+
+    /// Check that a buffer from _run contains a sine wave at a given frequency.
+    fn _assert_sine(buffer: &[defs::MonoFrame],
+                    frequency: defs::Sample,
+                    sample_rate: defs::Sample)
+    {
+        // Skip the generator envelope's attack
+        for (i, frame) in buffer.iter().enumerate().skip(100) {
+            let expected = (defs::TWOPI * frequency * (i + 1) as defs::Sample / sample_rate).sin();
+            let error_abs = defs::Sample::abs(frame[0] - expected);
+            assert!(error_abs < 2e-3, "For sample index {}, actual output == {}, expected == {}",
+                    i, frame[0], expected);
+        }
+    }
+
+    #[test]
+    /// Without feedback, the sine waveform is a pure sine at the note frequency.
+    fn test_no_feedback_is_sine() {
+        let sample_rate = 44_000.0;
+        _assert_sine(&_run(vec![], sample_rate, 400), 440.0, sample_rate);
+    }
+
+    #[test]
+    fn test_semitone_offset_and_detune() {
+        let sample_rate = 44_000.0;
+        _assert_sine(&_run(vec![(ParameterId::GeneratorAPitch, "12")], sample_rate, 400),
+                     880.0, sample_rate);
+        _assert_sine(&_run(vec![(ParameterId::GeneratorAPitch, "11"),
+                                (ParameterId::GeneratorADetune, "100")], sample_rate, 400),
+                     880.0, sample_rate);
+    }
+
+    #[test]
+    fn test_ratio_mode() {
+        let sample_rate = 44_000.0;
+        // The semitone offset is not used in ratio mode.
+        _assert_sine(&_run(vec![(ParameterId::GeneratorAFrequencyMode, "ratio"),
+                                (ParameterId::GeneratorAPitch, "7"),
+                                (ParameterId::GeneratorARatioCoarse, "3"),
+                                (ParameterId::GeneratorARatioFine, "0.5")], sample_rate, 400),
+                     3.5 * 440.0, sample_rate);
+        _assert_sine(&_run(vec![(ParameterId::GeneratorAFrequencyMode, "ratio"),
+                                (ParameterId::GeneratorARatioCoarse, "0"),
+                                (ParameterId::GeneratorARatioFine, "0.5"),
+                                (ParameterId::GeneratorADetune, "-100")], sample_rate, 400),
+                     0.5 * 440.0 * (-1.0 / 12.0 as defs::Sample).exp2(), sample_rate);
+    }
+
+    #[test]
+    /// Fixed frequency mode doesn't follow the note.
+    fn test_fixed_frequency_mode() {
+        let sample_rate = 44_000.0;
+        _assert_sine(&_run(vec![(ParameterId::GeneratorAFrequencyMode, "fixed"),
+                                (ParameterId::GeneratorAFixedFrequency, "100")], sample_rate, 400),
+                     100.0, sample_rate);
+        _assert_sine(&_run(vec![(ParameterId::GeneratorAFrequencyMode, "fixed"),
+                                (ParameterId::GeneratorAFixedFrequency, "100"),
+                                (ParameterId::GeneratorADetune, "100")], sample_rate, 400),
+                     100.0 * (1.0 / 12.0 as defs::Sample).exp2(), sample_rate);
+    }
+
+    #[test]
+    /// Moderate feedback produces a brighter waveform which is still periodic
+    /// at the note frequency.
+    fn test_moderate_feedback_is_periodic() {
+        // 100 samples per period
+        let sample_rate = 44_000.0;
+        let buffer = _run(vec![(ParameterId::GeneratorAFeedback, "0.25")], sample_rate, 1200);
+
+        let mut max_difference_from_sine: defs::Sample = 0.0;
+        for i in 1000..1100 {
+            let sine = (defs::TWOPI * 440.0 * (i + 1) as defs::Sample / sample_rate).sin();
+            max_difference_from_sine = max_difference_from_sine.max((buffer[i][0] - sine).abs());
+
+            let error_abs = defs::Sample::abs(buffer[i][0] - buffer[i + 100][0]);
+            assert!(error_abs < 1e-3, "Output at sample {} is not periodic", i);
+        }
+        assert!(max_difference_from_sine > 0.1, "Feedback has no effect");
+    }
+
+    #[test]
+    /// High feedback produces a noisy waveform, which stays within the
+    /// range of the waveform.
+    fn test_high_feedback_is_noisy() {
+        let sample_rate = 44_000.0;
+        let buffer = _run(vec![(ParameterId::GeneratorAFeedback, "1.0")], sample_rate, 1200);
+
+        let mut max_difference_between_periods: defs::Sample = 0.0;
+        for i in 1000..1100 {
+            assert!(buffer[i][0].abs() <= 1.0);
+            max_difference_between_periods = max_difference_between_periods.max(
+                (buffer[i][0] - buffer[i + 100][0]).abs());
+        }
+        assert!(max_difference_between_periods > 0.1, "Output is periodic");
+    }
+
+    #[test]
+    /// The sync output marks each wrap of the phase, once per period.
+    fn test_sync_output() {
+        let sample_rate = 44_000.0;
+        let mut generator = Generator::new(0);
+        let params = BaseliskPluginParameters::default();
+        let engine_events = vec![(0, EngineEvent::NoteChange { note: Some(69), velocity: 127 })];
+        let mod_buffer = vec![[0.0]; 44_050];
+        let mut sync_buffer = vec![[0.0]; 44_050];
+        let mut buffer = vec![[0.0]; 44_050];
+        generator.process_buffer(&mut buffer, &mod_buffer, None, &mut sync_buffer,
+                                 engine_events.iter(), sample_rate, &params);
+
+        let wraps: Vec<usize> = (0..sync_buffer.len())
+            .filter(|i| sync_buffer[*i][0] != 0.0)
+            .collect();
+        assert_eq!(wraps.len(), 440);
+        for (i, wrap) in wraps.iter().enumerate() {
+            assert!(sync_buffer[*wrap][0] > 0.0 && sync_buffer[*wrap][0] <= 1.0);
+            assert!((*wrap as isize - (100 * i + 99) as isize).abs() <= 1,
+                    "Wrap {} at sample {}", i, wrap);
+        }
+    }
+
+    #[test]
+    /// A generator that is hard synced to another restarts its period
+    /// whenever the other generator's period restarts.
+    fn test_hard_sync() {
+        let sample_rate = 44_000.0;
+        let num_samples = 1200;
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorBPitch, String::from("7")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorBWaveform, String::from("saw")).unwrap();
+        let engine_events = vec![(0, EngineEvent::NoteChange { note: Some(69), velocity: 127 })];
+        let mod_buffer = vec![[0.0]; num_samples];
+
+        // Generator A at 440 Hz is the sync source
+        let mut master = Generator::new(0);
+        let mut master_sync_buffer = vec![[0.0]; num_samples];
+        let mut master_buffer = vec![[0.0]; num_samples];
+        master.process_buffer(&mut master_buffer, &mod_buffer, None, &mut master_sync_buffer,
+                              engine_events.iter(), sample_rate, &params);
+
+        // Generator B plays a fifth higher, but restarts every 100 samples
+        let mut slave = Generator::new(1);
+        let mut slave_sync_buffer = vec![[0.0]; num_samples];
+        let mut buffer = vec![[0.0]; num_samples];
+        slave.process_buffer(&mut buffer, &mod_buffer, Some(&master_sync_buffer),
+                             &mut slave_sync_buffer, engine_events.iter(), sample_rate, &params);
+        for i in 1000..1100 {
+            let error_abs = defs::Sample::abs(buffer[i][0] - buffer[i - 100][0]);
+            assert!(error_abs < 1e-3, "Output at sample {} is not periodic", i);
+        }
+        // The slave passes on the sync, so that sync can be chained
+        for (master_sync, slave_sync) in master_sync_buffer.iter().zip(slave_sync_buffer.iter()) {
+            if master_sync[0] != 0.0 {
+                assert_eq!(master_sync, slave_sync);
+            }
+        }
+    }
+
+    /// Start a note on generator A, and get the mod index it uses for that note.
+    fn _get_mod_index(params_to_set: Vec<(ParameterId, &str)>,
+                      note: u8,
+                      velocity: u8) -> defs::Sample
+    {
+        let mut generator = Generator::new(0);
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(ParameterId::GeneratorAModIndex, String::from("4")).unwrap();
+        for (param_id, value) in params_to_set {
+            params.update_real_value_from_string(param_id, String::from(value)).unwrap();
+        }
+
+        let engine_events = vec![(0, EngineEvent::NoteChange { note: Some(note), velocity })];
+        let mod_buffer = vec![[0.0]; 10];
+        let mut sync_buffer = vec![[0.0]; 10];
+        let mut buffer = vec![[0.0]; 10];
+        generator.process_buffer(&mut buffer, &mod_buffer, None, &mut sync_buffer,
+                                 engine_events.iter(), 44_000.0, &params);
+        generator.state.target_mod_index
+    }
+
+    #[test]
+    fn test_velocity_sensitivity() {
+        // Without sensitivity, velocity has no effect
+        _assert_close(_get_mod_index(vec![], 60, 127), 4.0);
+        _assert_close(_get_mod_index(vec![], 60, 1), 4.0);
+
+        // At full sensitivity, the mod index is in proportion to velocity
+        let params = vec![(ParameterId::GeneratorAVelocitySensitivity, "1")];
+        _assert_close(_get_mod_index(params.clone(), 60, 127), 4.0);
+        _assert_close(_get_mod_index(params, 60, 127 / 2), 4.0 * 63.0 / 127.0);
+
+        // At half sensitivity, the mod index falls by up to half
+        let params = vec![(ParameterId::GeneratorAVelocitySensitivity, "0.5")];
+        _assert_close(_get_mod_index(params, 60, 0), 2.0);
+    }
+
+    #[test]
+    fn test_key_scaling() {
+        let params = vec![
+            (ParameterId::GeneratorAKeyBreakpoint, "60"),
+            (ParameterId::GeneratorAKeyLeftCurve, "-lin"),
+            (ParameterId::GeneratorAKeyLeftDepth, "1"),
+            (ParameterId::GeneratorAKeyRightCurve, "+exp"),
+            (ParameterId::GeneratorAKeyRightDepth, "0.5"),
+        ];
+        // No scaling at the breakpoint
+        _assert_close(_get_mod_index(params.clone(), 60, 127), 4.0);
+        // Two octaves below: halfway along the linear curve
+        _assert_close(_get_mod_index(params.clone(), 36, 127), 2.0);
+        // Further below, the mod index stops at zero
+        _assert_close(_get_mod_index(params.clone(), 0, 127), 0.0);
+        // One and four octaves above, on the exponential curve
+        _assert_close(_get_mod_index(params.clone(), 72, 127), 4.0 * (1.0 + 0.5 / 15.0));
+        _assert_close(_get_mod_index(params.clone(), 108, 127), 6.0);
+        // Beyond four octaves, the mod index stays at the full depth
+        _assert_close(_get_mod_index(params, 127, 127), 6.0);
+
+        let params = vec![
+            (ParameterId::GeneratorAKeyBreakpoint, "0"),
+            (ParameterId::GeneratorAKeyRightCurve, "+lin"),
+            (ParameterId::GeneratorAKeyRightDepth, "1"),
+        ];
+        _assert_close(_get_mod_index(params.clone(), 48, 127), 8.0);
+        _assert_close(_get_mod_index(params, 127, 127), 8.0);
+    }
+
+    /// Run generator A with two notes, the second starting 1.5 periods after the first.
+    fn _run_two_notes(params_to_set: Vec<(ParameterId, &str)>) -> Vec<defs::MonoFrame> {
+        let sample_rate = 44_000.0;
+        let num_samples = 400;
+        let mut generator = Generator::new(0);
+        let params = BaseliskPluginParameters::default();
+        for (param_id, value) in params_to_set {
+            params.update_real_value_from_string(param_id, String::from(value)).unwrap();
+        }
+
+        let engine_events = vec![
+            (0, EngineEvent::NoteChange { note: Some(69), velocity: 127 }),
+            (150, EngineEvent::NoteChange { note: Some(69), velocity: 127 }),
+        ];
+        let mod_buffer = vec![[0.0]; num_samples];
+        let mut sync_buffer = vec![[0.0]; num_samples];
+        let mut buffer = vec![[0.0]; num_samples];
+        generator.process_buffer(&mut buffer, &mod_buffer, None, &mut sync_buffer,
+                                 engine_events.iter(), sample_rate, &params);
+        buffer
+    }
+
+    #[test]
+    /// In free-running mode, the phase continues across notes.
+    fn test_free_running_phase() {
+        let buffer = _run_two_notes(vec![]);
+        // Half a period later, the sine is inverted
+        for i in 50..100 {
+            _assert_close(buffer[i + 150][0], -buffer[i][0]);
+        }
+    }
+
+    #[test]
+    /// In reset mode, each note starts from the start phase.
+    fn test_reset_phase() {
+        let buffer = _run_two_notes(vec![(ParameterId::GeneratorAPhaseMode, "reset")]);
+        for i in 50..100 {
+            _assert_close(buffer[i + 150][0], buffer[i][0]);
+        }
+
+        let buffer = _run_two_notes(vec![(ParameterId::GeneratorAPhaseMode, "reset"),
+                                         (ParameterId::GeneratorAStartPhase, "0.25")]);
+        for i in 50..100 {
+            _assert_close(buffer[i + 150][0], buffer[i][0]);
+            // A quarter period ahead of a sine starting from zero
+            let expected = (defs::TWOPI * (0.25 + 440.0 * (i + 1) as defs::Sample / 44_000.0)).sin();
+            _assert_close(buffer[i][0], expected);
+        }
+    }
+
+    #[test]
+    /// In random mode, each note starts from a different phase.
+    fn test_random_phase() {
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(
+            ParameterId::GeneratorAPhaseMode, String::from("random")).unwrap();
+        let mut generator = Generator::new(0);
+        let phases: Vec<defs::Sample> = (0..4)
+            .map(|_| {
+                generator.set_phase_for_new_note(&params);
+                generator.state.phase
+            })
+            .collect();
+        assert!(phases.iter().all(|phase| (0.0..1.0).contains(phase)));
+        assert!(phases.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    fn _assert_close(actual: defs::Sample, expected: defs::Sample) {
+        assert!((actual - expected).abs() < 2e-3,
+                "actual == {}, expected == {}", actual, expected);
+    }
+}
@@ -1,15 +1,23 @@
 #![allow(clippy::cast_precision_loss)]
 
 mod adsr;
+mod algorithm;
 mod buffer;
+mod dahdsr;
 mod delay;
 mod gain;
-mod generator;
+pub mod generator;
 mod filter;
+mod noise;
 mod note_selector;
+mod oversampling;
 mod pitch_bend;
+pub mod sine;
+mod sub_oscillator;
 mod traits;
+mod waveform;
 mod waveshaper;
+mod zdf_filter;
 
 use defs;
 use shared::{
@@ -19,17 +27,23 @@ use shared::{
         RawMidi
     },
     parameter::{
+        BaseliskPluginParameters,
         ParameterId,
     },
     SharedState,
 };
 use engine::{
-    adsr::Adsr,
+    adsr::{Adsr, AdsrId},
+    algorithm::{Algorithm, NUM_GENERATORS},
     buffer::ResizableFrameBuffer,
+    dahdsr::Dahdsr,
     delay::Delay,
     generator::Generator,
     filter::Filter,
+    noise::Noise,
     note_selector::MonoNoteSelector,
+    oversampling::Decimator,
+    sub_oscillator::SubOscillator,
     traits::Processor,
 };
 use sample::slice;
@@ -37,6 +51,32 @@ use std::sync::Arc;
 
 use vst::plugin::PluginParameters;
 
+/// Some parameters are read once per buffer, rather than by a processor at the
+/// frame they change. This returns true for those parameters.
+fn is_buffer_parameter(param_id: ParameterId) -> bool {
+    match param_id {
+        ParameterId::AmpEnvelope |
+        ParameterId::FilterEnvelope |
+        ParameterId::MixLevel |
+        ParameterId::Oversampling => true,
+        _ => algorithm::is_algorithm_parameter(param_id),
+    }
+}
+
+/// Apply changes to the parameters that are read once per buffer,
+/// so that they take effect from the start of the buffer.
+fn apply_buffer_parameter_events(engine_events: &[(usize, EngineEvent)],
+                                 params: &BaseliskPluginParameters)
+{
+    for (_, engine_event) in engine_events.iter() {
+        if let EngineEvent::ModulateParameter { param_id, value } = engine_event {
+            if is_buffer_parameter(*param_id) {
+                params.set_parameter(*param_id, *value);
+            }
+        }
+    }
+}
+
 pub struct Engine
 {
     // Misc
@@ -44,24 +84,31 @@ pub struct Engine
     shared_state: Arc<SharedState>,
     raw_midi_buffer: Vec<RawMidi>,
     engine_event_buffer: Vec<(usize, EngineEvent)>,
+    oversampled_event_buffer: Vec<(usize, EngineEvent)>,
     note_selector: MonoNoteSelector,
     timing_data: TimingData,
     dump_timing_info: bool,
     // Buffers
-    dummy_buffer: ResizableFrameBuffer<defs::MonoFrame>,
     mono_buffer: ResizableFrameBuffer<defs::MonoFrame>,
     mod_sum_buffer: ResizableFrameBuffer<defs::MonoFrame>,
-    generator_a_buffer: ResizableFrameBuffer<defs::MonoFrame>,
-    generator_b_buffer: ResizableFrameBuffer<defs::MonoFrame>,
-    generator_c_buffer: ResizableFrameBuffer<defs::MonoFrame>,
-    generator_d_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    generator_buffers: Vec<ResizableFrameBuffer<defs::MonoFrame>>,
+    sync_buffers: Vec<ResizableFrameBuffer<defs::MonoFrame>>,
+    sync_input_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    ring_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    noise_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    noise_mix_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    noise_modulation_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    oversampled_mix_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    sub_oscillator_buffer: ResizableFrameBuffer<defs::MonoFrame>,
     adsr_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    dahdsr_buffer: ResizableFrameBuffer<defs::MonoFrame>,
     // DSP Units
-    generator_a: Generator,
-    generator_b: Generator,
-    generator_c: Generator,
-    generator_d: Generator,
+    generators: Vec<Generator>,
+    noise: Noise,
+    sub_oscillator: SubOscillator,
+    decimator: Decimator,
     adsr: Adsr,
+    dahdsr: Dahdsr,
     filter: Filter,
     delay: Delay,
 }
@@ -76,24 +123,31 @@ impl Engine
             shared_state,
             raw_midi_buffer: Vec::with_capacity(defs::RAW_MIDI_BUF_LEN),
             engine_event_buffer: Vec::with_capacity(defs::ENGINE_EVENT_BUF_LEN),
+            oversampled_event_buffer: Vec::with_capacity(defs::ENGINE_EVENT_BUF_LEN),
             note_selector: MonoNoteSelector::new(),
             timing_data: TimingData::default(),
             dump_timing_info,
             // Buffers
-            dummy_buffer: ResizableFrameBuffer::new(),
             mono_buffer: ResizableFrameBuffer::new(),
             mod_sum_buffer: ResizableFrameBuffer::new(),
             adsr_buffer: ResizableFrameBuffer::new(),
-            generator_a_buffer: ResizableFrameBuffer::new(),
-            generator_b_buffer: ResizableFrameBuffer::new(),
-            generator_c_buffer: ResizableFrameBuffer::new(),
-            generator_d_buffer: ResizableFrameBuffer::new(),
+            dahdsr_buffer: ResizableFrameBuffer::new(),
+            generator_buffers: (0..NUM_GENERATORS).map(|_| ResizableFrameBuffer::new()).collect(),
+            sync_buffers: (0..NUM_GENERATORS).map(|_| ResizableFrameBuffer::new()).collect(),
+            sync_input_buffer: ResizableFrameBuffer::new(),
+            ring_buffer: ResizableFrameBuffer::new(),
+            noise_buffer: ResizableFrameBuffer::new(),
+            noise_mix_buffer: ResizableFrameBuffer::new(),
+            noise_modulation_buffer: ResizableFrameBuffer::new(),
+            oversampled_mix_buffer: ResizableFrameBuffer::new(),
+            sub_oscillator_buffer: ResizableFrameBuffer::new(),
             // DSP Units
-            generator_a: Generator::new(0),
-            generator_b: Generator::new(1),
-            generator_c: Generator::new(2),
-            generator_d: Generator::new(3),
-            adsr: Adsr::new(),
+            generators: (0..NUM_GENERATORS).map(Generator::new).collect(),
+            noise: Noise::new(),
+            sub_oscillator: SubOscillator::new(),
+            decimator: Decimator::new(),
+            adsr: Adsr::new(AdsrId::Main),
+            dahdsr: Dahdsr::new(),
             filter: Filter::new(),
             delay: Delay::new(),
         }
@@ -162,13 +216,15 @@ impl Engine
             return
         }
 
+        apply_buffer_parameter_events(&self.engine_event_buffer, &self.shared_state.parameters);
+
         self.timing_data.pre = (time::precise_time_ns() - engine_start_time) / 1000;
 
         let frames_this_buffer = left_output_buffer.len();
 
-        let mut mono_buffer = self.mono_buffer.get_sized_mut(frames_this_buffer);
+        let mono_buffer = self.mono_buffer.get_sized_mut(frames_this_buffer);
 
-        // ADSR buffer for Gain and Filter (shared for now).
+        // Envelope buffers for Gain and Filter.
         let adsr_start_time = time::precise_time_ns();
 
         let adsr_buffer = self.adsr_buffer.get_sized_mut(frames_this_buffer);
@@ -180,166 +236,190 @@ impl Engine
             &self.shared_state.parameters
         );
 
+        let dahdsr_buffer = self.dahdsr_buffer.get_sized_mut(frames_this_buffer);
+
+        let dahdsr_any_nonzero_output = self.dahdsr.process_buffer(
+            dahdsr_buffer,
+            self.engine_event_buffer.iter(),
+            self.sample_rate,
+            &self.shared_state.parameters
+        );
+
+        // Select which envelope is used for the gain and filter stages.
+        let (amp_envelope_buffer, amp_envelope_any_nonzero_output) =
+            match self.shared_state.parameters.get_real_value(
+                ParameterId::AmpEnvelope) as usize {
+            0 => (&*adsr_buffer, adsr_any_nonzero_output),
+            1 => (&*dahdsr_buffer, dahdsr_any_nonzero_output),
+            _ => panic!("Unknown amp envelope"),
+        };
+        let filter_envelope_buffer =
+            match self.shared_state.parameters.get_real_value(
+                ParameterId::FilterEnvelope) as usize {
+            0 => &*adsr_buffer,
+            1 => &*dahdsr_buffer,
+            _ => panic!("Unknown filter envelope"),
+        };
+
         self.timing_data.adsr = (time::precise_time_ns() - adsr_start_time) / 1000;
 
-        // Optimization: when ADSR is in the off state for a whole buffer,
+        // Optimization: when the amp envelope is in the off state for a whole buffer,
         // the result of the generator and gain stages is silence
-        if adsr_any_nonzero_output {
+        if amp_envelope_any_nonzero_output {
 
             // Signal Generator
             let generator_start_time = time::precise_time_ns();
 
-            let dummy_buffer = self.dummy_buffer.get_sized_mut(frames_this_buffer);
-
-            let mut generator_a_buffer = self.generator_a_buffer.get_sized_mut(frames_this_buffer);
-            let mut generator_b_buffer = self.generator_b_buffer.get_sized_mut(frames_this_buffer);
-            let mut generator_c_buffer = self.generator_c_buffer.get_sized_mut(frames_this_buffer);
-            let mut generator_d_buffer = self.generator_d_buffer.get_sized_mut(frames_this_buffer);
-            let mut mod_sum_buffer = self.mod_sum_buffer.get_sized_mut(frames_this_buffer);
-
-            match self.shared_state.parameters.get_real_value(
-                ParameterId::GeneratorRouting) as usize {
-            0 => {
-                // Parallel Stacks
-                // A -> B -v
-                //         |-> out
-                // C -> D -^
-                self.generator_a.process_buffer(
-                    &mut generator_a_buffer,
-                    &dummy_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
-
-                self.generator_b.process_buffer(
-                    &mut generator_b_buffer,
-                    &generator_a_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
+            for generator in self.generators.iter_mut() {
+                generator.update_wavetable(&self.shared_state.wavetables);
+            }
 
-                self.generator_c.process_buffer(
-                    &mut generator_c_buffer,
-                    &dummy_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
+            let algorithm = Algorithm::from_params(&self.shared_state.parameters);
 
-                self.generator_d.process_buffer(
-                    &mut generator_d_buffer,
-                    &generator_c_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
+            // The generators run at a multiple of the sample rate if oversampling
+            // is enabled, so their events are moved to the matching frames.
+            let factor = oversampling::get_factor(&self.shared_state.parameters);
+            let oversampled_frames = frames_this_buffer * factor;
+            let oversampled_rate = self.sample_rate * factor as defs::Sample;
+            self.oversampled_event_buffer.clear();
+            for (frame_num, engine_event) in self.engine_event_buffer.iter() {
+                self.oversampled_event_buffer.push((frame_num * factor, engine_event.clone()));
+            }
 
-                slice::equilibrium(mono_buffer);
-                sample::slice::add_in_place(&mut mono_buffer, &generator_b_buffer);
-                sample::slice::add_in_place(&mut mono_buffer, &generator_d_buffer);
-            },
-            1 => {
-                // Triple Mod Stack
-                // A -> B -> C -> D -> out
-                self.generator_a.process_buffer(
-                    &mut generator_a_buffer,
-                    &dummy_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
+            // Noise, which is only produced while it is heard or modulates a generator
+            let noise_buffer = self.noise_buffer.get_sized_mut(frames_this_buffer);
+            let noise_mix_buffer = self.noise_mix_buffer.get_sized_mut(frames_this_buffer);
+            self.noise.process_buffer(
+                noise_buffer,
+                noise_mix_buffer,
+                self.engine_event_buffer.iter(),
+                &self.shared_state.parameters
+            );
+            // The amounts that the noise modulates the generators are read once
+            // per buffer, after the noise has applied any changes to them.
+            let mut noise_modulation = [0.0; NUM_GENERATORS];
+            for (target, amount) in noise_modulation.iter_mut().enumerate() {
+                *amount = self.shared_state.parameters.get_real_value(
+                    noise::get_modulation_parameter(target));
+            }
+            let noise_modulation_buffer = self.noise_modulation_buffer.get_sized_mut(oversampled_frames);
+            if noise_modulation.iter().any(|amount| *amount != 0.0) {
+                oversampling::hold(noise_buffer, noise_modulation_buffer);
+            }
 
-                self.generator_b.process_buffer(
-                    &mut generator_b_buffer,
-                    &generator_a_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
+            // Make sure buffers of generators that haven't been processed yet
+            // (which is possible when generators modulate each other in a loop)
+            // are the right size.
+            for generator_buffer in self.generator_buffers.iter_mut() {
+                generator_buffer.get_sized_mut(oversampled_frames);
+            }
+            for sync_buffer in self.sync_buffers.iter_mut() {
+                sync_buffer.get_sized_mut(oversampled_frames);
+            }
 
-                self.generator_c.process_buffer(
-                    &mut generator_c_buffer,
-                    &generator_b_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
+            for target in algorithm.get_processing_order().iter() {
+                // Sum the outputs of the generators that modulate this generator
+                let mod_sum_buffer = self.mod_sum_buffer.get_sized_mut(oversampled_frames);
+                slice::equilibrium(mod_sum_buffer);
+                for source in 0..NUM_GENERATORS {
+                    let amount = algorithm.get_modulation(source, *target);
+                    if amount != 0.0 {
+                        gain::add_buffer_with_fixed_gain(
+                            amount,
+                            self.generator_buffers[source].get_sized_mut(oversampled_frames),
+                            mod_sum_buffer);
+                    }
+                }
+                if noise_modulation[*target] != 0.0 {
+                    gain::add_buffer_with_fixed_gain(
+                        noise_modulation[*target], noise_modulation_buffer, mod_sum_buffer);
+                }
 
-                self.generator_d.process_buffer(
-                    &mut generator_d_buffer,
-                    &generator_c_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
+                // Copy the sync buffer of the generator this generator is synced to
+                let sync_input_buffer = match algorithm.get_sync_source(*target) {
+                    Some(source) => {
+                        let sync_input_buffer = self.sync_input_buffer.get_sized_mut(oversampled_frames);
+                        slice::write(sync_input_buffer,
+                                     self.sync_buffers[source].get_sized_mut(oversampled_frames));
+                        Some(&sync_input_buffer[..])
+                    },
+                    None => None,
+                };
+
+                self.generators[*target].process_buffer(
+                    self.generator_buffers[*target].get_sized_mut(oversampled_frames),
+                    mod_sum_buffer,
+                    sync_input_buffer,
+                    self.sync_buffers[*target].get_sized_mut(oversampled_frames),
+                    self.oversampled_event_buffer.iter(),
+                    oversampled_rate,
                     &self.shared_state.parameters
                 );
 
-                sample::slice::write(&mut mono_buffer, &generator_d_buffer);
-            },
-            2 => {
-                // Triple Mod Branch
-                // A -v
-                // B -+-> D -> out
-                // C -^
-                self.generator_a.process_buffer(
-                    &mut generator_a_buffer,
-                    &dummy_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
+                // Ring modulation multiplies the output by another generator's output
+                if let Some(source) = algorithm.get_ring_source(*target) {
+                    let ring_buffer = self.ring_buffer.get_sized_mut(oversampled_frames);
+                    slice::write(ring_buffer,
+                                 self.generator_buffers[source].get_sized_mut(oversampled_frames));
+                    gain::process_buffer(ring_buffer,
+                                         self.generator_buffers[*target].get_sized_mut(oversampled_frames));
+                }
+            }
 
-                self.generator_b.process_buffer(
-                    &mut generator_b_buffer,
-                    &dummy_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
+            let sub_oscillator_buffer = self.sub_oscillator_buffer.get_sized_mut(frames_this_buffer);
+            self.sub_oscillator.process_buffer(
+                sub_oscillator_buffer,
+                self.engine_event_buffer.iter(),
+                self.sample_rate,
+                &self.shared_state.parameters
+            );
+
+            // Mix the carriers, any generators that are heard directly, the noise
+            // and the sub-oscillator.
+            // The mix level reduces the overall level to avoid clipping at later stages.
+            let mix_level = self.shared_state.parameters.get_real_value(ParameterId::MixLevel);
+            let oversampled_mix_buffer = self.oversampled_mix_buffer.get_sized_mut(oversampled_frames);
+            slice::equilibrium(oversampled_mix_buffer);
+            for generator in 0..NUM_GENERATORS {
+                let level = algorithm.get_mix_level(generator, &self.shared_state.parameters);
+                if level != 0.0 {
+                    gain::add_buffer_with_fixed_gain(
+                        level * mix_level,
+                        self.generator_buffers[generator].get_sized_mut(oversampled_frames),
+                        oversampled_mix_buffer);
+                }
+            }
+            // Return the generators to the base sample rate.
+            // The noise and sub-oscillator are produced at the base sample rate.
+            self.decimator.process_buffer(oversampled_mix_buffer, mono_buffer, factor);
+            gain::add_buffer_with_fixed_gain(mix_level, noise_mix_buffer, mono_buffer);
+            gain::add_buffer_with_fixed_gain(mix_level, sub_oscillator_buffer, mono_buffer);
 
-                self.generator_c.process_buffer(
-                    &mut generator_c_buffer,
-                    &dummy_buffer,
-                    self.engine_event_buffer.iter(),
-                    self.sample_rate,
-                    &self.shared_state.parameters
-                );
+            self.timing_data.generator = (time::precise_time_ns() - generator_start_time) / 1000;
 
-                slice::equilibrium(mod_sum_buffer);
-                sample::slice::add_in_place(&mut mod_sum_buffer, &generator_a_buffer);
-                sample::slice::add_in_place(&mut mod_sum_buffer, &generator_b_buffer);
-                sample::slice::add_in_place(&mut mod_sum_buffer, &generator_c_buffer);
+            // Use the amp envelope to apply gain to generator output
+            let gain_start_time = time::precise_time_ns();
+            gain::process_buffer(amp_envelope_buffer, mono_buffer);
+            self.timing_data.gain = (time::precise_time_ns() - gain_start_time) / 1000;
 
-                self.generator_d.process_buffer(
-                    &mut generator_d_buffer,
-                    &mod_sum_buffer,
+        } else {
+            // The generator envelopes are cheap, and keep running while nothing
+            // is heard, so that they carry on through their release.
+            for generator in self.generators.iter_mut() {
+                generator.process_silent_buffer(
+                    frames_this_buffer,
                     self.engine_event_buffer.iter(),
                     self.sample_rate,
                     &self.shared_state.parameters
                 );
-
-                sample::slice::write(&mut mono_buffer, &generator_d_buffer);
-            },
-            _ => panic!("Unknown generator routing"),
-            };
-            // Reduce level to avoid clipping at later stages
-            gain::process_buffer_fixed_gain(0.5, &mut mono_buffer);
-
-            self.timing_data.generator = (time::precise_time_ns() - generator_start_time) / 1000;
-
-            // Use ADSR to apply gain to generator output
-            let gain_start_time = time::precise_time_ns();
-            gain::process_buffer(adsr_buffer, mono_buffer);
-            self.timing_data.gain = (time::precise_time_ns() - gain_start_time) / 1000;
-
+            }
         }
 
         // Filter
         let filter_start_time = time::precise_time_ns();
 
         self.filter.process_buffer(
-            adsr_buffer,
+            filter_envelope_buffer,
             mono_buffer,
             self.engine_event_buffer.iter(),
             self.sample_rate,
@@ -387,9 +467,14 @@ impl Engine
 
     fn handle_midi_panic(&mut self) {
         self.note_selector.panic();
-        self.generator_a.panic();
-        self.generator_b.panic();
+        for generator in self.generators.iter_mut() {
+            generator.panic();
+        }
+        self.noise.panic();
+        self.sub_oscillator.panic();
+        self.decimator.panic();
         self.adsr.panic();
+        self.dahdsr.panic();
         self.filter.panic();
         self.delay.panic();
     }
@@ -422,3 +507,104 @@ impl TimingData {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_buffer_parameter_events() {
+        let params = BaseliskPluginParameters::default();
+        let events = vec![
+            (10, EngineEvent::ModulateParameter {
+                param_id: ParameterId::AmpEnvelope, value: 1.0 }),
+            (20, EngineEvent::ModulateParameter {
+                param_id: ParameterId::FilterEnvelope, value: 1.0 }),
+            (25, EngineEvent::ModulateParameter {
+                param_id: ParameterId::RoutingAToB, value: 0.5 }),
+            (25, EngineEvent::ModulateParameter {
+                param_id: ParameterId::GeneratorBDirect, value: 0.5 }),
+            (25, EngineEvent::ModulateParameter {
+                param_id: ParameterId::MixLevel, value: 0.5 }),
+            (25, EngineEvent::ModulateParameter {
+                param_id: ParameterId::GeneratorCSyncSource, value: 0.5 }),
+            (25, EngineEvent::ModulateParameter {
+                param_id: ParameterId::Oversampling, value: 1.0 }),
+            // Parameters read by processors are left to them
+            (30, EngineEvent::ModulateParameter {
+                param_id: ParameterId::FilterFrequency, value: 1.0 }),
+        ];
+        apply_buffer_parameter_events(&events, &params);
+        assert_eq!(params.get_real_value(ParameterId::AmpEnvelope), 1.0);
+        assert_eq!(params.get_real_value(ParameterId::FilterEnvelope), 1.0);
+        assert_eq!(params.get_parameter(ParameterId::RoutingAToB), 0.5);
+        assert_eq!(params.get_parameter(ParameterId::GeneratorBDirect), 0.5);
+        assert_eq!(params.get_parameter(ParameterId::MixLevel), 0.5);
+        assert_eq!(params.get_parameter(ParameterId::GeneratorCSyncSource), 0.5);
+        assert_eq!(params.get_parameter_text(ParameterId::Oversampling), "8x");
+        assert_eq!(params.get_real_value(ParameterId::FilterFrequency), 100.0);
+    }
+
+    #[test]
+    /// The generator envelopes keep running after the amp envelope has finished,
+    /// so the next note starts its attack from the level they have reached.
+    fn test_generator_envelopes_run_while_silent() {
+        let sample_rate = 1000.0;
+        let buffer_len = 100;
+        let shared_state = Arc::new(SharedState::new());
+        let params = &shared_state.parameters;
+        for (param_id, value) in &[
+            (ParameterId::GeneratorRouting, "dx7_32"),
+            (ParameterId::AdsrAttack, "0.001"),
+            (ParameterId::AdsrSustain, "1.0"),
+            (ParameterId::AdsrRelease, "0.02"),
+            (ParameterId::GeneratorAAttack, "1.0"),
+            (ParameterId::GeneratorARelease, "1.0"),
+            // Output a constant 1.0, so that the output of generator A is its envelope
+            (ParameterId::GeneratorAFrequencyMode, "fixed"),
+            (ParameterId::GeneratorAFixedFrequency, "1.0"),
+            (ParameterId::GeneratorAPhaseMode, "reset"),
+            (ParameterId::GeneratorAStartPhase, "0.25"),
+        ] {
+            params.update_real_value_from_string(*param_id, String::from(*value)).unwrap();
+        }
+        let mut engine = Engine::new(Arc::clone(&shared_state), false);
+        engine.set_sample_rate(sample_rate);
+
+        // A reference envelope that runs through every buffer
+        let mut reference = Adsr::new(AdsrId::Generator(0));
+        let mut reference_buffer = vec![[0.0]; buffer_len];
+        let mut left = vec![[0.0]; buffer_len];
+        let mut right = vec![[0.0]; buffer_len];
+        let mut run = |engine: &mut Engine, status: Option<u8>| {
+            engine.clear_midi_buffer();
+            let mut events = vec![];
+            if let Some(status) = status {
+                engine.push_raw_midi(RawMidi { time: 0, status, data1: 60, data2: 127 });
+                let note = if status == 0x90 { Some(60) } else { None };
+                events.push((0, EngineEvent::NoteChange { note, velocity: 127 }));
+            }
+            engine.audio_requested(&mut left, &mut right);
+            reference.process_buffer(&mut reference_buffer, events.iter(), sample_rate, params);
+        };
+
+        // Hold a note until the generator envelope reaches its sustain,
+        // then release it and wait for the amp envelope to finish.
+        run(&mut engine, Some(0x90));
+        for _ in 0..14 {
+            run(&mut engine, None);
+        }
+        run(&mut engine, Some(0x80));
+        for _ in 0..5 {
+            run(&mut engine, None);
+        }
+
+        run(&mut engine, Some(0x90));
+        let expected = reference_buffer[0][0];
+        let actual = engine.generator_buffers[0].get_sized_mut(buffer_len)[0][0];
+        // About halfway through the release, rather than where it was when
+        // the amp envelope finished.
+        assert!(expected > 0.3 && expected < 0.5, "expected == {}", expected);
+        assert!((actual - expected).abs() < 0.01, "actual == {}, expected == {}", actual, expected);
+    }
+}
@@ -0,0 +1,292 @@
+use defs;
+use engine::{
+    algorithm::NUM_GENERATORS,
+    traits,
+};
+use rand::{
+    FromEntropy,
+    Rng,
+    rngs::SmallRng,
+};
+use shared::{
+    event::EngineEvent,
+    parameter::{
+        BaseliskPluginParameters,
+        ParameterId,
+    },
+};
+use std::slice;
+
+/// Colours of noise that the noise source can produce.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoiseType {
+    /// Equal power at all frequencies
+    White,
+    /// Power falls by 3 dB per octave
+    Pink,
+    /// Power falls by 6 dB per octave
+    Brown,
+}
+
+impl NoiseType {
+    /// Get the noise type corresponding to the value of the NoiseType parameter.
+    pub fn from_value(value: defs::Sample) -> Self {
+        match value as usize {
+            0 => NoiseType::White,
+            1 => NoiseType::Pink,
+            2 => NoiseType::Brown,
+            _ => panic!("Unknown noise type"),
+        }
+    }
+}
+
+/// Get the parameter for the amount that the noise source modulates a generator.
+pub fn get_modulation_parameter(target: usize) -> ParameterId {
+    match target {
+        0 => ParameterId::NoiseToA,
+        1 => ParameterId::NoiseToB,
+        2 => ParameterId::NoiseToC,
+        3 => ParameterId::NoiseToD,
+        4 => ParameterId::NoiseToE,
+        5 => ParameterId::NoiseToF,
+        _ => panic!("Unknown generator ID"),
+    }
+}
+
+/// A noise source, which can be mixed into the output or used to modulate generators.
+pub struct Noise {
+    rng: SmallRng,
+    // Filter states used to colour white noise
+    pink_state: [defs::Sample; 7],
+    brown_state: defs::Sample,
+}
+
+impl Noise {
+    pub fn new() -> Self {
+        Self::from_rng(SmallRng::from_entropy())
+    }
+
+    fn from_rng(rng: SmallRng) -> Self {
+        Self {
+            rng,
+            pink_state: [0.0; 7],
+            brown_state: 0.0,
+        }
+    }
+
+    /// Produce the next sample of white noise, in the range -1.0 <= x < 1.0.
+    fn white(&mut self) -> defs::Sample {
+        self.rng.gen_range(-1.0, 1.0)
+    }
+
+    /// Produce the next sample of pink noise, using Paul Kellet's filter.
+    fn pink(&mut self) -> defs::Sample {
+        let white = self.white();
+        let b = &mut self.pink_state;
+        b[0] = 0.99886 * b[0] + white * 0.055_517_9;
+        b[1] = 0.99332 * b[1] + white * 0.075_075_9;
+        b[2] = 0.96900 * b[2] + white * 0.153_852;
+        b[3] = 0.86650 * b[3] + white * 0.310_485_6;
+        b[4] = 0.55000 * b[4] + white * 0.532_952_2;
+        b[5] = -0.7616 * b[5] - white * 0.016_898;
+        let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+        b[6] = white * 0.115_926;
+        // Scale to roughly the same peak level as white noise
+        pink * 0.11
+    }
+
+    /// Produce the next sample of brown noise, using a leaky integrator.
+    fn brown(&mut self) -> defs::Sample {
+        let white = self.white();
+        self.brown_state = (self.brown_state + 0.02 * white) / 1.02;
+        // Scale to roughly the same peak level as white noise
+        self.brown_state * 3.5
+    }
+
+    fn should_trigger_keyframe_for_param(&self, param: ParameterId) -> bool {
+        match param {
+            ParameterId::NoiseType |
+            ParameterId::NoiseLevel => true,
+            _ => (0..NUM_GENERATORS).any(|target| get_modulation_parameter(target) == param),
+        }
+    }
+
+    /// Produce noise into buffer, for modulating generators, and the noise
+    /// at the noise level into mix_buffer, for mixing into the output.
+    /// Noise is only produced while it is heard or modulates a generator.
+    pub fn process_buffer(&mut self,
+                          buffer: &mut defs::MonoFrameBufferSlice,
+                          mix_buffer: &mut defs::MonoFrameBufferSlice,
+                          mut engine_event_iter: slice::Iter<(usize, EngineEvent)>,
+                          params: &BaseliskPluginParameters)
+    {
+        let buffer_len = buffer.len();
+
+        let mut this_keyframe: usize = 0;
+        let mut next_keyframe: usize;
+        loop {
+            let next_event = engine_event_iter.next();
+
+            if let Some((frame_num, engine_event)) = next_event {
+                match engine_event {
+                    // Only noise parameter changes trigger keyframes
+                    EngineEvent::ModulateParameter { param_id, .. } =>
+                        if !self.should_trigger_keyframe_for_param(*param_id) { continue },
+                    _ => continue,
+                }
+                next_keyframe = *frame_num;
+            } else {
+                // No more events, so we'll process to the end of the buffer.
+                next_keyframe = buffer_len;
+            };
+
+            // Apply the old parameters up until next_keyframe.
+            let noise_type = NoiseType::from_value(params.get_real_value(ParameterId::NoiseType));
+            let level = params.get_real_value(ParameterId::NoiseLevel);
+            let modulates = (0..NUM_GENERATORS).any(|target|
+                params.get_real_value(get_modulation_parameter(target)) != 0.0);
+
+            let buffer_slice = buffer.get_mut(this_keyframe..next_keyframe).unwrap();
+            let mix_buffer_slice = mix_buffer.get_mut(this_keyframe..next_keyframe).unwrap();
+            if level != 0.0 || modulates {
+                self.generate(noise_type, buffer_slice);
+            } else {
+                for frame in buffer_slice.iter_mut() {
+                    frame[0] = 0.0;
+                }
+            }
+            for (mix_frame, frame) in mix_buffer_slice.iter_mut().zip(buffer_slice.iter()) {
+                mix_frame[0] = level * frame[0];
+            }
+
+            // We've reached the next_keyframe.
+            this_keyframe = next_keyframe;
+
+            // What we do now depends on whether we reached the end of the buffer.
+            if this_keyframe == buffer_len {
+                // Loop exit condition: reached the end of the buffer.
+                break
+            } else {
+                // Before the next iteration, use the event at this keyframe
+                // to update the current state.
+                if let Some((_, EngineEvent::ModulateParameter { param_id, value })) = next_event {
+                    params.set_parameter(*param_id, *value);
+                }
+            }
+        }
+    }
+
+    fn generate(&mut self, noise_type: NoiseType, buffer: &mut defs::MonoFrameBufferSlice) {
+        for frame in buffer.iter_mut() {
+            frame[0] = match noise_type {
+                NoiseType::White => self.white(),
+                NoiseType::Pink => self.pink(),
+                NoiseType::Brown => self.brown(),
+            };
+        }
+    }
+}
+
+impl traits::Processor for Noise {
+    fn panic(&mut self) {
+        self.pink_state = [0.0; 7];
+        self.brown_state = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use defs;
+    use rand::SeedableRng;
+
+    /// Produce noise of a type, from a fixed seed so that results are repeatable.
+    fn _run(noise_type: &str, num_samples: usize) -> Vec<defs::Sample> {
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(
+            ParameterId::NoiseType, String::from(noise_type)).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::NoiseLevel, String::from("1.0")).unwrap();
+        let mut noise = Noise::from_rng(SmallRng::seed_from_u64(1));
+        let mut buffer = vec![[0.0]; num_samples];
+        let mut mix_buffer = vec![[0.0]; num_samples];
+        noise.process_buffer(&mut buffer, &mut mix_buffer, [].iter(), &params);
+        buffer.iter().map(|frame| frame[0]).collect()
+    }
+
+    /// The ratio of the power of the difference between consecutive samples
+    /// to the power of the signal. This is larger for brighter noise:
+    /// about 2.0 for white noise, and close to 0.0 for noise that is mostly
+    /// low frequencies.
+    fn _brightness(samples: &[defs::Sample]) -> defs::Sample {
+        let power: defs::Sample = samples.iter().map(|x| x * x).sum();
+        let difference_power: defs::Sample = samples.windows(2)
+            .map(|pair| (pair[1] - pair[0]) * (pair[1] - pair[0]))
+            .sum();
+        difference_power / power
+    }
+
+    #[test]
+    fn test_white_noise() {
+        let samples = _run("white", 10000);
+        assert!(samples.iter().all(|x| *x >= -1.0 && *x < 1.0));
+        let mean = samples.iter().sum::<defs::Sample>() / samples.len() as defs::Sample;
+        assert!(mean.abs() < 0.05, "mean == {}", mean);
+        let brightness = _brightness(&samples);
+        assert!((brightness - 2.0).abs() < 0.1, "brightness == {}", brightness);
+    }
+
+    #[test]
+    /// Pink noise is darker than white noise, and brown noise is darker still.
+    fn test_noise_colours() {
+        let pink = _brightness(&_run("pink", 10000));
+        let brown = _brightness(&_run("brown", 10000));
+        assert!(pink < 1.0, "pink brightness == {}", pink);
+        assert!(brown < pink / 2.0, "brown brightness == {}, pink brightness == {}", brown, pink);
+    }
+
+    #[test]
+    /// Coloured noise stays within a usable level.
+    fn test_noise_level() {
+        for noise_type in &["pink", "brown"] {
+            let samples = _run(noise_type, 10000);
+            let peak = samples.iter().fold(0.0, |peak: defs::Sample, x| peak.max(x.abs()));
+            assert!(peak > 0.1 && peak < 1.5, "{} noise peak == {}", noise_type, peak);
+        }
+    }
+
+    #[test]
+    /// Noise parameter changes take effect at the frame they happen.
+    fn test_parameter_events() {
+        let params = BaseliskPluginParameters::default();
+        let events = [
+            (100, EngineEvent::ModulateParameter {
+                param_id: ParameterId::NoiseLevel, value: 0.5 }),
+            (200, EngineEvent::ModulateParameter {
+                param_id: ParameterId::NoiseLevel, value: 0.0 }),
+            (200, EngineEvent::ModulateParameter {
+                param_id: ParameterId::NoiseToC, value: 1.0 }),
+            (300, EngineEvent::ModulateParameter {
+                param_id: ParameterId::NoiseToC, value: 0.0 }),
+        ];
+        let mut noise = Noise::from_rng(SmallRng::seed_from_u64(1));
+        let mut buffer = vec![[0.0]; 400];
+        let mut mix_buffer = vec![[0.0]; 400];
+        noise.process_buffer(&mut buffer, &mut mix_buffer, events.iter(), &params);
+
+        let is_silent = |frames: &[defs::MonoFrame]| frames.iter().all(|frame| frame[0] == 0.0);
+        // Silent until the level is raised
+        assert!(is_silent(&buffer[..100]));
+        assert!(is_silent(&mix_buffer[..100]));
+        // Heard at the noise level
+        assert!(!is_silent(&mix_buffer[100..200]));
+        for (mix_frame, frame) in mix_buffer[100..200].iter().zip(buffer[100..200].iter()) {
+            assert_eq!(mix_frame[0], 0.5 * frame[0]);
+        }
+        // Only produced for modulation, then stopped
+        assert!(is_silent(&mix_buffer[200..]));
+        assert!(!is_silent(&buffer[200..300]));
+        assert!(is_silent(&buffer[300..]));
+        assert_eq!(params.get_real_value(ParameterId::NoiseToC), 0.0);
+    }
+}
@@ -9,6 +9,7 @@ use engine::traits;
 #[derive(Default)]
 pub struct MonoNoteSelector {
     notes_held: Vec<bool>,
+    note_velocities: Vec<u8>,
     note_priority_stack: Vec<u8>,
     note_selected: Option<u8>,
 }
@@ -23,6 +24,7 @@ impl MonoNoteSelector {
     pub fn new() -> Self {
         Self {
             notes_held: vec![false; 128],
+            note_velocities: vec![0; 128],
             note_priority_stack: Vec::with_capacity(128),
             note_selected: None,
         }
@@ -34,8 +36,8 @@ impl MonoNoteSelector {
         // result is an Option<Option<u8>> indicating whether the note changed as a
         // result of the MIDI event.
         let result = match midi_event {
-            MidiEvent::NoteOn { note, .. } => {
-                self.note_on(*note)
+            MidiEvent::NoteOn { note, velocity } => {
+                self.note_on(*note, *velocity)
             }
             MidiEvent::NoteOff { note } => {
                 self.note_off(*note)
@@ -43,14 +45,21 @@ impl MonoNoteSelector {
             _ => MidiEventResult::Ignore,
         };
         match result {
-            MidiEventResult::NoteChange(note_change) => Some(EngineEvent::NoteChange{ note: note_change }),
+            MidiEventResult::NoteChange(note_change) => Some(EngineEvent::NoteChange{
+                note: note_change,
+                // When returning to a held note, it keeps the velocity it was played with
+                velocity: note_change.map_or(0, |note| self.note_velocities[note as usize]),
+            }),
             MidiEventResult::Ignore => None,
         }
     }
 
     /// Return Some(Option<u8>) if the note changed as a result of this event.
     /// Otherwise, return None.
-    fn note_on(&mut self, note: u8) -> MidiEventResult {
+    fn note_on(&mut self, note: u8, velocity: u8) -> MidiEventResult {
+        if let Some(velocity_ref) = self.note_velocities.get_mut(note as usize) {
+            *velocity_ref = velocity;
+        }
         if let Some(note_held_ref) = self.notes_held.get_mut(note as usize) {
             // It's possible (due to dropped note events)
             // that the note was not actually off. Check for that here.
@@ -111,7 +120,7 @@ mod tests {
     fn check_note_change(event: EngineEvent,
                          expected_note: Option<u8>) -> bool
     {
-        if let EngineEvent::NoteChange { note } = event {
+        if let EngineEvent::NoteChange { note, .. } = event {
             if note == expected_note {
                 return true
             }
@@ -194,6 +203,30 @@ mod tests {
         let output = output.unwrap();
         assert!(check_note_change(output, None));
     }
+
+    #[test]
+    fn test_velocity() {
+        let mut note_selector = MonoNoteSelector::new();
+        note_selector.process_event(&MidiEvent::NoteOn{note: 10, velocity: 40});
+        let output = note_selector.process_event(&MidiEvent::NoteOn{note: 20, velocity: 100});
+        assert!(match output {
+            Some(EngineEvent::NoteChange { note: Some(20), velocity: 100 }) => true,
+            _ => false,
+        });
+
+        // Returning to the lower note uses the velocity it was played with
+        let output = note_selector.process_event(&MidiEvent::NoteOff{note: 20});
+        assert!(match output {
+            Some(EngineEvent::NoteChange { note: Some(10), velocity: 40 }) => true,
+            _ => false,
+        });
+
+        let output = note_selector.process_event(&MidiEvent::NoteOff{note: 10});
+        assert!(match output {
+            Some(EngineEvent::NoteChange { note: None, velocity: 0 }) => true,
+            _ => false,
+        });
+    }
 }
 
 impl traits::Processor for MonoNoteSelector {
@@ -0,0 +1,279 @@
+use defs;
+use engine::{
+    buffer::ResizableFrameBuffer,
+    traits,
+};
+use sample::slice;
+use shared::parameter::{
+    BaseliskPluginParameters,
+    ParameterId,
+};
+use std::f64;
+
+/// Number of taps of each half-band filter.
+/// This is of the form 4k + 3 so that the outermost taps are nonzero.
+const NUM_TAPS: usize = 63;
+
+/// Kaiser window shape parameter for the half-band filters.
+/// This gives around 80 dB of stopband attenuation.
+const KAISER_BETA: f64 = 8.0;
+
+/// Get the factor the generator section should be oversampled by.
+pub fn get_factor(params: &BaseliskPluginParameters) -> usize {
+    match params.get_real_value(ParameterId::Oversampling) as usize {
+        0 => 1,
+        1 => 2,
+        2 => 4,
+        3 => 8,
+        _ => panic!("Unknown oversampling factor"),
+    }
+}
+
+/// Upsample a buffer by repeating each sample to fill the output buffer.
+/// This is used for signals that are produced at the base sample rate
+/// but modulate generators at the oversampled rate.
+pub fn hold(input: &defs::MonoFrameBufferSlice,
+            output: &mut defs::MonoFrameBufferSlice)
+{
+    let factor = output.len() / input.len();
+    for (input_frame, output_frames) in input.iter().zip(output.chunks_mut(factor)) {
+        for output_frame in output_frames.iter_mut() {
+            *output_frame = *input_frame;
+        }
+    }
+}
+
+/// Zeroth order modified Bessel function of the first kind,
+/// used to compute the Kaiser window.
+fn bessel_i0(x: f64) -> f64 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut k = 1.0;
+    while term > 1e-12 * sum {
+        term *= (x / (2.0 * k)) * (x / (2.0 * k));
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
+
+/// Design a low pass FIR filter with its cutoff at a quarter of the sample rate,
+/// using a Kaiser windowed sinc. Every other tap (apart from the centre tap)
+/// of such a filter is zero, and the response is symmetric about the cutoff.
+fn design_half_band() -> [defs::Sample; NUM_TAPS] {
+    let half_width = (NUM_TAPS / 2) as f64;
+    let mut taps = [0.0; NUM_TAPS];
+    let mut sum = 0.0;
+    for (i, tap) in taps.iter_mut().enumerate() {
+        let n = i as f64 - half_width;
+        let sinc = if n == 0.0 {
+            1.0
+        } else {
+            (f64::consts::PI * n / 2.0).sin() / (f64::consts::PI * n / 2.0)
+        };
+        let window = bessel_i0(KAISER_BETA * (1.0 - (n / half_width) * (n / half_width)).sqrt())
+            / bessel_i0(KAISER_BETA);
+        let value = 0.5 * sinc * window;
+        *tap = value as defs::Sample;
+        sum += value;
+    }
+    // Normalize for unity gain at DC
+    for tap in taps.iter_mut() {
+        *tap /= sum as defs::Sample;
+    }
+    taps
+}
+
+/// One stage of decimation by a factor of two.
+struct HalfBandStage {
+    // The last NUM_TAPS input samples, stored twice over so that they can always
+    // be read as one contiguous slice, oldest first, starting at position.
+    history: [defs::Sample; 2 * NUM_TAPS],
+    position: usize,
+}
+
+impl HalfBandStage {
+    fn new() -> Self {
+        Self {
+            history: [0.0; 2 * NUM_TAPS],
+            position: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.history = [0.0; 2 * NUM_TAPS];
+        self.position = 0;
+    }
+
+    fn push(&mut self, value: defs::Sample) {
+        self.history[self.position] = value;
+        self.history[self.position + NUM_TAPS] = value;
+        self.position = (self.position + 1) % NUM_TAPS;
+    }
+
+    /// Filter the input buffer and keep every other sample.
+    /// The output buffer should be half the length of the input buffer.
+    fn process_buffer(&mut self,
+                      taps: &[defs::Sample; NUM_TAPS],
+                      input: &defs::MonoFrameBufferSlice,
+                      output: &mut defs::MonoFrameBufferSlice)
+    {
+        for (output_frame, input_frames) in output.iter_mut().zip(input.chunks(2)) {
+            for input_frame in input_frames {
+                self.push(input_frame[0]);
+            }
+            let window = &self.history[self.position..self.position + NUM_TAPS];
+            output_frame[0] = window.iter()
+                .zip(taps.iter())
+                .map(|(value, tap)| value * tap)
+                .sum();
+        }
+    }
+}
+
+/// Brings the output of the oversampled generator section back down to the
+/// base sample rate, using a cascade of half-band filters to remove content
+/// above the base Nyquist frequency before it can alias.
+pub struct Decimator {
+    factor: usize,
+    taps: [defs::Sample; NUM_TAPS],
+    // Stages in the order they are used for 8x oversampling;
+    // lower factors only use the later stages.
+    stages: [HalfBandStage; 3],
+    quarter_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+    half_buffer: ResizableFrameBuffer<defs::MonoFrame>,
+}
+
+impl Decimator {
+    pub fn new() -> Self {
+        Self {
+            factor: 1,
+            taps: design_half_band(),
+            stages: [HalfBandStage::new(), HalfBandStage::new(), HalfBandStage::new()],
+            quarter_buffer: ResizableFrameBuffer::new(),
+            half_buffer: ResizableFrameBuffer::new(),
+        }
+    }
+
+    /// Decimate the input buffer, which is at factor times the base sample rate,
+    /// into the output buffer.
+    pub fn process_buffer(&mut self,
+                          input: &defs::MonoFrameBufferSlice,
+                          output: &mut defs::MonoFrameBufferSlice,
+                          factor: usize)
+    {
+        // Filter state from a different factor would be at the wrong rate
+        if factor != self.factor {
+            self.factor = factor;
+            traits::Processor::panic(self);
+        }
+
+        let frames = output.len();
+        match factor {
+            1 => slice::write(output, input),
+            2 => self.stages[2].process_buffer(&self.taps, input, output),
+            4 => {
+                let half_buffer = self.half_buffer.get_sized_mut(2 * frames);
+                self.stages[1].process_buffer(&self.taps, input, half_buffer);
+                self.stages[2].process_buffer(&self.taps, half_buffer, output);
+            },
+            8 => {
+                let quarter_buffer = self.quarter_buffer.get_sized_mut(4 * frames);
+                self.stages[0].process_buffer(&self.taps, input, quarter_buffer);
+                let half_buffer = self.half_buffer.get_sized_mut(2 * frames);
+                self.stages[1].process_buffer(&self.taps, quarter_buffer, half_buffer);
+                self.stages[2].process_buffer(&self.taps, half_buffer, output);
+            },
+            _ => panic!("Unsupported oversampling factor"),
+        }
+    }
+}
+
+impl traits::Processor for Decimator {
+    fn panic(&mut self) {
+        for stage in self.stages.iter_mut() {
+            stage.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use defs;
+
+    const SAMPLE_RATE: defs::Sample = 48000.0;
+    const NUM_FRAMES: usize = 4800;
+
+    /// Decimate a sine at a frequency, and get the amplitude of the output
+    /// once the filters have settled.
+    fn _decimated_amplitude(frequency: f64, factor: usize) -> defs::Sample {
+        // The input is computed at double precision, as the phase error of
+        // single precision would add broadband noise at around -60 dB.
+        let oversampled_rate = f64::from(SAMPLE_RATE) * factor as f64;
+        let input: Vec<defs::MonoFrame> = (0..NUM_FRAMES * factor)
+            .map(|i| [(2.0 * f64::consts::PI * frequency * i as f64 / oversampled_rate).sin()
+                      as defs::Sample])
+            .collect();
+        let mut output = vec![[0.0]; NUM_FRAMES];
+        let mut decimator = Decimator::new();
+        decimator.process_buffer(&input, &mut output, factor);
+        // Estimate the amplitude from the RMS level, as the peaks of the output
+        // depend on where the samples fall in the period.
+        let settled = &output[NUM_FRAMES / 2..];
+        let power: defs::Sample = settled.iter().map(|frame| frame[0] * frame[0]).sum();
+        (2.0 * power / settled.len() as defs::Sample).sqrt()
+    }
+
+    #[test]
+    fn test_no_oversampling() {
+        let input = vec![[0.1], [0.2], [0.3]];
+        let mut output = vec![[0.0]; 3];
+        Decimator::new().process_buffer(&input, &mut output, 1);
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    /// Frequencies well below the base Nyquist frequency are passed at unity gain.
+    fn test_passband() {
+        for factor in &[2, 4, 8] {
+            for frequency in &[100.0, 1000.0, 15000.0] {
+                let amplitude = _decimated_amplitude(*frequency, *factor);
+                assert!((amplitude - 1.0).abs() < 0.01,
+                        "{}x, {} Hz: amplitude == {}", factor, frequency, amplitude);
+            }
+        }
+    }
+
+    #[test]
+    /// Frequencies that would alias at the base sample rate are removed.
+    fn test_stopband() {
+        for factor in &[2, 4, 8] {
+            for frequency in &[30000.0, 40000.0, 47000.0] {
+                let amplitude = _decimated_amplitude(*frequency, *factor);
+                assert!(amplitude < 1e-3,
+                        "{}x, {} Hz: amplitude == {}", factor, frequency, amplitude);
+            }
+        }
+        // Frequencies near the oversampled Nyquist frequency
+        let amplitude = _decimated_amplitude(180_000.0, 8);
+        assert!(amplitude < 1e-3, "amplitude == {}", amplitude);
+    }
+
+    #[test]
+    fn test_hold() {
+        let input = vec![[0.1], [0.2]];
+        let mut output = vec![[0.0]; 8];
+        hold(&input, &mut output);
+        assert_eq!(output, vec![[0.1], [0.1], [0.1], [0.1], [0.2], [0.2], [0.2], [0.2]]);
+    }
+
+    #[test]
+    fn test_get_factor() {
+        let params = BaseliskPluginParameters::default();
+        assert_eq!(get_factor(&params), 1);
+        params.update_real_value_from_string(
+            ParameterId::Oversampling, String::from("4x")).unwrap();
+        assert_eq!(get_factor(&params), 4);
+    }
+}
@@ -16,6 +16,40 @@ pub fn get_pitch_bend_semitones(midi_pitch_wheel_value: u16,
             defs::Sample::from(midi_pitch_wheel_value) - 8192.0) / 8192.0
 }
 
+/// Smoothing for pitch bends, to reduce audible stepping for wide pitch bends
+/// (e.g. 12+ semitones).
+/// The algorithm activates when the pitch wheel is used, and gently accelerates
+/// the actual frequency towards the target frequency.
+pub struct PitchBendPortamento {
+    multiplier: defs::Sample,
+}
+
+impl PitchBendPortamento {
+    pub fn new() -> Self {
+        Self {
+            multiplier: 1.0,
+        }
+    }
+
+    /// Move a frequency towards a target frequency, returning the new frequency.
+    pub fn approach(&mut self,
+                    frequency: defs::Sample,
+                    target_frequency: defs::Sample) -> defs::Sample
+    {
+        let diff = target_frequency - frequency;
+        if diff > 0.0 {
+            self.multiplier = defs::Sample::min(self.multiplier * 1.005, 1.05);
+            defs::Sample::min(self.multiplier * frequency, target_frequency)
+        } else if diff < 0.0 {
+            self.multiplier = defs::Sample::max(self.multiplier * (1.0 / 1.005), 1.0 / 1.05);
+            defs::Sample::max(self.multiplier * frequency, target_frequency)
+        } else {
+            self.multiplier = 1.0;
+            frequency
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
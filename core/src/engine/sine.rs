@@ -0,0 +1,145 @@
+//! Sine evaluation for the generators.
+//!
+//! Computing a sine for every sample of every generator is the hot path of
+//! the engine, so faster approximations are available. The implementation
+//! used by `sine` is selected at build time with a cargo feature:
+//!
+//! * (default): `f32::sin`
+//! * `fast_sine_table`: table lookup with linear interpolation
+//! * `fast_sine_polynomial`: polynomial approximation
+//!
+//! If both features are enabled, the polynomial is used.
+//! Run `cargo bench` to compare the implementations on a particular machine.
+
+use defs;
+
+/// Number of table entries per period of the sine wave.
+/// This must match SINE_TABLE_SIZE in build.rs.
+const SINE_TABLE_SIZE: usize = 1024;
+
+// Generated by the build script
+include!(concat!(env!("OUT_DIR"), "/sine_table.rs"));
+
+/// Get the sine of a phase, where a phase of 1 is a whole period.
+/// (That is, this is sin(2 * PI * phase).)
+#[inline]
+pub fn sine(phase: defs::Sample) -> defs::Sample {
+    if cfg!(feature = "fast_sine_polynomial") {
+        polynomial_sine(phase)
+    } else if cfg!(feature = "fast_sine_table") {
+        table_sine(phase)
+    } else {
+        std_sine(phase)
+    }
+}
+
+/// Sine using the standard library.
+#[inline]
+pub fn std_sine(phase: defs::Sample) -> defs::Sample {
+    defs::Sample::sin(defs::TWOPI * phase)
+}
+
+/// Round to the nearest integer, for |x| < 2^22.
+/// Adding and subtracting 1.5 * 2^23 leaves no bits for a fractional part,
+/// so the FPU does the rounding. Unlike f32::round, this needs no library call
+/// and can be vectorized.
+#[inline]
+fn round(x: defs::Sample) -> defs::Sample {
+    const MAGIC: defs::Sample = 12_582_912.0;
+    (x + MAGIC) - MAGIC
+}
+
+/// Sine by lookup in a table, interpolating linearly between entries.
+/// The absolute error is below 5e-6.
+#[inline]
+pub fn table_sine(phase: defs::Sample) -> defs::Sample {
+    // Offset by half a period to get 0 <= phase <= 1, which is cheaper than
+    // wrapping with floor(). The offset is undone by negating the result.
+    let phase = phase - round(phase) + 0.5;
+    let position = phase * SINE_TABLE_SIZE as defs::Sample;
+    // Make sure the index stays within the table at phase == 1.
+    let index = usize::min(position as usize, SINE_TABLE_SIZE - 1);
+    let fraction = position - index as defs::Sample;
+    let a = SINE_TABLE[index];
+    let b = SINE_TABLE[index + 1];
+    -(a + fraction * (b - a))
+}
+
+/// Sine using an odd polynomial over a quarter period either side of zero,
+/// using the symmetry of the sine wave for other phases.
+/// The absolute error is below 1e-6.
+#[inline]
+pub fn polynomial_sine(phase: defs::Sample) -> defs::Sample {
+    // Reduce to -0.5 <= x <= 0.5, then fold to -0.25 <= x <= 0.25.
+    // This is written without branches so that loops over buffers can be
+    // vectorized.
+    let x = phase - round(phase);
+    let magnitude = x.abs();
+    let folded = if magnitude > 0.25 { 0.5 - magnitude } else { magnitude };
+    let x = folded.copysign(x);
+
+    // Taylor series to the 11th power, evaluated in Horner form
+    let y = defs::TWOPI * x;
+    let y2 = y * y;
+    y * (1.0 + y2 * (-1.0 / 6.0 + y2 * (1.0 / 120.0 + y2 * (-1.0 / 5040.0
+        + y2 * (1.0 / 362_880.0 + y2 * (-1.0 / 39_916_800.0))))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use defs;
+    use std::f64;
+
+    /// Find the largest absolute error of a sine implementation,
+    /// over phases spanning several periods either side of zero.
+    /// The reference is computed at double precision, so that f32::sin
+    /// can be held to the same standard as the approximations.
+    fn _max_error(sine_fn: fn(defs::Sample) -> defs::Sample) -> defs::Sample {
+        let num_samples = 100_000;
+        (0..num_samples)
+            .map(|i| 8.0 * (i as defs::Sample / num_samples as defs::Sample) - 4.0)
+            .map(|phase| {
+                let expected = (2.0 * f64::consts::PI * f64::from(phase)).sin();
+                (f64::from(sine_fn(phase)) - expected).abs() as defs::Sample
+            })
+            .fold(0.0, defs::Sample::max)
+    }
+
+    #[test]
+    fn test_std_accuracy() {
+        let error = _max_error(std_sine);
+        assert!(error < 5e-6, "max error == {}", error);
+    }
+
+    #[test]
+    fn test_table_accuracy() {
+        let error = _max_error(table_sine);
+        assert!(error < 5e-6, "max error == {}", error);
+    }
+
+    #[test]
+    fn test_polynomial_accuracy() {
+        let error = _max_error(polynomial_sine);
+        assert!(error < 1e-6, "max error == {}", error);
+    }
+
+    #[test]
+    /// The approximations are accurate where the sine wave peaks and crosses zero.
+    fn test_key_points() {
+        for sine_fn in &[table_sine as fn(defs::Sample) -> defs::Sample, polynomial_sine] {
+            assert!(sine_fn(0.0).abs() < 1e-6);
+            assert!((sine_fn(0.25) - 1.0).abs() < 1e-6);
+            assert!(sine_fn(0.5).abs() < 1e-6);
+            assert!((sine_fn(0.75) + 1.0).abs() < 1e-6);
+            assert!(sine_fn(1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    /// The selected implementation is a sine, whichever feature is enabled.
+    fn test_selected_implementation() {
+        let error = _max_error(sine);
+        assert!(error < 5e-6, "max error == {}", error);
+    }
+}
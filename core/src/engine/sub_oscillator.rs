@@ -0,0 +1,241 @@
+use defs;
+use engine::{
+    generator::get_frequency,
+    pitch_bend::{self, PitchBendPortamento},
+    traits,
+    waveform::{self, Waveform},
+};
+use shared::{
+    event::EngineEvent,
+    parameter::{
+        BaseliskPluginParameters,
+        ParameterId,
+    },
+};
+use std::slice;
+
+/// An oscillator that plays one or two octaves below the note, for reinforcing bass.
+/// It follows the note and pitch bend in the same way as the generators.
+pub struct SubOscillator {
+    sample_rate: defs::Sample,
+    note: u8,
+    pitch_bend_wheel_value: u16,
+    frequency: defs::Sample,
+    target_frequency: defs::Sample,
+    pitchbend_portamento: PitchBendPortamento,
+    phase: defs::Sample, // 0 <= phase < 1
+    waveform: Waveform,
+    level: defs::Sample,
+}
+
+impl SubOscillator {
+    pub fn new() -> Self {
+        Self {
+            sample_rate: 0.0,
+            note: 69,
+            pitch_bend_wheel_value: 8192,
+            frequency: 1.0,
+            target_frequency: 0.0,
+            pitchbend_portamento: PitchBendPortamento::new(),
+            phase: 0.0,
+            waveform: Waveform::Pulse,
+            level: 0.0,
+        }
+    }
+
+    fn should_trigger_keyframe_for_param(&self, param: ParameterId) -> bool {
+        match param {
+            ParameterId::SubOscillatorWaveform |
+            ParameterId::SubOscillatorOctave |
+            ParameterId::SubOscillatorLevel => true,
+            _ => false,
+        }
+    }
+
+    /// Get the frequency that the sub-oscillator should play at, for the current note.
+    fn get_target_frequency(&self,
+                            params: &BaseliskPluginParameters,
+                            pitch_bend_semitones: defs::Sample) -> defs::Sample
+    {
+        let octave = params.get_real_value(ParameterId::SubOscillatorOctave);
+        get_frequency(defs::Sample::from(self.note) + pitch_bend_semitones - 12.0 * octave)
+    }
+
+    pub fn process_buffer(&mut self,
+                          buffer: &mut defs::MonoFrameBufferSlice,
+                          mut engine_event_iter: slice::Iter<(usize, EngineEvent)>,
+                          sample_rate: defs::Sample,
+                          params: &BaseliskPluginParameters)
+    {
+        self.sample_rate = sample_rate;
+        let buffer_len = buffer.len();
+
+        let mut this_keyframe: usize = 0;
+        let mut next_keyframe: usize;
+        loop {
+            let next_event = engine_event_iter.next();
+
+            if let Some((frame_num, engine_event)) = next_event {
+                match engine_event {
+                    // Note changes will trigger keyframes only if there is a new note
+                    // (i.e. not None)
+                    EngineEvent::NoteChange{ note, .. } => {
+                        if note.is_none() {
+                            continue
+                        }
+                    },
+                    // Pitch bends and sub-oscillator parameter changes will also trigger keyframes
+                    EngineEvent::PitchBend{ .. } => (),
+                    EngineEvent::ModulateParameter { param_id, .. } =>
+                        if !self.should_trigger_keyframe_for_param(*param_id) { continue },
+                }
+                next_keyframe = *frame_num;
+            } else {
+                // No more events, so we'll process to the end of the buffer.
+                next_keyframe = buffer_len;
+            };
+
+            // Apply the old parameters up until next_keyframe.
+            let pitch_bend_semitones = pitch_bend::get_pitch_bend_semitones(
+                self.pitch_bend_wheel_value, params);
+            self.target_frequency = self.get_target_frequency(params, pitch_bend_semitones);
+            self.frequency = self.pitchbend_portamento.approach(
+                self.frequency, self.target_frequency);
+
+            self.waveform = match params.get_real_value(ParameterId::SubOscillatorWaveform) as usize {
+                0 => Waveform::Pulse,
+                1 => Waveform::Sine,
+                _ => panic!("Unknown sub-oscillator waveform"),
+            };
+            self.level = params.get_real_value(ParameterId::SubOscillatorLevel);
+
+            let buffer_slice = buffer.get_mut(this_keyframe..next_keyframe).unwrap();
+            self.generate(buffer_slice);
+
+            // We've reached the next_keyframe.
+            this_keyframe = next_keyframe;
+
+            // What we do now depends on whether we reached the end of the buffer.
+            if this_keyframe == buffer_len {
+                // Loop exit condition: reached the end of the buffer.
+                break
+            } else {
+                // Before the next iteration, use the event at this keyframe
+                // to update the current state.
+                let (_, event) = next_event.unwrap();
+                match event {
+                    EngineEvent::NoteChange{ note, .. } => {
+                        if let Some(note) = note {
+                            self.note = *note;
+                            // No portamento (set frequency to what target
+                            // frequency will be next iteration)
+                            self.frequency = self.get_target_frequency(
+                                params, pitch_bend_semitones);
+                        }
+                    },
+                    EngineEvent::PitchBend{ wheel_value } => {
+                        self.pitch_bend_wheel_value = *wheel_value;
+                    },
+                    EngineEvent::ModulateParameter { param_id, value } =>
+                        if self.should_trigger_keyframe_for_param(*param_id) {
+                            params.set_parameter(*param_id, *value);
+                        }
+                };
+            }
+        }
+    }
+
+    fn generate(&mut self, buffer: &mut defs::MonoFrameBufferSlice) {
+        let step = self.frequency / self.sample_rate;
+        for frame in buffer.iter_mut() {
+            frame[0] = self.level * waveform::get_sample(self.waveform, self.phase, step, 0.5);
+            self.phase = waveform::wrap_phase(self.phase + step);
+        }
+    }
+}
+
+impl traits::Processor for SubOscillator {
+    fn panic(&mut self) {
+        self.phase = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use defs;
+
+    const SAMPLE_RATE: defs::Sample = 48000.0;
+
+    /// Run the sub-oscillator for one second from a note-on at frame 0,
+    /// and count the rising zero crossings in the output.
+    fn _count_cycles(params: &BaseliskPluginParameters, note: u8) -> usize {
+        let mut sub = SubOscillator::new();
+        let mut buffer = vec![[0.0]; SAMPLE_RATE as usize];
+        let events = vec![(0, EngineEvent::NoteChange{ note: Some(note), velocity: 127 })];
+        sub.process_buffer(&mut buffer, events.iter(), SAMPLE_RATE, params);
+        buffer.windows(2)
+            .filter(|pair| pair[0][0] <= 0.0 && pair[1][0] > 0.0)
+            .count()
+    }
+
+    #[test]
+    fn test_octaves_below_note() {
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(
+            ParameterId::SubOscillatorLevel, String::from("1.0")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::SubOscillatorWaveform, String::from("sine")).unwrap();
+
+        // One octave below A440
+        let cycles = _count_cycles(&params, 69);
+        assert!((219..=221).contains(&cycles), "cycles == {}", cycles);
+
+        // Two octaves below A440, with a square wave
+        params.update_real_value_from_string(
+            ParameterId::SubOscillatorOctave, String::from("2")).unwrap();
+        params.update_real_value_from_string(
+            ParameterId::SubOscillatorWaveform, String::from("square")).unwrap();
+        let cycles = _count_cycles(&params, 69);
+        assert!((109..=111).contains(&cycles), "cycles == {}", cycles);
+    }
+
+    #[test]
+    fn test_level() {
+        let params = BaseliskPluginParameters::default();
+        params.update_real_value_from_string(
+            ParameterId::SubOscillatorLevel, String::from("0.5")).unwrap();
+        let mut sub = SubOscillator::new();
+        let mut buffer = vec![[0.0]; 1000];
+        let events = vec![(0, EngineEvent::NoteChange{ note: Some(45), velocity: 127 })];
+        sub.process_buffer(&mut buffer, events.iter(), SAMPLE_RATE, &params);
+        let peak = buffer.iter().fold(0.0, |peak: defs::Sample, frame| peak.max(frame[0].abs()));
+        assert!((peak - 0.5).abs() < 0.01, "peak == {}", peak);
+    }
+
+    #[test]
+    /// The sub-oscillator follows pitch bends, as the generators do.
+    fn test_pitch_bend() {
+        let params = BaseliskPluginParameters::default();
+        let mut sub = SubOscillator::new();
+        let mut buffer = vec![[0.0]; 64];
+        let events = vec![
+            (0, EngineEvent::NoteChange{ note: Some(69), velocity: 127 }),
+            (0, EngineEvent::PitchBend{ wheel_value: 16383 }),
+        ];
+        sub.process_buffer(&mut buffer, events.iter(), SAMPLE_RATE, &params);
+        let target_frequency = get_frequency(57.0 + 2.0 * 8191.0 / 8192.0);
+        assert!(sub.frequency > 220.0 && sub.frequency < target_frequency);
+
+        // The bend is smoothed over the following buffers
+        for _ in 0..100 {
+            sub.process_buffer(&mut buffer, [].iter(), SAMPLE_RATE, &params);
+        }
+        _assert_close(sub.frequency, target_frequency);
+    }
+
+    fn _assert_close(actual: defs::Sample, expected: defs::Sample) {
+        assert!((actual - expected).abs() < 0.01,
+                "actual == {}, expected == {}", actual, expected);
+    }
+}
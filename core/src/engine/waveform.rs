@@ -0,0 +1,201 @@
+use defs;
+use engine::sine::sine;
+
+/// Waveforms that a generator can produce.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Waveform {
+    Sine,
+    HalfSine,
+    Saw,
+    Pulse,
+    Triangle,
+    Wavetable,
+}
+
+impl Waveform {
+    /// Get the waveform corresponding to the value of a waveform enum parameter.
+    pub fn from_value(value: defs::Sample) -> Self {
+        match value as usize {
+            0 => Waveform::Sine,
+            1 => Waveform::HalfSine,
+            2 => Waveform::Saw,
+            3 => Waveform::Pulse,
+            4 => Waveform::Triangle,
+            5 => Waveform::Wavetable,
+            _ => panic!("Unknown waveform"),
+        }
+    }
+}
+
+/// PolyBLEP residual for a step discontinuity of height 2 at phase 0.
+/// phase: current phase, 0 <= phase < 1
+/// phase_step: the phase advanced per sample, 0 <= phase_step <= 0.5
+fn poly_blep(phase: defs::Sample, phase_step: defs::Sample) -> defs::Sample {
+    if phase < phase_step {
+        let t = phase / phase_step;
+        2.0 * t - t * t - 1.0
+    } else if phase > 1.0 - phase_step {
+        let t = (phase - 1.0) / phase_step;
+        t * t + 2.0 * t + 1.0
+    } else {
+        0.0
+    }
+}
+
+/// PolyBLAMP residual for a change of slope of 1 per sample at phase 0.
+/// This is the integral of the PolyBLEP residual, and smooths corners
+/// in the same way that PolyBLEP smooths steps.
+/// phase: current phase, 0 <= phase < 1
+/// phase_step: the phase advanced per sample, 0 <= phase_step <= 0.5
+fn poly_blamp(phase: defs::Sample, phase_step: defs::Sample) -> defs::Sample {
+    let distance = if phase < phase_step {
+        phase / phase_step
+    } else if phase > 1.0 - phase_step {
+        (1.0 - phase) / phase_step
+    } else {
+        return 0.0
+    };
+    let t = 1.0 - distance;
+    t * t * t / 6.0
+}
+
+/// Wrap a phase value to the range 0 <= phase < 1.
+pub fn wrap_phase(phase: defs::Sample) -> defs::Sample {
+    phase - phase.floor()
+}
+
+/// Get a band-limited sample of a waveform.
+/// phase: the current phase, 0 <= phase < 1. A phase of 0 is the rising zero
+///        crossing (or the rising edge) of the waveform.
+/// phase_step: the phase advanced per sample. This is used to smooth
+///             discontinuities, and may be negative for through-zero FM.
+/// pulse_width: the proportion of the period that the pulse waveform is high.
+pub fn get_sample(waveform: Waveform,
+                  phase: defs::Sample,
+                  phase_step: defs::Sample,
+                  pulse_width: defs::Sample) -> defs::Sample
+{
+    // Regions of neighbouring discontinuities overlap if the step is large,
+    // so limit the width of the smoothed region.
+    let dt = defs::Sample::min(phase_step.abs(), 0.5);
+
+    match waveform {
+        // Generators play wavetables themselves. If a generator has no wavetable
+        // loaded, it produces a sine instead.
+        Waveform::Sine | Waveform::Wavetable => sine(phase),
+        Waveform::HalfSine => {
+            // Positive half of the sine wave, then silence. The slope changes by
+            // 2 * PI per period at the start and end of the positive half.
+            let naive = if phase < 0.5 {
+                sine(phase)
+            } else {
+                0.0
+            };
+            naive + 2.0 * defs::PI * dt * (
+                poly_blamp(phase, dt) + poly_blamp(wrap_phase(phase + 0.5), dt))
+        },
+        Waveform::Saw => {
+            // Rises from -1 to 1, and falls back to -1 at the end of the period.
+            // Offset by half a period so that phase 0 is a rising zero crossing.
+            let saw_phase = wrap_phase(phase + 0.5);
+            2.0 * saw_phase - 1.0 - poly_blep(saw_phase, dt)
+        },
+        Waveform::Pulse => {
+            let naive = if phase < pulse_width { 1.0 } else { -1.0 };
+            naive + poly_blep(phase, dt) - poly_blep(wrap_phase(phase - pulse_width), dt)
+        },
+        Waveform::Triangle => {
+            // Peak at phase 0.25, trough at phase 0.75. The slope changes by
+            // 8 per period at each of these corners.
+            let naive = 1.0 - 4.0 * (wrap_phase(phase + 0.25) - 0.5).abs();
+            naive + 8.0 * dt * (
+                poly_blamp(wrap_phase(phase + 0.25), dt) - poly_blamp(wrap_phase(phase - 0.25), dt))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use defs;
+
+    /// Compare a single waveform sample against an expected value.
+    fn _test(waveform: Waveform,
+             phase: defs::Sample,
+             phase_step: defs::Sample,
+             pulse_width: defs::Sample,
+             expected: defs::Sample)
+    {
+        let actual = get_sample(waveform, phase, phase_step, pulse_width);
+        let error_abs = defs::Sample::abs(actual - expected);
+        if error_abs > 1e-6 {
+            panic!("For phase {}, actual output == {}, expected == {}, absolute error = {}",
+                   phase, actual, expected, error_abs);
+        }
+    }
+
+    #[test]
+    /// Away from discontinuities, the waveforms match their naive shapes.
+    fn test_naive_shapes() {
+        let step = 0.01;
+        _test(Waveform::Sine, 0.25, step, 0.5, 1.0);
+        _test(Waveform::HalfSine, 0.25, step, 0.5, 1.0);
+        _test(Waveform::HalfSine, 0.75, step, 0.5, 0.0);
+        _test(Waveform::Saw, 0.0, step, 0.5, 0.0);
+        _test(Waveform::Saw, 0.25, step, 0.5, 0.5);
+        _test(Waveform::Saw, 0.75, step, 0.5, -0.5);
+        _test(Waveform::Pulse, 0.1, step, 0.5, 1.0);
+        _test(Waveform::Pulse, 0.6, step, 0.5, -1.0);
+        _test(Waveform::Pulse, 0.3, step, 0.25, -1.0);
+        _test(Waveform::Triangle, 0.0, step, 0.5, 0.0);
+        _test(Waveform::Triangle, 0.125, step, 0.5, 0.5);
+        _test(Waveform::Triangle, 0.5, step, 0.5, 0.0);
+        _test(Waveform::Triangle, 0.625, step, 0.5, -0.5);
+    }
+
+    #[test]
+    /// At a step discontinuity, the smoothed waveform passes through the midpoint
+    /// of the step.
+    fn test_steps_are_smoothed() {
+        let step = 0.01;
+        // At the falling edge of the saw
+        _test(Waveform::Saw, 0.5, step, 0.5, 0.0);
+        // At the rising and falling edges of the pulse
+        _test(Waveform::Pulse, 0.0, step, 0.5, 0.0);
+        _test(Waveform::Pulse, 0.5, step, 0.5, 0.0);
+        _test(Waveform::Pulse, 0.2, step, 0.2, 0.0);
+    }
+
+    #[test]
+    /// The corners of the triangle are rounded off.
+    fn test_corners_are_smoothed() {
+        let step = 0.01;
+        _test(Waveform::Triangle, 0.25, step, 0.5, 1.0 - 8.0 * step / 6.0);
+        _test(Waveform::Triangle, 0.75, step, 0.5, -1.0 + 8.0 * step / 6.0);
+        _test(Waveform::HalfSine, 0.0, step, 0.5, 2.0 * defs::PI * step / 6.0);
+    }
+
+    #[test]
+    /// A negative phase step (as in through-zero FM) is smoothed
+    /// in the same way as a positive one.
+    fn test_negative_phase_step() {
+        let step = 0.01;
+        _test(Waveform::Pulse, 0.5, -step, 0.5, 0.0);
+        _test(Waveform::Triangle, 0.25, -step, 0.5, 1.0 - 8.0 * step / 6.0);
+    }
+
+    #[test]
+    /// Each band-limited waveform has (close to) zero DC offset,
+    /// except the half-sine which is always positive.
+    fn test_mean_level() {
+        let num_samples = 1000;
+        let step = 1.0 / num_samples as defs::Sample;
+        for waveform in &[Waveform::Sine, Waveform::Saw, Waveform::Pulse, Waveform::Triangle] {
+            let sum: defs::Sample = (0..num_samples)
+                .map(|i| get_sample(*waveform, i as defs::Sample * step, step, 0.5))
+                .sum();
+            let mean = sum / num_samples as defs::Sample;
+            assert!(mean.abs() < 1e-3, "{:?} has mean {}", waveform, mean);
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! Zero-delay-feedback (topology-preserving transform) filter models.
+//!
+//! These discretize analog filter structures with the trapezoidal rule,
+//! solving the feedback loops exactly rather than with a unit delay.
+//! Unlike a biquad, their state stays meaningful when the cutoff changes,
+//! so they remain stable when it is modulated at audio rate.
+
+use defs;
+use engine::filter::FilterType;
+
+/// Lowest and highest values of the FilterQuality parameter.
+const MIN_QUALITY_FACTOR: defs::Sample = 0.5;
+const MAX_QUALITY_FACTOR: defs::Sample = 10.0;
+/// Quality factor above which the state variable filter's damping is taken
+/// down to reach zero at MAX_QUALITY_FACTOR.
+const SELF_OSCILLATION_KNEE: defs::Sample = 5.0;
+
+/// Get the integrator gain for a cutoff frequency, prewarped so that the
+/// cutoff of the digital filter matches that of the analog one.
+fn get_integrator_gain(frequency_hz: defs::Sample, sample_rate: defs::Sample) -> defs::Sample {
+    // Limit frequency_hz to just under half of the sample rate, where tan() diverges.
+    let frequency_hz = frequency_hz.min(0.495 * sample_rate);
+    defs::Sample::tan(0.5 * defs::TWOPI * frequency_hz / sample_rate)
+}
+
+/// A two-pole state variable filter, which provides every filter type.
+/// Follows the trapezoidal SVF described by Andrew Simper (Cytomic).
+#[derive(Clone, Default)]
+pub struct StateVariableFilter {
+    // Integrator states
+    ic1eq: defs::Sample,
+    ic2eq: defs::Sample,
+    // Coefficients
+    a1: defs::Sample,
+    a2: defs::Sample,
+    a3: defs::Sample,
+    k: defs::Sample,
+    // Mix of the input, band pass and low pass outputs that gives the filter type
+    m0: defs::Sample,
+    m1: defs::Sample,
+    m2: defs::Sample,
+    saturate_feedback: bool,
+}
+
+impl StateVariableFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        self.ic1eq = 0.0;
+        self.ic2eq = 0.0;
+    }
+
+    /// Enable saturation of the band pass integrator, which carries the resonance.
+    /// This limits the level of resonant peaks and self-oscillation.
+    pub fn set_feedback_saturation(&mut self, enabled: bool) {
+        self.saturate_feedback = enabled;
+    }
+
+    /// Compute the coefficients for a filter type.
+    /// Up to SELF_OSCILLATION_KNEE, the damping is the inverse of the quality
+    /// factor, as in the analog prototype, so the low pass and high pass gain at
+    /// the cutoff is the quality factor. Above it, the damping falls faster, and
+    /// reaches zero at the highest quality factor, where the filter
+    /// self-oscillates. gain_db is only used by the peaking filter.
+    pub fn set_coefficients(&mut self,
+                            filter_type: FilterType,
+                            frequency_hz: defs::Sample,
+                            quality_factor: defs::Sample,
+                            gain_db: defs::Sample,
+                            sample_rate: defs::Sample)
+    {
+        let g = get_integrator_gain(frequency_hz, sample_rate);
+        let self_oscillation = ((quality_factor - SELF_OSCILLATION_KNEE)
+            / (MAX_QUALITY_FACTOR - SELF_OSCILLATION_KNEE)).clamp(0.0, 1.0);
+        let damping = (1.0 / quality_factor - self_oscillation / MAX_QUALITY_FACTOR).max(0.0);
+        let amplitude = defs::Sample::powf(10.0, gain_db / 40.0);
+
+        // The peaking filter narrows with gain, so that its bandwidth is symmetric
+        // between boosts and cuts.
+        self.k = match filter_type {
+            FilterType::Peaking => damping / amplitude,
+            _ => damping,
+        };
+        self.a1 = 1.0 / (1.0 + g * (g + self.k));
+        self.a2 = g * self.a1;
+        self.a3 = g * self.a2;
+
+        let (m0, m1, m2) = match filter_type {
+            FilterType::Lowpass => (0.0, 0.0, 1.0),
+            FilterType::Highpass => (1.0, -self.k, -1.0),
+            FilterType::Bandpass => (0.0, self.k, 0.0),
+            FilterType::Notch => (1.0, -self.k, 0.0),
+            FilterType::Peaking => (1.0, self.k * (amplitude * amplitude - 1.0), 0.0),
+            FilterType::Allpass => (1.0, -2.0 * self.k, 0.0),
+        };
+        self.m0 = m0;
+        self.m1 = m1;
+        self.m2 = m2;
+    }
+
+    pub fn process(&mut self, input: defs::Sample) -> defs::Sample {
+        let v3 = input - self.ic2eq;
+        let v1 = self.a1 * self.ic1eq + self.a2 * v3;
+        let v2 = self.ic2eq + self.a2 * self.ic1eq + self.a3 * v3;
+        self.ic1eq = 2.0 * v1 - self.ic1eq;
+        if self.saturate_feedback {
+            self.ic1eq = self.ic1eq.tanh();
+        }
+        self.ic2eq = 2.0 * v2 - self.ic2eq;
+
+        self.m0 * input + self.m1 * v1 + self.m2 * v2
+    }
+}
+
+/// Number of one-pole stages in the ladder filter.
+const NUM_LADDER_STAGES: usize = 4;
+
+/// A four-pole (24 dB per octave) low pass ladder filter, after the Moog design.
+/// Resonance is negative feedback from the last stage to the input; at the highest
+/// quality factor the loop gain reaches 4 and the filter self-oscillates.
+#[derive(Clone, Default)]
+pub struct LadderFilter {
+    // Integrator state of each stage
+    states: [defs::Sample; NUM_LADDER_STAGES],
+    // Gain of each one-pole stage, g / (1 + g)
+    stage_gain: defs::Sample,
+    feedback: defs::Sample,
+    saturate_feedback: bool,
+}
+
+impl LadderFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        self.states = [0.0; NUM_LADDER_STAGES];
+    }
+
+    /// Enable saturation of the feedback from the last stage, as in the
+    /// transistor ladder. This limits the level of resonant peaks and
+    /// self-oscillation.
+    pub fn set_feedback_saturation(&mut self, enabled: bool) {
+        self.saturate_feedback = enabled;
+    }
+
+    pub fn set_coefficients(&mut self,
+                            frequency_hz: defs::Sample,
+                            quality_factor: defs::Sample,
+                            sample_rate: defs::Sample)
+    {
+        let g = get_integrator_gain(frequency_hz, sample_rate);
+        self.stage_gain = g / (1.0 + g);
+        let resonance = (quality_factor - MIN_QUALITY_FACTOR)
+            / (MAX_QUALITY_FACTOR - MIN_QUALITY_FACTOR);
+        self.feedback = 4.0 * resonance.clamp(0.0, 1.0);
+    }
+
+    pub fn process(&mut self, input: defs::Sample) -> defs::Sample {
+        let gain = self.stage_gain;
+
+        // The output of the ladder is gain^4 * u + s, where u is the input to the
+        // first stage and s depends only on the stage states. Solve the feedback
+        // loop u = input - feedback * output for u.
+        let s = self.states.iter().fold(0.0, |s, state| gain * s + (1.0 - gain) * state);
+        let gain4 = gain * gain * gain * gain;
+        // The input is boosted to make up for the passband gain lost to feedback.
+        let input = (1.0 + self.feedback) * input;
+        let u = if self.saturate_feedback {
+            // Saturate the output estimated by the linear solution, rather than
+            // solving the nonlinear loop iteratively.
+            let output = (gain4 * input + s) / (1.0 + self.feedback * gain4);
+            input - self.feedback * output.tanh()
+        } else {
+            (input - self.feedback * s) / (1.0 + self.feedback * gain4)
+        };
+
+        let mut stage_input = u;
+        for state in self.states.iter_mut() {
+            let v = gain * (stage_input - *state);
+            let stage_output = v + *state;
+            *state = stage_output + v;
+            stage_input = stage_output;
+        }
+        stage_input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64;
+
+    const SAMPLE_RATE: defs::Sample = 48000.0;
+
+    /// Filter a sine at a frequency, and get the amplitude of the output
+    /// once the filter has settled.
+    fn _amplitude<F>(filter_fn: F, frequency_hz: f64) -> defs::Sample
+        where F: FnMut(defs::Sample) -> defs::Sample
+    {
+        let num_samples = 48000;
+        let output: Vec<defs::Sample> = (0..num_samples)
+            .map(|i| (2.0 * f64::consts::PI * frequency_hz * i as f64
+                      / f64::from(SAMPLE_RATE)).sin() as defs::Sample)
+            .map(filter_fn)
+            .collect();
+        let settled = &output[num_samples / 2..];
+        let power: defs::Sample = settled.iter().map(|sample| sample * sample).sum();
+        (2.0 * power / settled.len() as defs::Sample).sqrt()
+    }
+
+    fn _svf_amplitude(filter_type: FilterType, frequency_hz: f64) -> defs::Sample {
+        _svf_resonant_amplitude(filter_type, 0.707, frequency_hz)
+    }
+
+    fn _svf_resonant_amplitude(filter_type: FilterType,
+                               quality_factor: defs::Sample,
+                               frequency_hz: f64) -> defs::Sample
+    {
+        let mut svf = StateVariableFilter::new();
+        svf.set_coefficients(filter_type, 1000.0, quality_factor, 6.0, SAMPLE_RATE);
+        _amplitude(|sample| svf.process(sample), frequency_hz)
+    }
+
+    fn _ladder_amplitude(quality_factor: defs::Sample, frequency_hz: f64) -> defs::Sample {
+        let mut ladder = LadderFilter::new();
+        ladder.set_coefficients(1000.0, quality_factor, SAMPLE_RATE);
+        _amplitude(|sample| ladder.process(sample), frequency_hz)
+    }
+
+    fn assert_float_eq(actual: defs::Sample, expected: defs::Sample) {
+        assert!((actual - expected).abs() < 0.01,
+                "actual = {}, expected = {}", actual, expected);
+    }
+
+    #[test]
+    fn test_svf_types() {
+        assert_float_eq(_svf_amplitude(FilterType::Lowpass, 50.0), 1.0);
+        assert!(_svf_amplitude(FilterType::Lowpass, 10000.0) < 0.02);
+        assert!(_svf_amplitude(FilterType::Highpass, 50.0) < 0.01);
+        assert_float_eq(_svf_amplitude(FilterType::Highpass, 10000.0), 1.0);
+        assert_float_eq(_svf_amplitude(FilterType::Bandpass, 1000.0), 1.0);
+        assert!(_svf_amplitude(FilterType::Notch, 1000.0) < 0.01);
+        assert_float_eq(_svf_amplitude(FilterType::Notch, 50.0), 1.0);
+        assert_float_eq(_svf_amplitude(FilterType::Peaking, 1000.0), 1.9953);
+        assert_float_eq(_svf_amplitude(FilterType::Peaking, 50.0), 1.0);
+        for frequency_hz in &[50.0, 1000.0, 10000.0] {
+            assert_float_eq(_svf_amplitude(FilterType::Allpass, *frequency_hz), 1.0);
+        }
+    }
+
+    #[test]
+    /// The gain at the cutoff is the quality factor, up to where the damping is
+    /// taken down for self-oscillation.
+    fn test_svf_resonance() {
+        for quality_factor in &[MIN_QUALITY_FACTOR, 0.707, 2.0, SELF_OSCILLATION_KNEE] {
+            let amplitude = _svf_resonant_amplitude(FilterType::Lowpass, *quality_factor, 1000.0);
+            assert!((amplitude / quality_factor - 1.0).abs() < 0.01,
+                    "quality_factor == {}, amplitude == {}", quality_factor, amplitude);
+            let amplitude = _svf_resonant_amplitude(FilterType::Highpass, *quality_factor, 1000.0);
+            assert!((amplitude / quality_factor - 1.0).abs() < 0.01,
+                    "quality_factor == {}, amplitude == {}", quality_factor, amplitude);
+        }
+    }
+
+    #[test]
+    fn test_ladder_response() {
+        // Unity gain in the passband, and a four-pole rolloff:
+        // about -48 dB two octaves above the cutoff.
+        assert_float_eq(_ladder_amplitude(MIN_QUALITY_FACTOR, 50.0), 1.0);
+        let amplitude = _ladder_amplitude(MIN_QUALITY_FACTOR, 4000.0);
+        assert!(amplitude < 0.006 && amplitude > 0.002, "amplitude == {}", amplitude);
+        // The passband gain is kept up as resonance increases.
+        assert_float_eq(_ladder_amplitude(5.0, 50.0), 1.0);
+        assert!(_ladder_amplitude(5.0, 1000.0) > 1.2);
+    }
+
+    #[test]
+    /// At the highest quality factor, both models keep ringing after an impulse.
+    fn test_self_oscillation() {
+        let mut svf = StateVariableFilter::new();
+        svf.set_coefficients(
+            FilterType::Lowpass, 1000.0, MAX_QUALITY_FACTOR, 0.0, SAMPLE_RATE);
+        let mut ladder = LadderFilter::new();
+        ladder.set_coefficients(1000.0, MAX_QUALITY_FACTOR, SAMPLE_RATE);
+
+        svf.process(1.0);
+        ladder.process(1.0);
+        let mut svf_peak: defs::Sample = 0.0;
+        let mut ladder_peak: defs::Sample = 0.0;
+        for i in 0..48000 {
+            let svf_output = svf.process(0.0);
+            let ladder_output = ladder.process(0.0);
+            if i >= 47000 {
+                svf_peak = svf_peak.max(svf_output.abs());
+                ladder_peak = ladder_peak.max(ladder_output.abs());
+            }
+        }
+        assert!(svf_peak > 0.01, "svf_peak == {}", svf_peak);
+        assert!(ladder_peak > 0.01, "ladder_peak == {}", ladder_peak);
+    }
+
+    #[test]
+    /// With feedback saturation, resonance stays at a bounded level however loud
+    /// the input, and the filters still ring after it stops.
+    fn test_feedback_saturation() {
+        let mut svf = StateVariableFilter::new();
+        svf.set_coefficients(
+            FilterType::Lowpass, 1000.0, MAX_QUALITY_FACTOR, 0.0, SAMPLE_RATE);
+        svf.set_feedback_saturation(true);
+        let mut ladder = LadderFilter::new();
+        ladder.set_coefficients(1000.0, MAX_QUALITY_FACTOR, SAMPLE_RATE);
+        ladder.set_feedback_saturation(true);
+
+        // A loud burst at the cutoff frequency, then silence
+        let burst_samples = 4800;
+        let mut svf_peaks = (0.0, 0.0);
+        let mut ladder_peaks = (0.0, 0.0);
+        for i in 0..2 * burst_samples {
+            let input = if i < burst_samples {
+                10.0 * (defs::TWOPI * 1000.0 * i as defs::Sample / SAMPLE_RATE).sin()
+            } else {
+                0.0
+            };
+            let svf_output = svf.process(input).abs();
+            let ladder_output = ladder.process(input).abs();
+            if i < burst_samples {
+                svf_peaks.0 = svf_output.max(svf_peaks.0);
+                ladder_peaks.0 = ladder_output.max(ladder_peaks.0);
+            } else {
+                svf_peaks.1 = svf_output.max(svf_peaks.1);
+                ladder_peaks.1 = ladder_output.max(ladder_peaks.1);
+            }
+        }
+        // Without saturation, the resonant peaks would keep growing through the burst.
+        assert!(svf_peaks.0 < 20.0, "svf_peaks == {:?}", svf_peaks);
+        assert!(ladder_peaks.0 < 20.0, "ladder_peaks == {:?}", ladder_peaks);
+        assert!(svf_peaks.1 > 0.1, "svf_peaks == {:?}", svf_peaks);
+        assert!(ladder_peaks.1 > 0.1, "ladder_peaks == {:?}", ladder_peaks);
+    }
+
+    #[test]
+    /// Sweeping the cutoff at audio rate with high resonance doesn't blow up.
+    fn test_audio_rate_modulation() {
+        let mut svf = StateVariableFilter::new();
+        let mut ladder = LadderFilter::new();
+        for i in 0..48000 {
+            let phase = i as defs::Sample / SAMPLE_RATE;
+            // Cutoff swept between 50 Hz and 20 kHz at 1 kHz
+            let frequency_hz = 50.0 * defs::Sample::powf(
+                400.0, 0.5 + 0.5 * (defs::TWOPI * 1000.0 * phase).sin());
+            let input = (defs::TWOPI * 110.0 * phase).sin();
+            svf.set_coefficients(FilterType::Lowpass, frequency_hz, 9.0, 0.0, SAMPLE_RATE);
+            ladder.set_coefficients(frequency_hz, 9.0, SAMPLE_RATE);
+            let svf_output = svf.process(input);
+            let ladder_output = ladder.process(input);
+            assert!(svf_output.abs() < 100.0, "svf_output == {}", svf_output);
+            assert!(ladder_output.abs() < 100.0, "ladder_output == {}", ladder_output);
+        }
+    }
+}
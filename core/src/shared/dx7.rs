@@ -0,0 +1,769 @@
+//! Import voices from six-operator FM synthesizer (DX7) SysEx voice banks.
+//!
+//! Only the 32-voice bulk dump format is supported. Features that baselisk
+//! doesn't have are approximated or ignored: see Voice::apply.
+
+use defs;
+use shared::parameter::{
+    BaseliskPluginParameters,
+    ParameterId,
+};
+use std::io::Read;
+
+pub const NUM_VOICES: usize = 32;
+pub const NUM_ALGORITHMS: usize = 32;
+const NUM_OPERATORS: usize = 6;
+
+const HEADER_SIZE: usize = 6;
+const VOICE_SIZE: usize = 128;
+const OPERATOR_SIZE: usize = 17;
+/// Header, packed voice data, checksum and end of exclusive
+const BANK_SIZE: usize = HEADER_SIZE + NUM_VOICES * VOICE_SIZE + 2;
+
+/// A list of (modulator, target) operator pairs, and a list of carrier operators.
+pub type OperatorConnections = (&'static [(usize, usize)], &'static [usize]);
+
+/// The modulation connections and carriers of the classic six-operator
+/// algorithms. Operators 1 to 6 are generators A to F.
+pub const ALGORITHMS: [OperatorConnections; NUM_ALGORITHMS] = [
+    (&[(2, 1), (6, 5), (5, 4), (4, 3)], &[1, 3]),
+    (&[(2, 1), (6, 5), (5, 4), (4, 3)], &[1, 3]),
+    (&[(3, 2), (2, 1), (6, 5), (5, 4)], &[1, 4]),
+    (&[(3, 2), (2, 1), (6, 5), (5, 4)], &[1, 4]),
+    (&[(2, 1), (4, 3), (6, 5)], &[1, 3, 5]),
+    (&[(2, 1), (4, 3), (6, 5)], &[1, 3, 5]),
+    (&[(2, 1), (4, 3), (5, 3), (6, 5)], &[1, 3]),
+    (&[(2, 1), (4, 3), (5, 3), (6, 5)], &[1, 3]),
+    (&[(2, 1), (4, 3), (5, 3), (6, 5)], &[1, 3]),
+    (&[(3, 2), (2, 1), (5, 4), (6, 4)], &[1, 4]),
+    (&[(3, 2), (2, 1), (5, 4), (6, 4)], &[1, 4]),
+    (&[(2, 1), (4, 3), (5, 3), (6, 3)], &[1, 3]),
+    (&[(2, 1), (4, 3), (5, 3), (6, 3)], &[1, 3]),
+    (&[(2, 1), (4, 3), (5, 4), (6, 4)], &[1, 3]),
+    (&[(2, 1), (4, 3), (5, 4), (6, 4)], &[1, 3]),
+    (&[(2, 1), (3, 1), (5, 1), (4, 3), (6, 5)], &[1]),
+    (&[(2, 1), (3, 1), (5, 1), (4, 3), (6, 5)], &[1]),
+    (&[(2, 1), (3, 1), (4, 1), (5, 4), (6, 5)], &[1]),
+    (&[(3, 2), (2, 1), (6, 4), (6, 5)], &[1, 4, 5]),
+    (&[(3, 1), (3, 2), (5, 4), (6, 4)], &[1, 2, 4]),
+    (&[(3, 1), (3, 2), (6, 4), (6, 5)], &[1, 2, 4, 5]),
+    (&[(2, 1), (6, 3), (6, 4), (6, 5)], &[1, 3, 4, 5]),
+    (&[(3, 2), (6, 4), (6, 5)], &[1, 2, 4, 5]),
+    (&[(6, 3), (6, 4), (6, 5)], &[1, 2, 3, 4, 5]),
+    (&[(6, 4), (6, 5)], &[1, 2, 3, 4, 5]),
+    (&[(3, 2), (5, 4), (6, 4)], &[1, 2, 4]),
+    (&[(3, 2), (5, 4), (6, 4)], &[1, 2, 4]),
+    (&[(2, 1), (5, 4), (4, 3)], &[1, 3, 6]),
+    (&[(4, 3), (6, 5)], &[1, 2, 3, 5]),
+    (&[(5, 4), (4, 3)], &[1, 2, 3, 6]),
+    (&[(6, 5)], &[1, 2, 3, 4, 5]),
+    (&[], &[1, 2, 3, 4, 5, 6]),
+];
+
+/// The operator with self-feedback in each of the algorithms.
+/// In algorithms 4 and 6 the feedback loop runs through several operators,
+/// but it is imported as self-feedback of operator 6.
+pub const FEEDBACK_OPERATORS: [usize; NUM_ALGORITHMS] = [
+    6, 2, 6, 6, 6, 6, 6, 4, 2, 3, 6, 2, 6, 6, 2, 6,
+    2, 3, 6, 3, 3, 6, 6, 6, 6, 6, 3, 5, 6, 5, 6, 6,
+];
+
+/// The parameters of a generator that an operator is imported to.
+struct GeneratorParameters {
+    frequency_mode: ParameterId,
+    ratio_coarse: ParameterId,
+    ratio_fine: ParameterId,
+    fixed_frequency: ParameterId,
+    detune: ParameterId,
+    mod_index: ParameterId,
+    attack: ParameterId,
+    decay: ParameterId,
+    sustain: ParameterId,
+    release: ParameterId,
+    waveform: ParameterId,
+    feedback: ParameterId,
+    level: ParameterId,
+}
+
+/// Operators 1 to 6 are imported to generators A to F.
+const GENERATOR_PARAMETERS: [GeneratorParameters; NUM_OPERATORS] = [
+    GeneratorParameters {
+        frequency_mode: ParameterId::GeneratorAFrequencyMode,
+        ratio_coarse: ParameterId::GeneratorARatioCoarse,
+        ratio_fine: ParameterId::GeneratorARatioFine,
+        fixed_frequency: ParameterId::GeneratorAFixedFrequency,
+        detune: ParameterId::GeneratorADetune,
+        mod_index: ParameterId::GeneratorAModIndex,
+        attack: ParameterId::GeneratorAAttack,
+        decay: ParameterId::GeneratorADecay,
+        sustain: ParameterId::GeneratorASustain,
+        release: ParameterId::GeneratorARelease,
+        waveform: ParameterId::GeneratorAWaveform,
+        feedback: ParameterId::GeneratorAFeedback,
+        level: ParameterId::GeneratorALevel,
+    },
+    GeneratorParameters {
+        frequency_mode: ParameterId::GeneratorBFrequencyMode,
+        ratio_coarse: ParameterId::GeneratorBRatioCoarse,
+        ratio_fine: ParameterId::GeneratorBRatioFine,
+        fixed_frequency: ParameterId::GeneratorBFixedFrequency,
+        detune: ParameterId::GeneratorBDetune,
+        mod_index: ParameterId::GeneratorBModIndex,
+        attack: ParameterId::GeneratorBAttack,
+        decay: ParameterId::GeneratorBDecay,
+        sustain: ParameterId::GeneratorBSustain,
+        release: ParameterId::GeneratorBRelease,
+        waveform: ParameterId::GeneratorBWaveform,
+        feedback: ParameterId::GeneratorBFeedback,
+        level: ParameterId::GeneratorBLevel,
+    },
+    GeneratorParameters {
+        frequency_mode: ParameterId::GeneratorCFrequencyMode,
+        ratio_coarse: ParameterId::GeneratorCRatioCoarse,
+        ratio_fine: ParameterId::GeneratorCRatioFine,
+        fixed_frequency: ParameterId::GeneratorCFixedFrequency,
+        detune: ParameterId::GeneratorCDetune,
+        mod_index: ParameterId::GeneratorCModIndex,
+        attack: ParameterId::GeneratorCAttack,
+        decay: ParameterId::GeneratorCDecay,
+        sustain: ParameterId::GeneratorCSustain,
+        release: ParameterId::GeneratorCRelease,
+        waveform: ParameterId::GeneratorCWaveform,
+        feedback: ParameterId::GeneratorCFeedback,
+        level: ParameterId::GeneratorCLevel,
+    },
+    GeneratorParameters {
+        frequency_mode: ParameterId::GeneratorDFrequencyMode,
+        ratio_coarse: ParameterId::GeneratorDRatioCoarse,
+        ratio_fine: ParameterId::GeneratorDRatioFine,
+        fixed_frequency: ParameterId::GeneratorDFixedFrequency,
+        detune: ParameterId::GeneratorDDetune,
+        mod_index: ParameterId::GeneratorDModIndex,
+        attack: ParameterId::GeneratorDAttack,
+        decay: ParameterId::GeneratorDDecay,
+        sustain: ParameterId::GeneratorDSustain,
+        release: ParameterId::GeneratorDRelease,
+        waveform: ParameterId::GeneratorDWaveform,
+        feedback: ParameterId::GeneratorDFeedback,
+        level: ParameterId::GeneratorDLevel,
+    },
+    GeneratorParameters {
+        frequency_mode: ParameterId::GeneratorEFrequencyMode,
+        ratio_coarse: ParameterId::GeneratorERatioCoarse,
+        ratio_fine: ParameterId::GeneratorERatioFine,
+        fixed_frequency: ParameterId::GeneratorEFixedFrequency,
+        detune: ParameterId::GeneratorEDetune,
+        mod_index: ParameterId::GeneratorEModIndex,
+        attack: ParameterId::GeneratorEAttack,
+        decay: ParameterId::GeneratorEDecay,
+        sustain: ParameterId::GeneratorESustain,
+        release: ParameterId::GeneratorERelease,
+        waveform: ParameterId::GeneratorEWaveform,
+        feedback: ParameterId::GeneratorEFeedback,
+        level: ParameterId::GeneratorELevel,
+    },
+    GeneratorParameters {
+        frequency_mode: ParameterId::GeneratorFFrequencyMode,
+        ratio_coarse: ParameterId::GeneratorFRatioCoarse,
+        ratio_fine: ParameterId::GeneratorFRatioFine,
+        fixed_frequency: ParameterId::GeneratorFFixedFrequency,
+        detune: ParameterId::GeneratorFDetune,
+        mod_index: ParameterId::GeneratorFModIndex,
+        attack: ParameterId::GeneratorFAttack,
+        decay: ParameterId::GeneratorFDecay,
+        sustain: ParameterId::GeneratorFSustain,
+        release: ParameterId::GeneratorFRelease,
+        waveform: ParameterId::GeneratorFWaveform,
+        feedback: ParameterId::GeneratorFFeedback,
+        level: ParameterId::GeneratorFLevel,
+    },
+];
+
+/// A modulator at full output level deviates the phase of its target
+/// by about this many radians.
+const FULL_LEVEL_PHASE_DEVIATION: defs::Sample = 2.0 * defs::PI;
+
+/// Operators in fixed frequency mode are compared with middle C when
+/// working out how strongly they modulate other operators.
+const MIDDLE_C_FREQUENCY: defs::Sample = 261.63;
+
+/// Convert an output level or envelope level (0 to 99) to a gain.
+/// Each step is about 0.75 dB, and level 0 is silent.
+fn level_to_gain(level: u8) -> defs::Sample {
+    if level == 0 {
+        return 0.0
+    }
+    defs::Sample::powf(2.0, (defs::Sample::from(level) - 99.0) / 8.0)
+}
+
+/// Approximate the time in seconds that an envelope stage at a rate
+/// (0 to 99) takes to move between two envelope levels.
+/// The time halves for roughly every 6 steps of rate.
+fn stage_time(rate: u8, from_level: u8, to_level: u8) -> defs::Sample {
+    let distance = (defs::Sample::from(from_level) - defs::Sample::from(to_level)).abs() / 99.0;
+    let full_range_time = 40.0 * defs::Sample::powf(2.0, -defs::Sample::from(rate) * 41.0 / 256.0);
+    full_range_time * distance
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Operator {
+    /// Envelope rates for the four stages, 0 to 99
+    eg_rates: [u8; 4],
+    /// Envelope levels at the end of the four stages, 0 to 99
+    eg_levels: [u8; 4],
+    /// Detune, 0 to 14 with 7 at the centre
+    detune: u8,
+    /// Output level, 0 to 99
+    output_level: u8,
+    fixed_frequency: bool,
+    frequency_coarse: u8,
+    frequency_fine: u8,
+    /// Keyboard level scaling depths below and above the break point, 0 to 99
+    level_scaling_depths: [u8; 2],
+    /// Keyboard rate scaling, 0 to 7
+    rate_scaling: u8,
+    /// Key velocity sensitivity, 0 to 7
+    velocity_sensitivity: u8,
+    /// Sensitivity to amplitude modulation from the LFO, 0 to 3
+    amp_mod_sensitivity: u8,
+}
+
+impl Operator {
+    /// Parse the 17 bytes of packed data for an operator.
+    fn from_packed(data: &[u8]) -> Self {
+        let mut eg_rates = [0; 4];
+        let mut eg_levels = [0; 4];
+        for stage in 0..4 {
+            eg_rates[stage] = u8::min(data[stage], 99);
+            eg_levels[stage] = u8::min(data[4 + stage], 99);
+        }
+        Self {
+            eg_rates,
+            eg_levels,
+            detune: u8::min((data[12] >> 3) & 0x0f, 14),
+            output_level: u8::min(data[14], 99),
+            fixed_frequency: data[15] & 0x01 != 0,
+            frequency_coarse: (data[15] >> 1) & 0x1f,
+            frequency_fine: u8::min(data[16], 99),
+            level_scaling_depths: [u8::min(data[9], 99), u8::min(data[10], 99)],
+            rate_scaling: data[12] & 0x07,
+            velocity_sensitivity: (data[13] >> 2) & 0x07,
+            amp_mod_sensitivity: data[13] & 0x03,
+        }
+    }
+
+    /// The frequency of the operator as a multiple of the note frequency,
+    /// when in ratio mode.
+    fn get_frequency_ratio(&self) -> defs::Sample {
+        let coarse = match self.frequency_coarse {
+            0 => 0.5,
+            coarse => defs::Sample::from(coarse),
+        };
+        coarse * (1.0 + defs::Sample::from(self.frequency_fine) / 100.0)
+    }
+
+    /// The frequency of the operator in Hz, when in fixed frequency mode.
+    fn get_fixed_frequency(&self) -> defs::Sample {
+        let exponent = defs::Sample::from(self.frequency_coarse % 4)
+            + defs::Sample::from(self.frequency_fine) / 100.0;
+        defs::Sample::powf(10.0, exponent)
+    }
+
+    /// The frequency of the operator relative to the note frequency.
+    fn get_relative_frequency(&self) -> defs::Sample {
+        if self.fixed_frequency {
+            self.get_fixed_frequency() / MIDDLE_C_FREQUENCY
+        } else {
+            self.get_frequency_ratio()
+        }
+    }
+}
+
+/// A voice parsed from a voice bank.
+#[derive(Clone, Debug)]
+pub struct Voice {
+    name: String,
+    /// Operators 1 to 6
+    operators: [Operator; NUM_OPERATORS],
+    /// Algorithm number, 1 to 32
+    algorithm: usize,
+    /// Feedback amount, 0 to 7
+    feedback: u8,
+    /// Pitch envelope levels, 0 to 99 with 50 at the note pitch
+    pitch_eg_levels: [u8; 4],
+    /// LFO pitch and amplitude modulation depths, 0 to 99
+    lfo_pitch_mod_depth: u8,
+    lfo_amp_mod_depth: u8,
+    /// Sensitivity to pitch modulation from the LFO, 0 to 7
+    pitch_mod_sensitivity: u8,
+    /// Transpose in semitones, 0 to 48 with 24 at the centre
+    transpose: u8,
+}
+
+impl Voice {
+    /// Parse the 128 bytes of packed data for a voice.
+    fn from_packed(data: &[u8]) -> Self {
+        let mut operators = [Operator::default(); NUM_OPERATORS];
+        for (i, operator) in operators.iter_mut().enumerate() {
+            // Operators are stored from operator 6 down to operator 1
+            let offset = (NUM_OPERATORS - 1 - i) * OPERATOR_SIZE;
+            *operator = Operator::from_packed(&data[offset..offset + OPERATOR_SIZE]);
+        }
+        let name: String = data[118..128].iter()
+            .map(|byte| match byte {
+                32..=126 => *byte as char,
+                _ => ' ',
+            })
+            .collect();
+        Self {
+            name: String::from(name.trim_end()),
+            operators,
+            algorithm: usize::from(data[110] & 0x1f) + 1,
+            feedback: data[111] & 0x07,
+            pitch_eg_levels: [data[106], data[107], data[108], data[109]],
+            lfo_pitch_mod_depth: data[114],
+            lfo_amp_mod_depth: data[115],
+            pitch_mod_sensitivity: (data[116] >> 4) & 0x07,
+            transpose: data[117],
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Set the parameters to play this voice.
+    ///
+    /// Each operator is imported to a generator with a sine waveform, and the
+    /// generator routing is set to the voice's algorithm. Features are mapped
+    /// approximately:
+    /// - Four-stage operator envelopes become ADSR envelopes, with the highest
+    ///   of the first three levels as the peak and the third level as sustain.
+    /// - Modulation depth is set through the mod index of each generator,
+    ///   from the frequencies of the operators that modulate it.
+    /// - Detune is imported as one cent per step.
+    ///
+    /// Keyboard scaling, velocity sensitivity, the pitch envelope, the LFO
+    /// and transpose are not imported. The amp envelope is set to sustain
+    /// at full level, so that the generator envelopes shape the sound.
+    ///
+    /// Returns a description of each feature that the voice uses but that
+    /// wasn't imported, so that the difference in sound can be explained.
+    pub fn apply(&self, parameters: &BaseliskPluginParameters) -> Vec<String> {
+        let set = |param: ParameterId, value: String| {
+            parameters.update_real_value_from_string(param, value).unwrap()
+        };
+
+        set(ParameterId::GeneratorRouting, format!("dx7_{}", self.algorithm));
+        let (connections, _) = ALGORITHMS[self.algorithm - 1];
+        let feedback_operator = FEEDBACK_OPERATORS[self.algorithm - 1];
+
+        let mut longest_release: defs::Sample = 0.0;
+        for (i, (operator, ids)) in self.operators.iter()
+            .zip(GENERATOR_PARAMETERS.iter())
+            .enumerate()
+        {
+            let number = i + 1;
+            set(ids.waveform, String::from("sine"));
+
+            if operator.fixed_frequency {
+                set(ids.frequency_mode, String::from("fixed"));
+                set(ids.fixed_frequency, operator.get_fixed_frequency().to_string());
+            } else {
+                let ratio = operator.get_frequency_ratio();
+                set(ids.frequency_mode, String::from("ratio"));
+                set(ids.ratio_coarse, ratio.floor().to_string());
+                set(ids.ratio_fine, ratio.fract().to_string());
+            }
+            set(ids.detune, (defs::Sample::from(operator.detune) - 7.0).to_string());
+
+            // Baselisk modulates frequency in proportion to the frequency of the
+            // target, so scale the mod index by the average frequency of the
+            // modulators to get a similar phase deviation.
+            let (modulator_frequency_sum, num_modulators) = connections.iter()
+                .filter(|(_, target)| *target == number)
+                .fold((0.0, 0), |(sum, count), (source, _)| {
+                    (sum + self.operators[source - 1].get_relative_frequency(), count + 1)
+                });
+            let mod_index = if num_modulators > 0 {
+                FULL_LEVEL_PHASE_DEVIATION * modulator_frequency_sum
+                    / (num_modulators as defs::Sample * operator.get_relative_frequency())
+            } else {
+                1.0
+            };
+            set(ids.mod_index, mod_index.to_string());
+
+            let [rate_1, rate_2, rate_3, rate_4] = operator.eg_rates;
+            let [level_1, level_2, level_3, level_4] = operator.eg_levels;
+            let peak = u8::max(level_1, u8::max(level_2, level_3));
+            let sustain = if peak > 0 {
+                level_to_gain(level_3) / level_to_gain(peak)
+            } else {
+                0.0
+            };
+            let release = stage_time(rate_4, level_3, level_4);
+            longest_release = defs::Sample::max(longest_release, release);
+            set(ids.attack, stage_time(rate_1, level_4, level_1).to_string());
+            set(ids.decay, (stage_time(rate_2, level_1, level_2)
+                            + stage_time(rate_3, level_2, level_3)).to_string());
+            set(ids.sustain, sustain.to_string());
+            set(ids.release, release.to_string());
+            set(ids.level, (level_to_gain(operator.output_level) * level_to_gain(peak)).to_string());
+
+            let feedback = if number == feedback_operator && self.feedback > 0 {
+                defs::Sample::powf(2.0, defs::Sample::from(self.feedback) - 8.0)
+            } else {
+                0.0
+            };
+            set(ids.feedback, feedback.to_string());
+        }
+
+        set(ParameterId::AmpEnvelope, String::from("adsr"));
+        set(ParameterId::AdsrAttack, String::from("0"));
+        set(ParameterId::AdsrSustain, String::from("1"));
+        set(ParameterId::AdsrRelease, longest_release.to_string());
+
+        self.get_ignored_features()
+    }
+
+    /// Describe the features used by the voice that apply doesn't import.
+    fn get_ignored_features(&self) -> Vec<String> {
+        let list_operators = |uses_feature: &dyn Fn(&Operator) -> bool| -> Option<String> {
+            let numbers: Vec<String> = self.operators.iter()
+                .enumerate()
+                .filter(|(_, operator)| uses_feature(operator))
+                .map(|(i, _)| (i + 1).to_string())
+                .collect();
+            if numbers.is_empty() {
+                None
+            } else {
+                Some(numbers.join(", "))
+            }
+        };
+
+        let mut features = Vec::new();
+        if let Some(numbers) = list_operators(&|op| op.level_scaling_depths != [0, 0]) {
+            features.push(format!("keyboard level scaling of operators {}", numbers));
+        }
+        if let Some(numbers) = list_operators(&|op| op.rate_scaling > 0) {
+            features.push(format!("keyboard rate scaling of operators {}", numbers));
+        }
+        if let Some(numbers) = list_operators(&|op| op.velocity_sensitivity > 0) {
+            features.push(format!("velocity sensitivity of operators {}", numbers));
+        }
+        if self.pitch_eg_levels.iter().any(|level| *level != 50) {
+            features.push(String::from("pitch envelope"));
+        }
+        if self.lfo_pitch_mod_depth > 0 && self.pitch_mod_sensitivity > 0 {
+            features.push(String::from("LFO pitch modulation"));
+        }
+        if let Some(numbers) = list_operators(&|op| op.amp_mod_sensitivity > 0) {
+            if self.lfo_amp_mod_depth > 0 {
+                features.push(format!("LFO amplitude modulation of operators {}", numbers));
+            }
+        }
+        if self.transpose != 24 {
+            features.push(format!("transpose of {} semitones", i32::from(self.transpose) - 24));
+        }
+        features
+    }
+}
+
+/// Parse the voices from a 32-voice bulk dump.
+pub fn parse_bank(data: &[u8]) -> Result<Vec<Voice>, String> {
+    if data.len() < BANK_SIZE {
+        return Err(format!("Expected a {} byte voice bank, but got {} bytes",
+                           BANK_SIZE, data.len()))
+    }
+    // The low nibble of the third byte is the MIDI channel
+    if data[0] != 0xf0 || data[1] != 0x43 || data[2] & 0xf0 != 0
+        || data[3] != 0x09 || data[4] != 0x20 || data[5] != 0x00
+    {
+        return Err(String::from("Not a 32 voice bank"))
+    }
+    if data[BANK_SIZE - 1] != 0xf7 {
+        return Err(String::from("Missing end of SysEx message"))
+    }
+
+    let voice_data = &data[HEADER_SIZE..HEADER_SIZE + NUM_VOICES * VOICE_SIZE];
+    let sum = voice_data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    let checksum = sum.wrapping_neg() & 0x7f;
+    if checksum != data[BANK_SIZE - 2] {
+        return Err(format!("Checksum mismatch: expected {}, got {}",
+                           checksum, data[BANK_SIZE - 2]))
+    }
+
+    Ok(voice_data.chunks(VOICE_SIZE).map(Voice::from_packed).collect())
+}
+
+/// Read the voices from a 32-voice bulk dump, such as a .syx file.
+pub fn read_bank<R: Read>(mut reader: R) -> Result<Vec<Voice>, String> {
+    let mut data = Vec::new();
+    if let Err(reason) = reader.read_to_end(&mut data) {
+        return Err(format!("Can't read voice bank: {}", reason))
+    }
+    parse_bank(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use defs;
+
+    /// Pack an operator with the given envelope and frequency settings.
+    fn _pack_operator(rates: [u8; 4],
+                      levels: [u8; 4],
+                      detune: u8,
+                      output_level: u8,
+                      fixed_frequency: bool,
+                      coarse: u8,
+                      fine: u8) -> Vec<u8>
+    {
+        let mut data = Vec::with_capacity(OPERATOR_SIZE);
+        data.extend_from_slice(&rates);
+        data.extend_from_slice(&levels);
+        // Keyboard level scaling break point, depths and curves
+        data.extend_from_slice(&[39, 0, 0, 0]);
+        // Rate scaling and detune
+        data.push(detune << 3);
+        // Amplitude modulation and velocity sensitivity
+        data.push(0);
+        data.push(output_level);
+        data.push((coarse << 1) | fixed_frequency as u8);
+        data.push(fine);
+        data
+    }
+
+    /// Pack a voice from operators 1 to 6.
+    fn _pack_voice(name: &str, operators: &[Vec<u8>], algorithm: usize, feedback: u8) -> Vec<u8> {
+        let mut data = Vec::with_capacity(VOICE_SIZE);
+        for operator in operators.iter().rev() {
+            data.extend_from_slice(operator);
+        }
+        // Pitch envelope rates and levels
+        data.extend_from_slice(&[99, 99, 99, 99, 50, 50, 50, 50]);
+        data.push(algorithm as u8 - 1);
+        // Oscillator key sync and feedback
+        data.push(0x08 | feedback);
+        // LFO, pitch modulation sensitivity and transpose
+        data.extend_from_slice(&[35, 0, 0, 0, 0x31, 24]);
+        let mut name_bytes = name.as_bytes().to_vec();
+        name_bytes.resize(10, b' ');
+        data.extend_from_slice(&name_bytes);
+        assert_eq!(data.len(), VOICE_SIZE);
+        data
+    }
+
+    /// The initial voice: only operator 1 is audible, at the note frequency.
+    fn _init_voice() -> Vec<u8> {
+        let mut operators = vec![_pack_operator([99, 99, 99, 99], [99, 99, 99, 0], 7, 99, false, 1, 0)];
+        for _ in 1..NUM_OPERATORS {
+            operators.push(_pack_operator([99, 99, 99, 99], [99, 99, 99, 0], 7, 0, false, 1, 0));
+        }
+        _pack_voice("INIT VOICE", &operators, 1, 0)
+    }
+
+    /// An electric piano style voice using algorithm 5, with a fixed frequency
+    /// operator, a detuned operator and feedback.
+    fn _piano_voice() -> Vec<u8> {
+        let operators = vec![
+            _pack_operator([96, 25, 25, 67], [99, 75, 0, 0], 10, 99, false, 1, 0),
+            _pack_operator([95, 50, 35, 78], [99, 75, 0, 0], 7, 58, false, 14, 0),
+            _pack_operator([95, 20, 20, 50], [99, 95, 0, 0], 4, 99, false, 1, 0),
+            _pack_operator([95, 29, 20, 50], [99, 95, 0, 0], 7, 89, false, 1, 0),
+            _pack_operator([95, 20, 20, 50], [99, 95, 0, 0], 7, 99, false, 1, 0),
+            _pack_operator([95, 29, 20, 50], [99, 95, 0, 0], 14, 79, true, 2, 50),
+        ];
+        _pack_voice("E.PIANO", &operators, 5, 6)
+    }
+
+    /// Build a 32-voice bulk dump: the piano voice first, then initial voices.
+    fn _build_bank() -> Vec<u8> {
+        let mut voice_data = _piano_voice();
+        for _ in 1..NUM_VOICES {
+            voice_data.extend(_init_voice());
+        }
+        let sum = voice_data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+
+        let mut bank = vec![0xf0, 0x43, 0x00, 0x09, 0x20, 0x00];
+        bank.extend(voice_data);
+        bank.push(sum.wrapping_neg() & 0x7f);
+        bank.push(0xf7);
+        bank
+    }
+
+    fn _assert_close(actual: defs::Sample, expected: defs::Sample) {
+        assert!((actual - expected).abs() < 1e-3,
+                "actual == {}, expected == {}", actual, expected);
+    }
+
+    /// Check that the generators play the operators at their exact frequencies.
+    fn _assert_frequencies_imported(voice: &Voice, params: &BaseliskPluginParameters) {
+        for (operator, ids) in voice.operators.iter().zip(GENERATOR_PARAMETERS.iter()) {
+            if operator.fixed_frequency {
+                _assert_close(params.get_real_value(ids.fixed_frequency) / operator.get_fixed_frequency(), 1.0);
+            } else {
+                let ratio = params.get_real_value(ids.ratio_coarse) + params.get_real_value(ids.ratio_fine);
+                _assert_close(ratio, operator.get_frequency_ratio());
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_bank() {
+        let voices = read_bank(&_build_bank()[..]).unwrap();
+        assert_eq!(voices.len(), NUM_VOICES);
+        assert_eq!(voices[0].get_name(), "E.PIANO");
+        assert_eq!(voices[0].algorithm, 5);
+        assert_eq!(voices[0].feedback, 6);
+        assert_eq!(voices[0].operators[1].frequency_coarse, 14);
+        assert_eq!(voices[0].operators[1].output_level, 58);
+        assert_eq!(voices[0].operators[5].eg_rates, [95, 29, 20, 50]);
+        assert!(voices[0].operators[5].fixed_frequency);
+        for voice in voices[1..].iter() {
+            assert_eq!(voice.get_name(), "INIT VOICE");
+            assert_eq!(voice.algorithm, 1);
+        }
+    }
+
+    #[test]
+    fn test_invalid_bank() {
+        let bank = _build_bank();
+        assert!(parse_bank(&bank[..BANK_SIZE - 1]).is_err());
+
+        // Wrong format number: a single voice dump
+        let mut single_voice = bank.clone();
+        single_voice[3] = 0x00;
+        assert!(parse_bank(&single_voice).is_err());
+
+        let mut corrupted = bank.clone();
+        corrupted[100] ^= 0x01;
+        assert!(parse_bank(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_apply_voice() {
+        let params = BaseliskPluginParameters::default();
+        let voices = parse_bank(&_build_bank()).unwrap();
+        assert!(voices[0].apply(&params).is_empty());
+
+        // Algorithm 5, following the generator routing names
+        assert_eq!(params.get_parameter_text(ParameterId::GeneratorRouting), "dx7_5");
+
+        // Operator 2 runs at 14 times the note frequency
+        _assert_close(params.get_real_value(ParameterId::GeneratorBFrequencyMode), 1.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorBRatioCoarse), 14.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorBRatioFine), 0.0);
+
+        // Operator 6 runs at a fixed 10^2.5 Hz, detuned upwards
+        _assert_close(params.get_real_value(ParameterId::GeneratorFFrequencyMode), 2.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorFFixedFrequency), 316.228);
+        _assert_close(params.get_real_value(ParameterId::GeneratorFDetune), 7.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorCDetune), -3.0);
+
+        // Full output level is full generator level, and lower output levels are quieter
+        _assert_close(params.get_real_value(ParameterId::GeneratorALevel), 1.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorDLevel), 0.42045);
+        _assert_close(params.get_real_value(ParameterId::GeneratorASustain), 0.0);
+
+        // Feedback goes to operator 6 in algorithm 5
+        _assert_close(params.get_real_value(ParameterId::GeneratorFFeedback), 0.25);
+        _assert_close(params.get_real_value(ParameterId::GeneratorAFeedback), 0.0);
+
+        // Operator 1 is modulated by operator 2 at 14 times its frequency,
+        // which needs more than the maximum mod index
+        _assert_close(params.get_real_value(ParameterId::GeneratorAModIndex), 8.0);
+        // Operator 3 is modulated by operator 4 at the same frequency
+        _assert_close(params.get_real_value(ParameterId::GeneratorCModIndex), 2.0 * defs::PI);
+    }
+
+    #[test]
+    fn test_apply_init_voice() {
+        let params = BaseliskPluginParameters::default();
+        let voices = parse_bank(&_build_bank()).unwrap();
+        voices[1].apply(&params);
+
+        assert_eq!(params.get_parameter_text(ParameterId::GeneratorRouting), "dx7_1");
+        _assert_close(params.get_real_value(ParameterId::GeneratorALevel), 1.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorASustain), 1.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorBLevel), 0.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorARatioCoarse), 1.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorADetune), 0.0);
+        _assert_close(params.get_real_value(ParameterId::AdsrSustain), 1.0);
+    }
+
+    #[test]
+    fn test_apply_high_ratio() {
+        // The highest ratio: coarse 31, fine 99
+        let mut operators = vec![_pack_operator([99, 99, 99, 99], [99, 99, 99, 0], 7, 99, false, 31, 99)];
+        for _ in 1..NUM_OPERATORS {
+            operators.push(_pack_operator([99, 99, 99, 99], [99, 99, 99, 0], 7, 0, false, 1, 0));
+        }
+        let voice = Voice::from_packed(&_pack_voice("HIGH", &operators, 1, 0));
+        let params = BaseliskPluginParameters::default();
+        voice.apply(&params);
+
+        _assert_close(params.get_real_value(ParameterId::GeneratorARatioCoarse), 61.0);
+        _assert_close(params.get_real_value(ParameterId::GeneratorARatioFine), 0.69);
+        _assert_frequencies_imported(&voice, &params);
+    }
+
+    #[test]
+    fn test_ignored_features() {
+        let mut data = _init_voice();
+        // Operator 1 is stored last, operator 2 before it
+        let operator_1 = 5 * OPERATOR_SIZE;
+        let operator_2 = 4 * OPERATOR_SIZE;
+        data[operator_1 + 10] = 20;
+        data[operator_2 + 9] = 30;
+        data[operator_2 + 13] = 3 << 2;
+        data[107] = 60;
+        data[117] = 36;
+        let params = BaseliskPluginParameters::default();
+        let features = Voice::from_packed(&data).apply(&params);
+        assert_eq!(features, vec![
+            String::from("keyboard level scaling of operators 1, 2"),
+            String::from("velocity sensitivity of operators 2"),
+            String::from("pitch envelope"),
+            String::from("transpose of 12 semitones"),
+        ]);
+
+        // LFO modulation is only used with both depth and sensitivity
+        let mut data = _init_voice();
+        data[114] = 40;
+        data[115] = 40;
+        let features = Voice::from_packed(&data).apply(&params);
+        assert_eq!(features, vec![String::from("LFO pitch modulation")]);
+        data[operator_1 + 13] = 2;
+        let features = Voice::from_packed(&data).apply(&params);
+        assert_eq!(features, vec![
+            String::from("LFO pitch modulation"),
+            String::from("LFO amplitude modulation of operators 1"),
+        ]);
+    }
+
+    /// Import every voice of the first factory voice bank (ROM1A).
+    /// The bank isn't distributed with baselisk, so run this with the path
+    /// to a copy in BASELISK_DX7_ROM1A: cargo test -- --ignored
+    #[test]
+    #[ignore]
+    fn test_factory_bank() {
+        let path = std::env::var("BASELISK_DX7_ROM1A")
+            .expect("BASELISK_DX7_ROM1A should be the path to the ROM1A bank");
+        let file = std::fs::File::open(path).unwrap();
+        let voices = read_bank(std::io::BufReader::new(file)).unwrap();
+
+        assert_eq!(voices[0].get_name(), "BRASS   1");
+        assert_eq!(voices[0].algorithm, 22);
+        assert_eq!(voices[10].get_name(), "E.PIANO 1");
+        assert_eq!(voices[10].algorithm, 5);
+
+        for voice in voices.iter() {
+            let params = BaseliskPluginParameters::default();
+            voice.apply(&params);
+            assert_eq!(params.get_parameter_text(ParameterId::GeneratorRouting),
+                       format!("dx7_{}", voice.algorithm));
+            _assert_frequencies_imported(voice, &params);
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use super::super::parameter;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum EngineEvent {
-    NoteChange { note: Option<u8> },
+    // The velocity is that of the selected note, or 0 if there is no note.
+    NoteChange { note: Option<u8>, velocity: u8 },
     PitchBend { wheel_value: u16 },
     ModulateParameter { param_id: parameter::ParameterId, value: f32 },
 }
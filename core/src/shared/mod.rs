@@ -1,7 +1,9 @@
 
+pub mod dx7;
 pub mod event;
 pub mod modmatrix;
 pub mod parameter;
+pub mod wavetable;
 
 use std::sync::Arc;
 
@@ -10,6 +12,7 @@ pub struct SharedState {
     // pass the parameters to the host thread.
     pub parameters: Arc<parameter::BaseliskPluginParameters>,
     pub modmatrix: modmatrix::ModulationMatrix,
+    pub wavetables: wavetable::WavetableBank,
 }
 
 impl SharedState {
@@ -19,6 +22,8 @@ impl SharedState {
         Self {
             parameters,
             modmatrix: modmatrix::ModulationMatrix::new(parameters_clone),
+            // One wavetable for each generator
+            wavetables: wavetable::WavetableBank::new(6),
         }
     }
 }
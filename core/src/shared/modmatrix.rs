@@ -9,6 +9,7 @@ use shared::{
         ParameterId,
     },
 };
+use std::io::{BufRead, Write};
 use std::sync::{
     Arc,
     atomic::{AtomicI32, Ordering},
@@ -46,6 +47,20 @@ impl ModulationMatrix
         self.param_id_to_learn.store(param as i32, Ordering::Relaxed);
     }
 
+    /// Cancel a pending MIDI learn, if there is one.
+    /// Returns the parameter that was waiting to be learned.
+    pub fn cancel_learn(&self) -> Option<ParameterId>
+    {
+        let param_id = self.param_id_to_learn.swap(-1, Ordering::Relaxed);
+        if param_id >= 0 {
+            let param = ParameterId::from(param_id);
+            println!("Cancelled MIDI learn for parameter {}",
+                     self.parameters.get_parameter_name(param));
+            return Some(param)
+        }
+        None
+    }
+
     pub fn bind_parameter(&self, number: u8, param: ParameterId)
     {
         println!("Binding CC {} to parameter {}",
@@ -54,6 +69,89 @@ impl ModulationMatrix
         self.controllers[number as usize].bind(param as i32);
     }
 
+    /// Remove the binding for a single MIDI CC number.
+    /// Returns the parameter that was bound to it, if any.
+    pub fn unbind_controller(&self, number: u8) -> Option<ParameterId>
+    {
+        let param = match self.controllers.get(number as usize) {
+            Some(controller) => controller.unbind(),
+            None => None,
+        };
+        if let Some(param) = param {
+            println!("Unbinding CC {} from parameter {}",
+                     number,
+                     self.parameters.get_parameter_name(param));
+        }
+        param
+    }
+
+    /// Remove all MIDI CC bindings.
+    pub fn clear_bindings(&self)
+    {
+        for controller in self.controllers.iter() {
+            controller.unbind();
+        }
+    }
+
+    /// Get a snapshot of the current MIDI CC bindings as
+    /// (controller number, parameter) pairs, ordered by controller number.
+    pub fn get_bindings(&self) -> Vec<(u8, ParameterId)>
+    {
+        self.controllers.iter()
+            .enumerate()
+            .filter_map(|(number, controller)| {
+                controller.get_param_id().map(|param| (number as u8, param))
+            })
+            .collect()
+    }
+
+    /// Write the current MIDI CC bindings as a controller map.
+    /// Each line holds a controller number followed by a parameter name,
+    /// e.g. "74 filter frequency".
+    pub fn save_controller_map<W: Write>(&self, writer: &mut W) -> std::io::Result<()>
+    {
+        for (number, param) in self.get_bindings() {
+            writeln!(writer, "{} {}", number, self.parameters.get_parameter_name(param))?;
+        }
+        Ok(())
+    }
+
+    /// Replace the current MIDI CC bindings with those read from a controller map.
+    /// Blank lines and lines starting with '#' are ignored.
+    /// If any line can't be parsed, the existing bindings are left unchanged.
+    pub fn load_controller_map<R: BufRead>(&self, reader: R) -> Result<(), String>
+    {
+        let mut bindings = Vec::new();
+        for (line_num, line) in reader.lines().enumerate() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => return Err(String::from("Error reading controller map")),
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue
+            }
+            let mut tokens = line.splitn(2, ' ');
+            let number = match tokens.next().unwrap().parse::<u8>() {
+                Ok(number) if (number as usize) < self.controllers.len() => number,
+                _ => return Err(format!("Line {}: invalid controller number", line_num + 1)),
+            };
+            let param = match tokens.next()
+                .and_then(|name| self.parameters.get_parameter_id_by_name(name.trim()))
+            {
+                Some(param) => param,
+                None => return Err(format!("Line {}: unknown parameter name", line_num + 1)),
+            };
+            bindings.push((number, param));
+        }
+
+        self.clear_bindings();
+        for (number, param) in bindings {
+            self.bind_parameter(number, param);
+        }
+        Ok(())
+    }
+
     /// Process a MidiEvent.
     /// Maybe emit an EngineEvent::ModulateParameter.
     pub fn process_event(&self, event: &MidiEvent) -> Option<EngineEvent> {
@@ -91,6 +189,25 @@ impl SingleController
         self.param_id.store(param_id, Ordering::Relaxed);
     }
 
+    /// Remove any binding from this controller.
+    /// Returns the parameter that was previously bound.
+    pub fn unbind(&self) -> Option<ParameterId> {
+        let param_id = self.param_id.swap(-1, Ordering::Relaxed);
+        if param_id >= 0 {
+            return Some(ParameterId::from(param_id))
+        }
+        None
+    }
+
+    /// Get the parameter bound to this controller, if any.
+    pub fn get_param_id(&self) -> Option<ParameterId> {
+        let param_id = self.param_id.load(Ordering::Relaxed);
+        if param_id >= 0 {
+            return Some(ParameterId::from(param_id))
+        }
+        None
+    }
+
     /// Process an incoming MIDI CC value.
     /// Maybe emit an EngineEvent::ModulateParameter.
     pub fn process(&self, cc_value: u8) -> Option<EngineEvent> {
@@ -106,3 +223,115 @@ impl SingleController
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_modmatrix() -> ModulationMatrix {
+        ModulationMatrix::new(Arc::new(BaseliskPluginParameters::default()))
+    }
+
+    #[test]
+    fn test_get_bindings_ordered_by_controller() {
+        let modmatrix = new_modmatrix();
+        assert!(modmatrix.get_bindings().is_empty());
+
+        modmatrix.bind_parameter(74, ParameterId::FilterFrequency);
+        modmatrix.bind_parameter(7, ParameterId::DelayFeedback);
+
+        let bindings = modmatrix.get_bindings();
+        assert_eq!(bindings.len(), 2);
+        assert_eq!(bindings[0].0, 7);
+        assert_eq!(bindings[0].1 as i32, ParameterId::DelayFeedback as i32);
+        assert_eq!(bindings[1].0, 74);
+        assert_eq!(bindings[1].1 as i32, ParameterId::FilterFrequency as i32);
+    }
+
+    #[test]
+    fn test_unbind_controller() {
+        let modmatrix = new_modmatrix();
+        modmatrix.bind_parameter(74, ParameterId::FilterFrequency);
+
+        assert!(modmatrix.unbind_controller(74).is_some());
+        assert!(modmatrix.get_bindings().is_empty());
+        assert!(modmatrix.process_event(
+            &MidiEvent::ControlChange { number: 74, value: 127 }).is_none());
+
+        // Unbinding an unbound or out of range controller is harmless
+        assert!(modmatrix.unbind_controller(74).is_none());
+        assert!(modmatrix.unbind_controller(200).is_none());
+    }
+
+    #[test]
+    fn test_clear_bindings() {
+        let modmatrix = new_modmatrix();
+        modmatrix.bind_parameter(1, ParameterId::FilterFrequency);
+        modmatrix.bind_parameter(2, ParameterId::FilterQuality);
+
+        modmatrix.clear_bindings();
+        assert!(modmatrix.get_bindings().is_empty());
+    }
+
+    #[test]
+    fn test_controller_map_round_trip() {
+        let modmatrix = new_modmatrix();
+        modmatrix.bind_parameter(74, ParameterId::FilterFrequency);
+        modmatrix.bind_parameter(7, ParameterId::DelayFeedback);
+
+        let mut controller_map = Vec::new();
+        modmatrix.save_controller_map(&mut controller_map).unwrap();
+        assert_eq!(String::from_utf8(controller_map.clone()).unwrap(),
+                   "7 delay feedback\n74 filter frequency\n");
+
+        let other_modmatrix = new_modmatrix();
+        other_modmatrix.bind_parameter(1, ParameterId::AdsrAttack);
+        other_modmatrix.load_controller_map(controller_map.as_slice()).unwrap();
+
+        let bindings = other_modmatrix.get_bindings();
+        assert_eq!(bindings.len(), 2);
+        assert_eq!(bindings[0].0, 7);
+        assert_eq!(bindings[0].1 as i32, ParameterId::DelayFeedback as i32);
+        assert_eq!(bindings[1].0, 74);
+        assert_eq!(bindings[1].1 as i32, ParameterId::FilterFrequency as i32);
+    }
+
+    #[test]
+    fn test_controller_map_comments_and_blank_lines() {
+        let modmatrix = new_modmatrix();
+        let controller_map = "# My controller\n\n71 adsr decay\n";
+        modmatrix.load_controller_map(controller_map.as_bytes()).unwrap();
+
+        let bindings = modmatrix.get_bindings();
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(bindings[0].0, 71);
+        assert_eq!(bindings[0].1 as i32, ParameterId::AdsrDecay as i32);
+    }
+
+    #[test]
+    fn test_controller_map_errors_keep_bindings() {
+        let modmatrix = new_modmatrix();
+        modmatrix.bind_parameter(1, ParameterId::AdsrAttack);
+
+        modmatrix.load_controller_map("74 not a parameter\n".as_bytes()).unwrap_err();
+        modmatrix.load_controller_map("200 filter frequency\n".as_bytes()).unwrap_err();
+        modmatrix.load_controller_map("filter frequency\n".as_bytes()).unwrap_err();
+
+        let bindings = modmatrix.get_bindings();
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(bindings[0].1 as i32, ParameterId::AdsrAttack as i32);
+    }
+
+    #[test]
+    fn test_cancel_learn() {
+        let modmatrix = new_modmatrix();
+        assert!(modmatrix.cancel_learn().is_none());
+
+        modmatrix.learn_parameter(ParameterId::FilterQuality);
+        assert!(modmatrix.cancel_learn().is_some());
+
+        // The next CC should not be learned
+        modmatrix.process_event(&MidiEvent::ControlChange { number: 10, value: 0 });
+        assert!(modmatrix.get_bindings().is_empty());
+    }
+}
@@ -8,7 +8,7 @@ use std::sync::atomic::{
     Ordering
 };
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ParameterId {
     AdsrAttack,
     AdsrDecay,
@@ -35,6 +35,243 @@ pub enum ParameterId {
     PitchBendRange,
     WaveshaperInputGain,
     WaveshaperOutputGain,
+    // Parameters added since are appended below, so that the indices of existing
+    // parameters stay the same. VST hosts use the indices to save automation.
+    AdsrAttackCurve,
+    AdsrDecayCurve,
+    AdsrReleaseCurve,
+    AmpEnvelope,
+    DahdsrDelay,
+    DahdsrAttack,
+    DahdsrHold,
+    DahdsrDecay,
+    DahdsrSustain,
+    DahdsrRelease,
+    DahdsrLoop,
+    FilterEnvelope,
+    FilterKeyTracking,
+    FilterKeyTrackingCenter,
+    GeneratorAAttack,
+    GeneratorADecay,
+    GeneratorASustain,
+    GeneratorARelease,
+    GeneratorBAttack,
+    GeneratorBDecay,
+    GeneratorBSustain,
+    GeneratorBRelease,
+    GeneratorCAttack,
+    GeneratorCDecay,
+    GeneratorCSustain,
+    GeneratorCRelease,
+    GeneratorDAttack,
+    GeneratorDDecay,
+    GeneratorDSustain,
+    GeneratorDRelease,
+    GeneratorAWaveform,
+    GeneratorAPulseWidth,
+    GeneratorBWaveform,
+    GeneratorBPulseWidth,
+    GeneratorCWaveform,
+    GeneratorCPulseWidth,
+    GeneratorDWaveform,
+    GeneratorDPulseWidth,
+    GeneratorAWavetablePosition,
+    GeneratorBWavetablePosition,
+    GeneratorCWavetablePosition,
+    GeneratorDWavetablePosition,
+    GeneratorAFeedback,
+    GeneratorBFeedback,
+    GeneratorCFeedback,
+    GeneratorDFeedback,
+    GeneratorAFrequencyMode,
+    GeneratorARatioCoarse,
+    GeneratorARatioFine,
+    GeneratorAFixedFrequency,
+    GeneratorADetune,
+    GeneratorBFrequencyMode,
+    GeneratorBRatioCoarse,
+    GeneratorBRatioFine,
+    GeneratorBFixedFrequency,
+    GeneratorBDetune,
+    GeneratorCFrequencyMode,
+    GeneratorCRatioCoarse,
+    GeneratorCRatioFine,
+    GeneratorCFixedFrequency,
+    GeneratorCDetune,
+    GeneratorDFrequencyMode,
+    GeneratorDRatioCoarse,
+    GeneratorDRatioFine,
+    GeneratorDFixedFrequency,
+    GeneratorDDetune,
+    RoutingAToB,
+    RoutingAToC,
+    RoutingAToD,
+    RoutingBToA,
+    RoutingBToC,
+    RoutingBToD,
+    RoutingCToA,
+    RoutingCToB,
+    RoutingCToD,
+    RoutingDToA,
+    RoutingDToB,
+    RoutingDToC,
+    RoutingAOutput,
+    RoutingBOutput,
+    RoutingCOutput,
+    RoutingDOutput,
+    GeneratorALevel,
+    GeneratorBLevel,
+    GeneratorCLevel,
+    GeneratorDLevel,
+    GeneratorEPitch,
+    GeneratorEFrequencyMode,
+    GeneratorERatioCoarse,
+    GeneratorERatioFine,
+    GeneratorEFixedFrequency,
+    GeneratorEDetune,
+    GeneratorEModIndex,
+    GeneratorEAttack,
+    GeneratorEDecay,
+    GeneratorESustain,
+    GeneratorERelease,
+    GeneratorEWaveform,
+    GeneratorEPulseWidth,
+    GeneratorEWavetablePosition,
+    GeneratorEFeedback,
+    GeneratorELevel,
+    GeneratorFPitch,
+    GeneratorFFrequencyMode,
+    GeneratorFRatioCoarse,
+    GeneratorFRatioFine,
+    GeneratorFFixedFrequency,
+    GeneratorFDetune,
+    GeneratorFModIndex,
+    GeneratorFAttack,
+    GeneratorFDecay,
+    GeneratorFSustain,
+    GeneratorFRelease,
+    GeneratorFWaveform,
+    GeneratorFPulseWidth,
+    GeneratorFWavetablePosition,
+    GeneratorFFeedback,
+    GeneratorFLevel,
+    RoutingAToE,
+    RoutingAToF,
+    RoutingBToE,
+    RoutingBToF,
+    RoutingCToE,
+    RoutingCToF,
+    RoutingDToE,
+    RoutingDToF,
+    RoutingEToA,
+    RoutingEToB,
+    RoutingEToC,
+    RoutingEToD,
+    RoutingEToF,
+    RoutingFToA,
+    RoutingFToB,
+    RoutingFToC,
+    RoutingFToD,
+    RoutingFToE,
+    RoutingEOutput,
+    RoutingFOutput,
+    GeneratorAOutput,
+    GeneratorADirect,
+    GeneratorBOutput,
+    GeneratorBDirect,
+    GeneratorCOutput,
+    GeneratorCDirect,
+    GeneratorDOutput,
+    GeneratorDDirect,
+    GeneratorEOutput,
+    GeneratorEDirect,
+    GeneratorFOutput,
+    GeneratorFDirect,
+    MixLevel,
+    NoiseType,
+    NoiseLevel,
+    NoiseToA,
+    NoiseToB,
+    NoiseToC,
+    NoiseToD,
+    NoiseToE,
+    NoiseToF,
+    SubOscillatorWaveform,
+    SubOscillatorOctave,
+    SubOscillatorLevel,
+    GeneratorASyncSource,
+    GeneratorARingSource,
+    GeneratorBSyncSource,
+    GeneratorBRingSource,
+    GeneratorCSyncSource,
+    GeneratorCRingSource,
+    GeneratorDSyncSource,
+    GeneratorDRingSource,
+    GeneratorESyncSource,
+    GeneratorERingSource,
+    GeneratorFSyncSource,
+    GeneratorFRingSource,
+    GeneratorAPhaseMode,
+    GeneratorAStartPhase,
+    GeneratorBPhaseMode,
+    GeneratorBStartPhase,
+    GeneratorCPhaseMode,
+    GeneratorCStartPhase,
+    GeneratorDPhaseMode,
+    GeneratorDStartPhase,
+    GeneratorEPhaseMode,
+    GeneratorEStartPhase,
+    GeneratorFPhaseMode,
+    GeneratorFStartPhase,
+    Oversampling,
+    GeneratorAVelocitySensitivity,
+    GeneratorAKeyBreakpoint,
+    GeneratorAKeyLeftCurve,
+    GeneratorAKeyRightCurve,
+    GeneratorAKeyLeftDepth,
+    GeneratorAKeyRightDepth,
+    GeneratorAKeyRateScaling,
+    GeneratorBVelocitySensitivity,
+    GeneratorBKeyBreakpoint,
+    GeneratorBKeyLeftCurve,
+    GeneratorBKeyRightCurve,
+    GeneratorBKeyLeftDepth,
+    GeneratorBKeyRightDepth,
+    GeneratorBKeyRateScaling,
+    GeneratorCVelocitySensitivity,
+    GeneratorCKeyBreakpoint,
+    GeneratorCKeyLeftCurve,
+    GeneratorCKeyRightCurve,
+    GeneratorCKeyLeftDepth,
+    GeneratorCKeyRightDepth,
+    GeneratorCKeyRateScaling,
+    GeneratorDVelocitySensitivity,
+    GeneratorDKeyBreakpoint,
+    GeneratorDKeyLeftCurve,
+    GeneratorDKeyRightCurve,
+    GeneratorDKeyLeftDepth,
+    GeneratorDKeyRightDepth,
+    GeneratorDKeyRateScaling,
+    GeneratorEVelocitySensitivity,
+    GeneratorEKeyBreakpoint,
+    GeneratorEKeyLeftCurve,
+    GeneratorEKeyRightCurve,
+    GeneratorEKeyLeftDepth,
+    GeneratorEKeyRightDepth,
+    GeneratorEKeyRateScaling,
+    GeneratorFVelocitySensitivity,
+    GeneratorFKeyBreakpoint,
+    GeneratorFKeyLeftCurve,
+    GeneratorFKeyRightCurve,
+    GeneratorFKeyLeftDepth,
+    GeneratorFKeyRightDepth,
+    GeneratorFKeyRateScaling,
+    FilterType,
+    FilterGain,
+    FilterModel,
+    FilterSlope,
+    FilterDrive,
+    FilterFeedbackSaturation,
 }
 
 impl From<i32> for ParameterId {
@@ -67,19 +304,256 @@ impl From<i32> for ParameterId {
             22 => ParameterId::PitchBendRange,
             23 => ParameterId::WaveshaperInputGain,
             24 => ParameterId::WaveshaperOutputGain,
+            25 => ParameterId::AdsrAttackCurve,
+            26 => ParameterId::AdsrDecayCurve,
+            27 => ParameterId::AdsrReleaseCurve,
+            28 => ParameterId::AmpEnvelope,
+            29 => ParameterId::DahdsrDelay,
+            30 => ParameterId::DahdsrAttack,
+            31 => ParameterId::DahdsrHold,
+            32 => ParameterId::DahdsrDecay,
+            33 => ParameterId::DahdsrSustain,
+            34 => ParameterId::DahdsrRelease,
+            35 => ParameterId::DahdsrLoop,
+            36 => ParameterId::FilterEnvelope,
+            37 => ParameterId::FilterKeyTracking,
+            38 => ParameterId::FilterKeyTrackingCenter,
+            39 => ParameterId::GeneratorAAttack,
+            40 => ParameterId::GeneratorADecay,
+            41 => ParameterId::GeneratorASustain,
+            42 => ParameterId::GeneratorARelease,
+            43 => ParameterId::GeneratorBAttack,
+            44 => ParameterId::GeneratorBDecay,
+            45 => ParameterId::GeneratorBSustain,
+            46 => ParameterId::GeneratorBRelease,
+            47 => ParameterId::GeneratorCAttack,
+            48 => ParameterId::GeneratorCDecay,
+            49 => ParameterId::GeneratorCSustain,
+            50 => ParameterId::GeneratorCRelease,
+            51 => ParameterId::GeneratorDAttack,
+            52 => ParameterId::GeneratorDDecay,
+            53 => ParameterId::GeneratorDSustain,
+            54 => ParameterId::GeneratorDRelease,
+            55 => ParameterId::GeneratorAWaveform,
+            56 => ParameterId::GeneratorAPulseWidth,
+            57 => ParameterId::GeneratorBWaveform,
+            58 => ParameterId::GeneratorBPulseWidth,
+            59 => ParameterId::GeneratorCWaveform,
+            60 => ParameterId::GeneratorCPulseWidth,
+            61 => ParameterId::GeneratorDWaveform,
+            62 => ParameterId::GeneratorDPulseWidth,
+            63 => ParameterId::GeneratorAWavetablePosition,
+            64 => ParameterId::GeneratorBWavetablePosition,
+            65 => ParameterId::GeneratorCWavetablePosition,
+            66 => ParameterId::GeneratorDWavetablePosition,
+            67 => ParameterId::GeneratorAFeedback,
+            68 => ParameterId::GeneratorBFeedback,
+            69 => ParameterId::GeneratorCFeedback,
+            70 => ParameterId::GeneratorDFeedback,
+            71 => ParameterId::GeneratorAFrequencyMode,
+            72 => ParameterId::GeneratorARatioCoarse,
+            73 => ParameterId::GeneratorARatioFine,
+            74 => ParameterId::GeneratorAFixedFrequency,
+            75 => ParameterId::GeneratorADetune,
+            76 => ParameterId::GeneratorBFrequencyMode,
+            77 => ParameterId::GeneratorBRatioCoarse,
+            78 => ParameterId::GeneratorBRatioFine,
+            79 => ParameterId::GeneratorBFixedFrequency,
+            80 => ParameterId::GeneratorBDetune,
+            81 => ParameterId::GeneratorCFrequencyMode,
+            82 => ParameterId::GeneratorCRatioCoarse,
+            83 => ParameterId::GeneratorCRatioFine,
+            84 => ParameterId::GeneratorCFixedFrequency,
+            85 => ParameterId::GeneratorCDetune,
+            86 => ParameterId::GeneratorDFrequencyMode,
+            87 => ParameterId::GeneratorDRatioCoarse,
+            88 => ParameterId::GeneratorDRatioFine,
+            89 => ParameterId::GeneratorDFixedFrequency,
+            90 => ParameterId::GeneratorDDetune,
+            91 => ParameterId::RoutingAToB,
+            92 => ParameterId::RoutingAToC,
+            93 => ParameterId::RoutingAToD,
+            94 => ParameterId::RoutingBToA,
+            95 => ParameterId::RoutingBToC,
+            96 => ParameterId::RoutingBToD,
+            97 => ParameterId::RoutingCToA,
+            98 => ParameterId::RoutingCToB,
+            99 => ParameterId::RoutingCToD,
+            100 => ParameterId::RoutingDToA,
+            101 => ParameterId::RoutingDToB,
+            102 => ParameterId::RoutingDToC,
+            103 => ParameterId::RoutingAOutput,
+            104 => ParameterId::RoutingBOutput,
+            105 => ParameterId::RoutingCOutput,
+            106 => ParameterId::RoutingDOutput,
+            107 => ParameterId::GeneratorALevel,
+            108 => ParameterId::GeneratorBLevel,
+            109 => ParameterId::GeneratorCLevel,
+            110 => ParameterId::GeneratorDLevel,
+            111 => ParameterId::GeneratorEPitch,
+            112 => ParameterId::GeneratorEFrequencyMode,
+            113 => ParameterId::GeneratorERatioCoarse,
+            114 => ParameterId::GeneratorERatioFine,
+            115 => ParameterId::GeneratorEFixedFrequency,
+            116 => ParameterId::GeneratorEDetune,
+            117 => ParameterId::GeneratorEModIndex,
+            118 => ParameterId::GeneratorEAttack,
+            119 => ParameterId::GeneratorEDecay,
+            120 => ParameterId::GeneratorESustain,
+            121 => ParameterId::GeneratorERelease,
+            122 => ParameterId::GeneratorEWaveform,
+            123 => ParameterId::GeneratorEPulseWidth,
+            124 => ParameterId::GeneratorEWavetablePosition,
+            125 => ParameterId::GeneratorEFeedback,
+            126 => ParameterId::GeneratorELevel,
+            127 => ParameterId::GeneratorFPitch,
+            128 => ParameterId::GeneratorFFrequencyMode,
+            129 => ParameterId::GeneratorFRatioCoarse,
+            130 => ParameterId::GeneratorFRatioFine,
+            131 => ParameterId::GeneratorFFixedFrequency,
+            132 => ParameterId::GeneratorFDetune,
+            133 => ParameterId::GeneratorFModIndex,
+            134 => ParameterId::GeneratorFAttack,
+            135 => ParameterId::GeneratorFDecay,
+            136 => ParameterId::GeneratorFSustain,
+            137 => ParameterId::GeneratorFRelease,
+            138 => ParameterId::GeneratorFWaveform,
+            139 => ParameterId::GeneratorFPulseWidth,
+            140 => ParameterId::GeneratorFWavetablePosition,
+            141 => ParameterId::GeneratorFFeedback,
+            142 => ParameterId::GeneratorFLevel,
+            143 => ParameterId::RoutingAToE,
+            144 => ParameterId::RoutingAToF,
+            145 => ParameterId::RoutingBToE,
+            146 => ParameterId::RoutingBToF,
+            147 => ParameterId::RoutingCToE,
+            148 => ParameterId::RoutingCToF,
+            149 => ParameterId::RoutingDToE,
+            150 => ParameterId::RoutingDToF,
+            151 => ParameterId::RoutingEToA,
+            152 => ParameterId::RoutingEToB,
+            153 => ParameterId::RoutingEToC,
+            154 => ParameterId::RoutingEToD,
+            155 => ParameterId::RoutingEToF,
+            156 => ParameterId::RoutingFToA,
+            157 => ParameterId::RoutingFToB,
+            158 => ParameterId::RoutingFToC,
+            159 => ParameterId::RoutingFToD,
+            160 => ParameterId::RoutingFToE,
+            161 => ParameterId::RoutingEOutput,
+            162 => ParameterId::RoutingFOutput,
+            163 => ParameterId::GeneratorAOutput,
+            164 => ParameterId::GeneratorADirect,
+            165 => ParameterId::GeneratorBOutput,
+            166 => ParameterId::GeneratorBDirect,
+            167 => ParameterId::GeneratorCOutput,
+            168 => ParameterId::GeneratorCDirect,
+            169 => ParameterId::GeneratorDOutput,
+            170 => ParameterId::GeneratorDDirect,
+            171 => ParameterId::GeneratorEOutput,
+            172 => ParameterId::GeneratorEDirect,
+            173 => ParameterId::GeneratorFOutput,
+            174 => ParameterId::GeneratorFDirect,
+            175 => ParameterId::MixLevel,
+            176 => ParameterId::NoiseType,
+            177 => ParameterId::NoiseLevel,
+            178 => ParameterId::NoiseToA,
+            179 => ParameterId::NoiseToB,
+            180 => ParameterId::NoiseToC,
+            181 => ParameterId::NoiseToD,
+            182 => ParameterId::NoiseToE,
+            183 => ParameterId::NoiseToF,
+            184 => ParameterId::SubOscillatorWaveform,
+            185 => ParameterId::SubOscillatorOctave,
+            186 => ParameterId::SubOscillatorLevel,
+            187 => ParameterId::GeneratorASyncSource,
+            188 => ParameterId::GeneratorARingSource,
+            189 => ParameterId::GeneratorBSyncSource,
+            190 => ParameterId::GeneratorBRingSource,
+            191 => ParameterId::GeneratorCSyncSource,
+            192 => ParameterId::GeneratorCRingSource,
+            193 => ParameterId::GeneratorDSyncSource,
+            194 => ParameterId::GeneratorDRingSource,
+            195 => ParameterId::GeneratorESyncSource,
+            196 => ParameterId::GeneratorERingSource,
+            197 => ParameterId::GeneratorFSyncSource,
+            198 => ParameterId::GeneratorFRingSource,
+            199 => ParameterId::GeneratorAPhaseMode,
+            200 => ParameterId::GeneratorAStartPhase,
+            201 => ParameterId::GeneratorBPhaseMode,
+            202 => ParameterId::GeneratorBStartPhase,
+            203 => ParameterId::GeneratorCPhaseMode,
+            204 => ParameterId::GeneratorCStartPhase,
+            205 => ParameterId::GeneratorDPhaseMode,
+            206 => ParameterId::GeneratorDStartPhase,
+            207 => ParameterId::GeneratorEPhaseMode,
+            208 => ParameterId::GeneratorEStartPhase,
+            209 => ParameterId::GeneratorFPhaseMode,
+            210 => ParameterId::GeneratorFStartPhase,
+            211 => ParameterId::Oversampling,
+            212 => ParameterId::GeneratorAVelocitySensitivity,
+            213 => ParameterId::GeneratorAKeyBreakpoint,
+            214 => ParameterId::GeneratorAKeyLeftCurve,
+            215 => ParameterId::GeneratorAKeyRightCurve,
+            216 => ParameterId::GeneratorAKeyLeftDepth,
+            217 => ParameterId::GeneratorAKeyRightDepth,
+            218 => ParameterId::GeneratorAKeyRateScaling,
+            219 => ParameterId::GeneratorBVelocitySensitivity,
+            220 => ParameterId::GeneratorBKeyBreakpoint,
+            221 => ParameterId::GeneratorBKeyLeftCurve,
+            222 => ParameterId::GeneratorBKeyRightCurve,
+            223 => ParameterId::GeneratorBKeyLeftDepth,
+            224 => ParameterId::GeneratorBKeyRightDepth,
+            225 => ParameterId::GeneratorBKeyRateScaling,
+            226 => ParameterId::GeneratorCVelocitySensitivity,
+            227 => ParameterId::GeneratorCKeyBreakpoint,
+            228 => ParameterId::GeneratorCKeyLeftCurve,
+            229 => ParameterId::GeneratorCKeyRightCurve,
+            230 => ParameterId::GeneratorCKeyLeftDepth,
+            231 => ParameterId::GeneratorCKeyRightDepth,
+            232 => ParameterId::GeneratorCKeyRateScaling,
+            233 => ParameterId::GeneratorDVelocitySensitivity,
+            234 => ParameterId::GeneratorDKeyBreakpoint,
+            235 => ParameterId::GeneratorDKeyLeftCurve,
+            236 => ParameterId::GeneratorDKeyRightCurve,
+            237 => ParameterId::GeneratorDKeyLeftDepth,
+            238 => ParameterId::GeneratorDKeyRightDepth,
+            239 => ParameterId::GeneratorDKeyRateScaling,
+            240 => ParameterId::GeneratorEVelocitySensitivity,
+            241 => ParameterId::GeneratorEKeyBreakpoint,
+            242 => ParameterId::GeneratorEKeyLeftCurve,
+            243 => ParameterId::GeneratorEKeyRightCurve,
+            244 => ParameterId::GeneratorEKeyLeftDepth,
+            245 => ParameterId::GeneratorEKeyRightDepth,
+            246 => ParameterId::GeneratorEKeyRateScaling,
+            247 => ParameterId::GeneratorFVelocitySensitivity,
+            248 => ParameterId::GeneratorFKeyBreakpoint,
+            249 => ParameterId::GeneratorFKeyLeftCurve,
+            250 => ParameterId::GeneratorFKeyRightCurve,
+            251 => ParameterId::GeneratorFKeyLeftDepth,
+            252 => ParameterId::GeneratorFKeyRightDepth,
+            253 => ParameterId::GeneratorFKeyRateScaling,
+            254 => ParameterId::FilterType,
+            255 => ParameterId::FilterGain,
+            256 => ParameterId::FilterModel,
+            257 => ParameterId::FilterSlope,
+            258 => ParameterId::FilterDrive,
+            259 => ParameterId::FilterFeedbackSaturation,
             _ => panic!("Parameter ID out of bounds"),
         }
     }
 }
-pub const NUM_PARAMS: i32 = 33;
+pub const NUM_PARAMS: i32 = 260;
 
 pub enum ParameterUnit {
     NoUnit,
     Seconds,
     Hz,
     Semitones,
+    Cents,
     Octaves,
     Percent,
+    Decibels,
 }
 
 fn unit_formatter(unit: &ParameterUnit, value: defs::Sample) -> String {
@@ -88,8 +562,10 @@ fn unit_formatter(unit: &ParameterUnit, value: defs::Sample) -> String {
         ParameterUnit::Seconds => format!("{:.1} Seconds", value),
         ParameterUnit::Hz => format!("{:.1} Hz", value),
         ParameterUnit::Semitones => format!("{:.1} Semitones", value),
+        ParameterUnit::Cents => format!("{:.1} Cents", value),
         ParameterUnit::Octaves => format!("{:.1} Octaves", value),
         ParameterUnit::Percent => format!("{:.1} %", value * 100.0),
+        ParameterUnit::Decibels => format!("{:.1} dB", value),
     }
 }
 
@@ -98,6 +574,17 @@ pub struct BaseliskPluginParameters {
     adsr_decay: Parameter,
     adsr_sustain: Parameter,
     adsr_release: Parameter,
+    adsr_attack_curve: Parameter,
+    adsr_decay_curve: Parameter,
+    adsr_release_curve: Parameter,
+    amp_envelope: Parameter,
+    dahdsr_delay: Parameter,
+    dahdsr_attack: Parameter,
+    dahdsr_hold: Parameter,
+    dahdsr_decay: Parameter,
+    dahdsr_sustain: Parameter,
+    dahdsr_release: Parameter,
+    dahdsr_loop: Parameter,
     delay_time_left: Parameter,
     delay_time_right: Parameter,
     delay_feedback: Parameter,
@@ -107,16 +594,240 @@ pub struct BaseliskPluginParameters {
     filter_frequency: Parameter,
     filter_sweep_range: Parameter,
     filter_quality: Parameter,
+    filter_envelope: Parameter,
+    filter_key_tracking: Parameter,
+    filter_key_tracking_center: Parameter,
+    filter_type: Parameter,
+    filter_gain: Parameter,
+    filter_model: Parameter,
+    filter_slope: Parameter,
+    filter_drive: Parameter,
+    filter_feedback_saturation: Parameter,
     generator_a_pitch: Parameter,
+    generator_a_frequency_mode: Parameter,
+    generator_a_ratio_coarse: Parameter,
+    generator_a_ratio_fine: Parameter,
+    generator_a_fixed_frequency: Parameter,
+    generator_a_detune: Parameter,
     generator_a_mod_index: Parameter,
+    generator_a_attack: Parameter,
+    generator_a_decay: Parameter,
+    generator_a_sustain: Parameter,
+    generator_a_release: Parameter,
+    generator_a_waveform: Parameter,
+    generator_a_pulse_width: Parameter,
+    generator_a_wavetable_position: Parameter,
+    generator_a_feedback: Parameter,
+    generator_a_level: Parameter,
+    generator_a_output: Parameter,
+    generator_a_direct: Parameter,
+    generator_a_sync_source: Parameter,
+    generator_a_ring_source: Parameter,
+    generator_a_phase_mode: Parameter,
+    generator_a_start_phase: Parameter,
+    generator_a_velocity_sensitivity: Parameter,
+    generator_a_key_breakpoint: Parameter,
+    generator_a_key_left_curve: Parameter,
+    generator_a_key_right_curve: Parameter,
+    generator_a_key_left_depth: Parameter,
+    generator_a_key_right_depth: Parameter,
+    generator_a_key_rate_scaling: Parameter,
     generator_b_pitch: Parameter,
+    generator_b_frequency_mode: Parameter,
+    generator_b_ratio_coarse: Parameter,
+    generator_b_ratio_fine: Parameter,
+    generator_b_fixed_frequency: Parameter,
+    generator_b_detune: Parameter,
     generator_b_mod_index: Parameter,
+    generator_b_attack: Parameter,
+    generator_b_decay: Parameter,
+    generator_b_sustain: Parameter,
+    generator_b_release: Parameter,
+    generator_b_waveform: Parameter,
+    generator_b_pulse_width: Parameter,
+    generator_b_wavetable_position: Parameter,
+    generator_b_feedback: Parameter,
+    generator_b_level: Parameter,
+    generator_b_output: Parameter,
+    generator_b_direct: Parameter,
+    generator_b_sync_source: Parameter,
+    generator_b_ring_source: Parameter,
+    generator_b_phase_mode: Parameter,
+    generator_b_start_phase: Parameter,
+    generator_b_velocity_sensitivity: Parameter,
+    generator_b_key_breakpoint: Parameter,
+    generator_b_key_left_curve: Parameter,
+    generator_b_key_right_curve: Parameter,
+    generator_b_key_left_depth: Parameter,
+    generator_b_key_right_depth: Parameter,
+    generator_b_key_rate_scaling: Parameter,
     generator_c_pitch: Parameter,
+    generator_c_frequency_mode: Parameter,
+    generator_c_ratio_coarse: Parameter,
+    generator_c_ratio_fine: Parameter,
+    generator_c_fixed_frequency: Parameter,
+    generator_c_detune: Parameter,
     generator_c_mod_index: Parameter,
+    generator_c_attack: Parameter,
+    generator_c_decay: Parameter,
+    generator_c_sustain: Parameter,
+    generator_c_release: Parameter,
+    generator_c_waveform: Parameter,
+    generator_c_pulse_width: Parameter,
+    generator_c_wavetable_position: Parameter,
+    generator_c_feedback: Parameter,
+    generator_c_level: Parameter,
+    generator_c_output: Parameter,
+    generator_c_direct: Parameter,
+    generator_c_sync_source: Parameter,
+    generator_c_ring_source: Parameter,
+    generator_c_phase_mode: Parameter,
+    generator_c_start_phase: Parameter,
+    generator_c_velocity_sensitivity: Parameter,
+    generator_c_key_breakpoint: Parameter,
+    generator_c_key_left_curve: Parameter,
+    generator_c_key_right_curve: Parameter,
+    generator_c_key_left_depth: Parameter,
+    generator_c_key_right_depth: Parameter,
+    generator_c_key_rate_scaling: Parameter,
     generator_d_pitch: Parameter,
+    generator_d_frequency_mode: Parameter,
+    generator_d_ratio_coarse: Parameter,
+    generator_d_ratio_fine: Parameter,
+    generator_d_fixed_frequency: Parameter,
+    generator_d_detune: Parameter,
     generator_d_mod_index: Parameter,
+    generator_d_attack: Parameter,
+    generator_d_decay: Parameter,
+    generator_d_sustain: Parameter,
+    generator_d_release: Parameter,
+    generator_d_waveform: Parameter,
+    generator_d_pulse_width: Parameter,
+    generator_d_wavetable_position: Parameter,
+    generator_d_feedback: Parameter,
+    generator_d_level: Parameter,
+    generator_d_output: Parameter,
+    generator_d_direct: Parameter,
+    generator_d_sync_source: Parameter,
+    generator_d_ring_source: Parameter,
+    generator_d_phase_mode: Parameter,
+    generator_d_start_phase: Parameter,
+    generator_d_velocity_sensitivity: Parameter,
+    generator_d_key_breakpoint: Parameter,
+    generator_d_key_left_curve: Parameter,
+    generator_d_key_right_curve: Parameter,
+    generator_d_key_left_depth: Parameter,
+    generator_d_key_right_depth: Parameter,
+    generator_d_key_rate_scaling: Parameter,
+    generator_e_pitch: Parameter,
+    generator_e_frequency_mode: Parameter,
+    generator_e_ratio_coarse: Parameter,
+    generator_e_ratio_fine: Parameter,
+    generator_e_fixed_frequency: Parameter,
+    generator_e_detune: Parameter,
+    generator_e_mod_index: Parameter,
+    generator_e_attack: Parameter,
+    generator_e_decay: Parameter,
+    generator_e_sustain: Parameter,
+    generator_e_release: Parameter,
+    generator_e_waveform: Parameter,
+    generator_e_pulse_width: Parameter,
+    generator_e_wavetable_position: Parameter,
+    generator_e_feedback: Parameter,
+    generator_e_level: Parameter,
+    generator_e_output: Parameter,
+    generator_e_direct: Parameter,
+    generator_e_sync_source: Parameter,
+    generator_e_ring_source: Parameter,
+    generator_e_phase_mode: Parameter,
+    generator_e_start_phase: Parameter,
+    generator_e_velocity_sensitivity: Parameter,
+    generator_e_key_breakpoint: Parameter,
+    generator_e_key_left_curve: Parameter,
+    generator_e_key_right_curve: Parameter,
+    generator_e_key_left_depth: Parameter,
+    generator_e_key_right_depth: Parameter,
+    generator_e_key_rate_scaling: Parameter,
+    generator_f_pitch: Parameter,
+    generator_f_frequency_mode: Parameter,
+    generator_f_ratio_coarse: Parameter,
+    generator_f_ratio_fine: Parameter,
+    generator_f_fixed_frequency: Parameter,
+    generator_f_detune: Parameter,
+    generator_f_mod_index: Parameter,
+    generator_f_attack: Parameter,
+    generator_f_decay: Parameter,
+    generator_f_sustain: Parameter,
+    generator_f_release: Parameter,
+    generator_f_waveform: Parameter,
+    generator_f_pulse_width: Parameter,
+    generator_f_wavetable_position: Parameter,
+    generator_f_feedback: Parameter,
+    generator_f_level: Parameter,
+    generator_f_output: Parameter,
+    generator_f_direct: Parameter,
+    generator_f_sync_source: Parameter,
+    generator_f_ring_source: Parameter,
+    generator_f_phase_mode: Parameter,
+    generator_f_start_phase: Parameter,
+    generator_f_velocity_sensitivity: Parameter,
+    generator_f_key_breakpoint: Parameter,
+    generator_f_key_left_curve: Parameter,
+    generator_f_key_right_curve: Parameter,
+    generator_f_key_left_depth: Parameter,
+    generator_f_key_right_depth: Parameter,
+    generator_f_key_rate_scaling: Parameter,
     generator_routing: Parameter,
     pitch_bend_range: Parameter,
+    routing_a_to_b: Parameter,
+    routing_a_to_c: Parameter,
+    routing_a_to_d: Parameter,
+    routing_a_to_e: Parameter,
+    routing_a_to_f: Parameter,
+    routing_b_to_a: Parameter,
+    routing_b_to_c: Parameter,
+    routing_b_to_d: Parameter,
+    routing_b_to_e: Parameter,
+    routing_b_to_f: Parameter,
+    routing_c_to_a: Parameter,
+    routing_c_to_b: Parameter,
+    routing_c_to_d: Parameter,
+    routing_c_to_e: Parameter,
+    routing_c_to_f: Parameter,
+    routing_d_to_a: Parameter,
+    routing_d_to_b: Parameter,
+    routing_d_to_c: Parameter,
+    routing_d_to_e: Parameter,
+    routing_d_to_f: Parameter,
+    routing_e_to_a: Parameter,
+    routing_e_to_b: Parameter,
+    routing_e_to_c: Parameter,
+    routing_e_to_d: Parameter,
+    routing_e_to_f: Parameter,
+    routing_f_to_a: Parameter,
+    routing_f_to_b: Parameter,
+    routing_f_to_c: Parameter,
+    routing_f_to_d: Parameter,
+    routing_f_to_e: Parameter,
+    routing_a_output: Parameter,
+    routing_b_output: Parameter,
+    routing_c_output: Parameter,
+    routing_d_output: Parameter,
+    routing_e_output: Parameter,
+    routing_f_output: Parameter,
+    mix_level: Parameter,
+    noise_type: Parameter,
+    noise_level: Parameter,
+    noise_to_a: Parameter,
+    noise_to_b: Parameter,
+    noise_to_c: Parameter,
+    noise_to_d: Parameter,
+    noise_to_e: Parameter,
+    noise_to_f: Parameter,
+    sub_oscillator_waveform: Parameter,
+    sub_oscillator_octave: Parameter,
+    sub_oscillator_level: Parameter,
+    oversampling: Parameter,
     waveshaper_input_gain: Parameter,
     waveshaper_output_gain: Parameter,
 }
@@ -136,6 +847,43 @@ impl Default for BaseliskPluginParameters {
             adsr_release: Parameter::new_exponential(
                 "adsr release",
                 ParameterUnit::Seconds, 0.02, 10.0, 0.4),
+            adsr_attack_curve: Parameter::new_linear(
+                "adsr attack curve",
+                ParameterUnit::NoUnit, -1.0, 1.0, 0.0),
+            adsr_decay_curve: Parameter::new_linear(
+                "adsr decay curve",
+                ParameterUnit::NoUnit, -1.0, 1.0, 0.0),
+            adsr_release_curve: Parameter::new_linear(
+                "adsr release curve",
+                ParameterUnit::NoUnit, -1.0, 1.0, 0.0),
+            amp_envelope: Parameter::new_enum(
+                "amp envelope",
+                vec!["adsr", "dahdsr"],
+                0,
+            ),
+            dahdsr_delay: Parameter::new_linear(
+                "dahdsr delay",
+                ParameterUnit::Seconds, 0.0, 10.0, 0.0),
+            dahdsr_attack: Parameter::new_exponential(
+                "dahdsr attack",
+                ParameterUnit::Seconds, 0.001, 10.0, 0.02),
+            dahdsr_hold: Parameter::new_linear(
+                "dahdsr hold",
+                ParameterUnit::Seconds, 0.0, 10.0, 0.0),
+            dahdsr_decay: Parameter::new_exponential(
+                "dahdsr decay",
+                ParameterUnit::Seconds, 0.02, 10.0, 0.707),
+            dahdsr_sustain: Parameter::new_linear(
+                "dahdsr sustain",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            dahdsr_release: Parameter::new_exponential(
+                "dahdsr release",
+                ParameterUnit::Seconds, 0.02, 10.0, 0.4),
+            dahdsr_loop: Parameter::new_enum(
+                "dahdsr loop",
+                vec!["off", "attack", "delay"],
+                0,
+            ),
             delay_time_left: Parameter::new_exponential(
                 "delay time left",
                 ParameterUnit::Seconds, 0.08, 1.0, 0.375),
@@ -163,42 +911,834 @@ impl Default for BaseliskPluginParameters {
             filter_quality: Parameter::new_exponential(
                 "filter quality",
                 ParameterUnit::NoUnit, 0.5, 10.0, 0.707),
+            filter_envelope: Parameter::new_enum(
+                "filter envelope",
+                vec!["adsr", "dahdsr"],
+                0,
+            ),
+            filter_key_tracking: Parameter::new_linear(
+                "filter key tracking",
+                ParameterUnit::Percent, 0.0, 2.0, 0.0),
+            filter_key_tracking_center: Parameter::new_linear(
+                "filter key tracking center",
+                ParameterUnit::NoUnit, 0.0, 127.0, 60.0
+            ).enable_int_snapping(),
+            filter_type: Parameter::new_enum(
+                "filter type",
+                vec!["lowpass", "highpass", "bandpass", "notch", "peaking", "allpass"],
+                0,
+            ),
+            filter_gain: Parameter::new_linear(
+                "filter gain",
+                ParameterUnit::Decibels, -24.0, 24.0, 6.0),
+            filter_model: Parameter::new_enum(
+                "filter model",
+                vec!["biquad", "svf", "ladder"],
+                0,
+            ),
+            filter_slope: Parameter::new_enum(
+                "filter slope",
+                vec!["12db", "24db", "36db"],
+                0,
+            ),
+            filter_drive: Parameter::new_linear(
+                "filter drive",
+                ParameterUnit::Decibels, 0.0, 36.0, 0.0),
+            filter_feedback_saturation: Parameter::new_enum(
+                "filter feedback saturation",
+                vec!["off", "on"],
+                0,
+            ),
             generator_a_pitch: Parameter::new_linear(
                 "generator a pitch",
                 ParameterUnit::Semitones, -36.0, 36.0, 0.0
             ).enable_int_snapping(),
+            generator_a_frequency_mode: Parameter::new_enum(
+                "generator a frequency mode",
+                vec!["semitones", "ratio", "fixed"],
+                0,
+            ),
+            generator_a_ratio_coarse: Parameter::new_linear(
+                "generator a ratio coarse",
+                ParameterUnit::NoUnit, 0.0, 64.0, 1.0
+            ).enable_int_snapping(),
+            generator_a_ratio_fine: Parameter::new_linear(
+                "generator a ratio fine",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_a_fixed_frequency: Parameter::new_exponential(
+                "generator a fixed frequency",
+                ParameterUnit::Hz, 1.0, 20000.0, 440.0),
+            generator_a_detune: Parameter::new_linear(
+                "generator a detune",
+                ParameterUnit::Cents, -100.0, 100.0, 0.0),
             generator_a_mod_index: Parameter::new_linear(
                 "generator a mod index",
                 ParameterUnit::NoUnit, 0.0, 8.0, 1.0),
+            generator_a_attack: Parameter::new_exponential(
+                "generator a attack",
+                ParameterUnit::Seconds, 0.001, 10.0, 0.001),
+            generator_a_decay: Parameter::new_exponential(
+                "generator a decay",
+                ParameterUnit::Seconds, 0.02, 10.0, 0.707),
+            generator_a_sustain: Parameter::new_linear(
+                "generator a sustain",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_a_release: Parameter::new_exponential(
+                "generator a release",
+                ParameterUnit::Seconds, 0.02, 10.0, 10.0),
+            generator_a_waveform: Parameter::new_enum(
+                "generator a waveform",
+                vec!["sine", "halfsine", "saw", "pulse", "triangle", "wavetable"],
+                0,
+            ),
+            generator_a_pulse_width: Parameter::new_linear(
+                "generator a pulse width",
+                ParameterUnit::Percent, 0.01, 0.99, 0.5),
+            generator_a_wavetable_position: Parameter::new_linear(
+                "generator a wavetable position",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_a_feedback: Parameter::new_linear(
+                "generator a feedback",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_a_level: Parameter::new_linear(
+                "generator a level",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_a_output: Parameter::new_linear(
+                "generator a output",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_a_direct: Parameter::new_linear(
+                "generator a direct",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_a_sync_source: Parameter::new_enum(
+                "generator a sync source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_a_ring_source: Parameter::new_enum(
+                "generator a ring source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_a_phase_mode: Parameter::new_enum(
+                "generator a phase mode",
+                vec!["free", "reset", "random"],
+                0,
+            ),
+            generator_a_start_phase: Parameter::new_linear(
+                "generator a start phase",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_a_velocity_sensitivity: Parameter::new_linear(
+                "generator a velocity sensitivity",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_a_key_breakpoint: Parameter::new_linear(
+                "generator a key breakpoint",
+                ParameterUnit::NoUnit, 0.0, 127.0, 60.0
+            ).enable_int_snapping(),
+            generator_a_key_left_curve: Parameter::new_enum(
+                "generator a key left curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_a_key_right_curve: Parameter::new_enum(
+                "generator a key right curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_a_key_left_depth: Parameter::new_linear(
+                "generator a key left depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_a_key_right_depth: Parameter::new_linear(
+                "generator a key right depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_a_key_rate_scaling: Parameter::new_linear(
+                "generator a key rate scaling",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
             generator_b_pitch: Parameter::new_linear(
                 "generator b pitch",
                 ParameterUnit::Semitones, -36.0, 36.0, 0.0
             ).enable_int_snapping(),
+            generator_b_frequency_mode: Parameter::new_enum(
+                "generator b frequency mode",
+                vec!["semitones", "ratio", "fixed"],
+                0,
+            ),
+            generator_b_ratio_coarse: Parameter::new_linear(
+                "generator b ratio coarse",
+                ParameterUnit::NoUnit, 0.0, 64.0, 1.0
+            ).enable_int_snapping(),
+            generator_b_ratio_fine: Parameter::new_linear(
+                "generator b ratio fine",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_b_fixed_frequency: Parameter::new_exponential(
+                "generator b fixed frequency",
+                ParameterUnit::Hz, 1.0, 20000.0, 440.0),
+            generator_b_detune: Parameter::new_linear(
+                "generator b detune",
+                ParameterUnit::Cents, -100.0, 100.0, 0.0),
             generator_b_mod_index: Parameter::new_linear(
                 "generator b mod index",
                 ParameterUnit::NoUnit, 0.0, 8.0, 1.0),
+            generator_b_attack: Parameter::new_exponential(
+                "generator b attack",
+                ParameterUnit::Seconds, 0.001, 10.0, 0.001),
+            generator_b_decay: Parameter::new_exponential(
+                "generator b decay",
+                ParameterUnit::Seconds, 0.02, 10.0, 0.707),
+            generator_b_sustain: Parameter::new_linear(
+                "generator b sustain",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_b_release: Parameter::new_exponential(
+                "generator b release",
+                ParameterUnit::Seconds, 0.02, 10.0, 10.0),
+            generator_b_waveform: Parameter::new_enum(
+                "generator b waveform",
+                vec!["sine", "halfsine", "saw", "pulse", "triangle", "wavetable"],
+                0,
+            ),
+            generator_b_pulse_width: Parameter::new_linear(
+                "generator b pulse width",
+                ParameterUnit::Percent, 0.01, 0.99, 0.5),
+            generator_b_wavetable_position: Parameter::new_linear(
+                "generator b wavetable position",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_b_feedback: Parameter::new_linear(
+                "generator b feedback",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_b_level: Parameter::new_linear(
+                "generator b level",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_b_output: Parameter::new_linear(
+                "generator b output",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_b_direct: Parameter::new_linear(
+                "generator b direct",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_b_sync_source: Parameter::new_enum(
+                "generator b sync source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_b_ring_source: Parameter::new_enum(
+                "generator b ring source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_b_phase_mode: Parameter::new_enum(
+                "generator b phase mode",
+                vec!["free", "reset", "random"],
+                0,
+            ),
+            generator_b_start_phase: Parameter::new_linear(
+                "generator b start phase",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_b_velocity_sensitivity: Parameter::new_linear(
+                "generator b velocity sensitivity",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_b_key_breakpoint: Parameter::new_linear(
+                "generator b key breakpoint",
+                ParameterUnit::NoUnit, 0.0, 127.0, 60.0
+            ).enable_int_snapping(),
+            generator_b_key_left_curve: Parameter::new_enum(
+                "generator b key left curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_b_key_right_curve: Parameter::new_enum(
+                "generator b key right curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_b_key_left_depth: Parameter::new_linear(
+                "generator b key left depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_b_key_right_depth: Parameter::new_linear(
+                "generator b key right depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_b_key_rate_scaling: Parameter::new_linear(
+                "generator b key rate scaling",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
             generator_c_pitch: Parameter::new_linear(
                 "generator c pitch",
                 ParameterUnit::Semitones, -36.0, 36.0, 0.0
             ).enable_int_snapping(),
+            generator_c_frequency_mode: Parameter::new_enum(
+                "generator c frequency mode",
+                vec!["semitones", "ratio", "fixed"],
+                0,
+            ),
+            generator_c_ratio_coarse: Parameter::new_linear(
+                "generator c ratio coarse",
+                ParameterUnit::NoUnit, 0.0, 64.0, 1.0
+            ).enable_int_snapping(),
+            generator_c_ratio_fine: Parameter::new_linear(
+                "generator c ratio fine",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_c_fixed_frequency: Parameter::new_exponential(
+                "generator c fixed frequency",
+                ParameterUnit::Hz, 1.0, 20000.0, 440.0),
+            generator_c_detune: Parameter::new_linear(
+                "generator c detune",
+                ParameterUnit::Cents, -100.0, 100.0, 0.0),
             generator_c_mod_index: Parameter::new_linear(
                 "generator c mod index",
                 ParameterUnit::NoUnit, 0.0, 8.0, 1.0),
+            generator_c_attack: Parameter::new_exponential(
+                "generator c attack",
+                ParameterUnit::Seconds, 0.001, 10.0, 0.001),
+            generator_c_decay: Parameter::new_exponential(
+                "generator c decay",
+                ParameterUnit::Seconds, 0.02, 10.0, 0.707),
+            generator_c_sustain: Parameter::new_linear(
+                "generator c sustain",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_c_release: Parameter::new_exponential(
+                "generator c release",
+                ParameterUnit::Seconds, 0.02, 10.0, 10.0),
+            generator_c_waveform: Parameter::new_enum(
+                "generator c waveform",
+                vec!["sine", "halfsine", "saw", "pulse", "triangle", "wavetable"],
+                0,
+            ),
+            generator_c_pulse_width: Parameter::new_linear(
+                "generator c pulse width",
+                ParameterUnit::Percent, 0.01, 0.99, 0.5),
+            generator_c_wavetable_position: Parameter::new_linear(
+                "generator c wavetable position",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_c_feedback: Parameter::new_linear(
+                "generator c feedback",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_c_level: Parameter::new_linear(
+                "generator c level",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_c_output: Parameter::new_linear(
+                "generator c output",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_c_direct: Parameter::new_linear(
+                "generator c direct",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_c_sync_source: Parameter::new_enum(
+                "generator c sync source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_c_ring_source: Parameter::new_enum(
+                "generator c ring source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_c_phase_mode: Parameter::new_enum(
+                "generator c phase mode",
+                vec!["free", "reset", "random"],
+                0,
+            ),
+            generator_c_start_phase: Parameter::new_linear(
+                "generator c start phase",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_c_velocity_sensitivity: Parameter::new_linear(
+                "generator c velocity sensitivity",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_c_key_breakpoint: Parameter::new_linear(
+                "generator c key breakpoint",
+                ParameterUnit::NoUnit, 0.0, 127.0, 60.0
+            ).enable_int_snapping(),
+            generator_c_key_left_curve: Parameter::new_enum(
+                "generator c key left curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_c_key_right_curve: Parameter::new_enum(
+                "generator c key right curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_c_key_left_depth: Parameter::new_linear(
+                "generator c key left depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_c_key_right_depth: Parameter::new_linear(
+                "generator c key right depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_c_key_rate_scaling: Parameter::new_linear(
+                "generator c key rate scaling",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
             generator_d_pitch: Parameter::new_linear(
                 "generator d pitch",
                 ParameterUnit::Semitones, -36.0, 36.0, 0.0
             ).enable_int_snapping(),
+            generator_d_frequency_mode: Parameter::new_enum(
+                "generator d frequency mode",
+                vec!["semitones", "ratio", "fixed"],
+                0,
+            ),
+            generator_d_ratio_coarse: Parameter::new_linear(
+                "generator d ratio coarse",
+                ParameterUnit::NoUnit, 0.0, 64.0, 1.0
+            ).enable_int_snapping(),
+            generator_d_ratio_fine: Parameter::new_linear(
+                "generator d ratio fine",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_d_fixed_frequency: Parameter::new_exponential(
+                "generator d fixed frequency",
+                ParameterUnit::Hz, 1.0, 20000.0, 440.0),
+            generator_d_detune: Parameter::new_linear(
+                "generator d detune",
+                ParameterUnit::Cents, -100.0, 100.0, 0.0),
             generator_d_mod_index: Parameter::new_linear(
                 "generator d mod index",
                 ParameterUnit::NoUnit, 0.0, 8.0, 1.0),
+            generator_d_attack: Parameter::new_exponential(
+                "generator d attack",
+                ParameterUnit::Seconds, 0.001, 10.0, 0.001),
+            generator_d_decay: Parameter::new_exponential(
+                "generator d decay",
+                ParameterUnit::Seconds, 0.02, 10.0, 0.707),
+            generator_d_sustain: Parameter::new_linear(
+                "generator d sustain",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_d_release: Parameter::new_exponential(
+                "generator d release",
+                ParameterUnit::Seconds, 0.02, 10.0, 10.0),
+            generator_d_waveform: Parameter::new_enum(
+                "generator d waveform",
+                vec!["sine", "halfsine", "saw", "pulse", "triangle", "wavetable"],
+                0,
+            ),
+            generator_d_pulse_width: Parameter::new_linear(
+                "generator d pulse width",
+                ParameterUnit::Percent, 0.01, 0.99, 0.5),
+            generator_d_wavetable_position: Parameter::new_linear(
+                "generator d wavetable position",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_d_feedback: Parameter::new_linear(
+                "generator d feedback",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_d_level: Parameter::new_linear(
+                "generator d level",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_d_output: Parameter::new_linear(
+                "generator d output",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_d_direct: Parameter::new_linear(
+                "generator d direct",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_d_sync_source: Parameter::new_enum(
+                "generator d sync source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_d_ring_source: Parameter::new_enum(
+                "generator d ring source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_d_phase_mode: Parameter::new_enum(
+                "generator d phase mode",
+                vec!["free", "reset", "random"],
+                0,
+            ),
+            generator_d_start_phase: Parameter::new_linear(
+                "generator d start phase",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_d_velocity_sensitivity: Parameter::new_linear(
+                "generator d velocity sensitivity",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_d_key_breakpoint: Parameter::new_linear(
+                "generator d key breakpoint",
+                ParameterUnit::NoUnit, 0.0, 127.0, 60.0
+            ).enable_int_snapping(),
+            generator_d_key_left_curve: Parameter::new_enum(
+                "generator d key left curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_d_key_right_curve: Parameter::new_enum(
+                "generator d key right curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_d_key_left_depth: Parameter::new_linear(
+                "generator d key left depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_d_key_right_depth: Parameter::new_linear(
+                "generator d key right depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_d_key_rate_scaling: Parameter::new_linear(
+                "generator d key rate scaling",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_e_pitch: Parameter::new_linear(
+                "generator e pitch",
+                ParameterUnit::Semitones, -36.0, 36.0, 0.0
+            ).enable_int_snapping(),
+            generator_e_frequency_mode: Parameter::new_enum(
+                "generator e frequency mode",
+                vec!["semitones", "ratio", "fixed"],
+                0,
+            ),
+            generator_e_ratio_coarse: Parameter::new_linear(
+                "generator e ratio coarse",
+                ParameterUnit::NoUnit, 0.0, 64.0, 1.0
+            ).enable_int_snapping(),
+            generator_e_ratio_fine: Parameter::new_linear(
+                "generator e ratio fine",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_e_fixed_frequency: Parameter::new_exponential(
+                "generator e fixed frequency",
+                ParameterUnit::Hz, 1.0, 20000.0, 440.0),
+            generator_e_detune: Parameter::new_linear(
+                "generator e detune",
+                ParameterUnit::Cents, -100.0, 100.0, 0.0),
+            generator_e_mod_index: Parameter::new_linear(
+                "generator e mod index",
+                ParameterUnit::NoUnit, 0.0, 8.0, 1.0),
+            generator_e_attack: Parameter::new_exponential(
+                "generator e attack",
+                ParameterUnit::Seconds, 0.001, 10.0, 0.001),
+            generator_e_decay: Parameter::new_exponential(
+                "generator e decay",
+                ParameterUnit::Seconds, 0.02, 10.0, 0.707),
+            generator_e_sustain: Parameter::new_linear(
+                "generator e sustain",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_e_release: Parameter::new_exponential(
+                "generator e release",
+                ParameterUnit::Seconds, 0.02, 10.0, 10.0),
+            generator_e_waveform: Parameter::new_enum(
+                "generator e waveform",
+                vec!["sine", "halfsine", "saw", "pulse", "triangle", "wavetable"],
+                0,
+            ),
+            generator_e_pulse_width: Parameter::new_linear(
+                "generator e pulse width",
+                ParameterUnit::Percent, 0.01, 0.99, 0.5),
+            generator_e_wavetable_position: Parameter::new_linear(
+                "generator e wavetable position",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_e_feedback: Parameter::new_linear(
+                "generator e feedback",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_e_level: Parameter::new_linear(
+                "generator e level",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_e_output: Parameter::new_linear(
+                "generator e output",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_e_direct: Parameter::new_linear(
+                "generator e direct",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_e_sync_source: Parameter::new_enum(
+                "generator e sync source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_e_ring_source: Parameter::new_enum(
+                "generator e ring source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_e_phase_mode: Parameter::new_enum(
+                "generator e phase mode",
+                vec!["free", "reset", "random"],
+                0,
+            ),
+            generator_e_start_phase: Parameter::new_linear(
+                "generator e start phase",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_e_velocity_sensitivity: Parameter::new_linear(
+                "generator e velocity sensitivity",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_e_key_breakpoint: Parameter::new_linear(
+                "generator e key breakpoint",
+                ParameterUnit::NoUnit, 0.0, 127.0, 60.0
+            ).enable_int_snapping(),
+            generator_e_key_left_curve: Parameter::new_enum(
+                "generator e key left curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_e_key_right_curve: Parameter::new_enum(
+                "generator e key right curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_e_key_left_depth: Parameter::new_linear(
+                "generator e key left depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_e_key_right_depth: Parameter::new_linear(
+                "generator e key right depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_e_key_rate_scaling: Parameter::new_linear(
+                "generator e key rate scaling",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_f_pitch: Parameter::new_linear(
+                "generator f pitch",
+                ParameterUnit::Semitones, -36.0, 36.0, 0.0
+            ).enable_int_snapping(),
+            generator_f_frequency_mode: Parameter::new_enum(
+                "generator f frequency mode",
+                vec!["semitones", "ratio", "fixed"],
+                0,
+            ),
+            generator_f_ratio_coarse: Parameter::new_linear(
+                "generator f ratio coarse",
+                ParameterUnit::NoUnit, 0.0, 64.0, 1.0
+            ).enable_int_snapping(),
+            generator_f_ratio_fine: Parameter::new_linear(
+                "generator f ratio fine",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_f_fixed_frequency: Parameter::new_exponential(
+                "generator f fixed frequency",
+                ParameterUnit::Hz, 1.0, 20000.0, 440.0),
+            generator_f_detune: Parameter::new_linear(
+                "generator f detune",
+                ParameterUnit::Cents, -100.0, 100.0, 0.0),
+            generator_f_mod_index: Parameter::new_linear(
+                "generator f mod index",
+                ParameterUnit::NoUnit, 0.0, 8.0, 1.0),
+            generator_f_attack: Parameter::new_exponential(
+                "generator f attack",
+                ParameterUnit::Seconds, 0.001, 10.0, 0.001),
+            generator_f_decay: Parameter::new_exponential(
+                "generator f decay",
+                ParameterUnit::Seconds, 0.02, 10.0, 0.707),
+            generator_f_sustain: Parameter::new_linear(
+                "generator f sustain",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_f_release: Parameter::new_exponential(
+                "generator f release",
+                ParameterUnit::Seconds, 0.02, 10.0, 10.0),
+            generator_f_waveform: Parameter::new_enum(
+                "generator f waveform",
+                vec!["sine", "halfsine", "saw", "pulse", "triangle", "wavetable"],
+                0,
+            ),
+            generator_f_pulse_width: Parameter::new_linear(
+                "generator f pulse width",
+                ParameterUnit::Percent, 0.01, 0.99, 0.5),
+            generator_f_wavetable_position: Parameter::new_linear(
+                "generator f wavetable position",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_f_feedback: Parameter::new_linear(
+                "generator f feedback",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_f_level: Parameter::new_linear(
+                "generator f level",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_f_output: Parameter::new_linear(
+                "generator f output",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            generator_f_direct: Parameter::new_linear(
+                "generator f direct",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_f_sync_source: Parameter::new_enum(
+                "generator f sync source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_f_ring_source: Parameter::new_enum(
+                "generator f ring source",
+                vec!["off", "a", "b", "c", "d", "e", "f"],
+                0,
+            ),
+            generator_f_phase_mode: Parameter::new_enum(
+                "generator f phase mode",
+                vec!["free", "reset", "random"],
+                0,
+            ),
+            generator_f_start_phase: Parameter::new_linear(
+                "generator f start phase",
+                ParameterUnit::NoUnit, 0.0, 1.0, 0.0),
+            generator_f_velocity_sensitivity: Parameter::new_linear(
+                "generator f velocity sensitivity",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_f_key_breakpoint: Parameter::new_linear(
+                "generator f key breakpoint",
+                ParameterUnit::NoUnit, 0.0, 127.0, 60.0
+            ).enable_int_snapping(),
+            generator_f_key_left_curve: Parameter::new_enum(
+                "generator f key left curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_f_key_right_curve: Parameter::new_enum(
+                "generator f key right curve",
+                vec!["-lin", "-exp", "+exp", "+lin"],
+                0,
+            ),
+            generator_f_key_left_depth: Parameter::new_linear(
+                "generator f key left depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_f_key_right_depth: Parameter::new_linear(
+                "generator f key right depth",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            generator_f_key_rate_scaling: Parameter::new_linear(
+                "generator f key rate scaling",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
             generator_routing: Parameter::new_enum(
                 "generator routing",
-                vec!["2simple", "3stack", "3branch"],
+                vec!["2simple", "3stack", "3branch", "custom",
+                     "dx7_1", "dx7_2", "dx7_3", "dx7_4", "dx7_5", "dx7_6", "dx7_7", "dx7_8",
+                     "dx7_9", "dx7_10", "dx7_11", "dx7_12", "dx7_13", "dx7_14", "dx7_15", "dx7_16",
+                     "dx7_17", "dx7_18", "dx7_19", "dx7_20", "dx7_21", "dx7_22", "dx7_23", "dx7_24",
+                     "dx7_25", "dx7_26", "dx7_27", "dx7_28", "dx7_29", "dx7_30", "dx7_31", "dx7_32"],
                 0,
             ),
             pitch_bend_range: Parameter::new_linear(
                 "generator pitch bend range",
                 ParameterUnit::Semitones, 0.0, 36.0, 2.0),
+            routing_a_to_b: Parameter::new_linear(
+                "routing a to b",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_a_to_c: Parameter::new_linear(
+                "routing a to c",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_a_to_d: Parameter::new_linear(
+                "routing a to d",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_a_to_e: Parameter::new_linear(
+                "routing a to e",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_a_to_f: Parameter::new_linear(
+                "routing a to f",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_b_to_a: Parameter::new_linear(
+                "routing b to a",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_b_to_c: Parameter::new_linear(
+                "routing b to c",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_b_to_d: Parameter::new_linear(
+                "routing b to d",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_b_to_e: Parameter::new_linear(
+                "routing b to e",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_b_to_f: Parameter::new_linear(
+                "routing b to f",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_c_to_a: Parameter::new_linear(
+                "routing c to a",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_c_to_b: Parameter::new_linear(
+                "routing c to b",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_c_to_d: Parameter::new_linear(
+                "routing c to d",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_c_to_e: Parameter::new_linear(
+                "routing c to e",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_c_to_f: Parameter::new_linear(
+                "routing c to f",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_d_to_a: Parameter::new_linear(
+                "routing d to a",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_d_to_b: Parameter::new_linear(
+                "routing d to b",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_d_to_c: Parameter::new_linear(
+                "routing d to c",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_d_to_e: Parameter::new_linear(
+                "routing d to e",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_d_to_f: Parameter::new_linear(
+                "routing d to f",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_e_to_a: Parameter::new_linear(
+                "routing e to a",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_e_to_b: Parameter::new_linear(
+                "routing e to b",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_e_to_c: Parameter::new_linear(
+                "routing e to c",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_e_to_d: Parameter::new_linear(
+                "routing e to d",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_e_to_f: Parameter::new_linear(
+                "routing e to f",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_f_to_a: Parameter::new_linear(
+                "routing f to a",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_f_to_b: Parameter::new_linear(
+                "routing f to b",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_f_to_c: Parameter::new_linear(
+                "routing f to c",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_f_to_d: Parameter::new_linear(
+                "routing f to d",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_f_to_e: Parameter::new_linear(
+                "routing f to e",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_a_output: Parameter::new_linear(
+                "routing a output",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_b_output: Parameter::new_linear(
+                "routing b output",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_c_output: Parameter::new_linear(
+                "routing c output",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_d_output: Parameter::new_linear(
+                "routing d output",
+                ParameterUnit::Percent, 0.0, 1.0, 1.0),
+            routing_e_output: Parameter::new_linear(
+                "routing e output",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            routing_f_output: Parameter::new_linear(
+                "routing f output",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            mix_level: Parameter::new_linear(
+                "mix level",
+                ParameterUnit::Percent, 0.0, 1.0, 0.5),
+            noise_type: Parameter::new_enum(
+                "noise type",
+                vec!["white", "pink", "brown"],
+                0,
+            ),
+            noise_level: Parameter::new_linear(
+                "noise level",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            noise_to_a: Parameter::new_linear(
+                "noise to a",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            noise_to_b: Parameter::new_linear(
+                "noise to b",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            noise_to_c: Parameter::new_linear(
+                "noise to c",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            noise_to_d: Parameter::new_linear(
+                "noise to d",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            noise_to_e: Parameter::new_linear(
+                "noise to e",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            noise_to_f: Parameter::new_linear(
+                "noise to f",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            sub_oscillator_waveform: Parameter::new_enum(
+                "sub oscillator waveform",
+                vec!["square", "sine"],
+                0,
+            ),
+            sub_oscillator_octave: Parameter::new_linear(
+                "sub oscillator octave",
+                ParameterUnit::NoUnit, 1.0, 2.0, 1.0
+            ).enable_int_snapping(),
+            sub_oscillator_level: Parameter::new_linear(
+                "sub oscillator level",
+                ParameterUnit::Percent, 0.0, 1.0, 0.0),
+            oversampling: Parameter::new_enum(
+                "oversampling",
+                vec!["1x", "2x", "4x", "8x"],
+                0,
+            ),
             waveshaper_input_gain: Parameter::new_linear(
                 "waveshaper input gain",
                 ParameterUnit::Percent, 0.0, 1.0, 0.333),
@@ -237,6 +1777,17 @@ impl BaseliskPluginParameters
             ParameterId::AdsrDecay => &self.adsr_decay,
             ParameterId::AdsrSustain => &self.adsr_sustain,
             ParameterId::AdsrRelease => &self.adsr_release,
+            ParameterId::AdsrAttackCurve => &self.adsr_attack_curve,
+            ParameterId::AdsrDecayCurve => &self.adsr_decay_curve,
+            ParameterId::AdsrReleaseCurve => &self.adsr_release_curve,
+            ParameterId::AmpEnvelope => &self.amp_envelope,
+            ParameterId::DahdsrDelay => &self.dahdsr_delay,
+            ParameterId::DahdsrAttack => &self.dahdsr_attack,
+            ParameterId::DahdsrHold => &self.dahdsr_hold,
+            ParameterId::DahdsrDecay => &self.dahdsr_decay,
+            ParameterId::DahdsrSustain => &self.dahdsr_sustain,
+            ParameterId::DahdsrRelease => &self.dahdsr_release,
+            ParameterId::DahdsrLoop => &self.dahdsr_loop,
             ParameterId::DelayTimeLeft => &self.delay_time_left,
             ParameterId::DelayTimeRight => &self.delay_time_right,
             ParameterId::DelayFeedback => &self.delay_feedback,
@@ -246,16 +1797,240 @@ impl BaseliskPluginParameters
             ParameterId::FilterFrequency => &self.filter_frequency,
             ParameterId::FilterSweepRange => &self.filter_sweep_range,
             ParameterId::FilterQuality => &self.filter_quality,
+            ParameterId::FilterEnvelope => &self.filter_envelope,
+            ParameterId::FilterKeyTracking => &self.filter_key_tracking,
+            ParameterId::FilterKeyTrackingCenter => &self.filter_key_tracking_center,
+            ParameterId::FilterType => &self.filter_type,
+            ParameterId::FilterGain => &self.filter_gain,
+            ParameterId::FilterModel => &self.filter_model,
+            ParameterId::FilterSlope => &self.filter_slope,
+            ParameterId::FilterDrive => &self.filter_drive,
+            ParameterId::FilterFeedbackSaturation => &self.filter_feedback_saturation,
             ParameterId::GeneratorAPitch => &self.generator_a_pitch,
+            ParameterId::GeneratorAFrequencyMode => &self.generator_a_frequency_mode,
+            ParameterId::GeneratorARatioCoarse => &self.generator_a_ratio_coarse,
+            ParameterId::GeneratorARatioFine => &self.generator_a_ratio_fine,
+            ParameterId::GeneratorAFixedFrequency => &self.generator_a_fixed_frequency,
+            ParameterId::GeneratorADetune => &self.generator_a_detune,
             ParameterId::GeneratorAModIndex => &self.generator_a_mod_index,
+            ParameterId::GeneratorAAttack => &self.generator_a_attack,
+            ParameterId::GeneratorADecay => &self.generator_a_decay,
+            ParameterId::GeneratorASustain => &self.generator_a_sustain,
+            ParameterId::GeneratorARelease => &self.generator_a_release,
+            ParameterId::GeneratorAWaveform => &self.generator_a_waveform,
+            ParameterId::GeneratorAPulseWidth => &self.generator_a_pulse_width,
+            ParameterId::GeneratorAWavetablePosition => &self.generator_a_wavetable_position,
+            ParameterId::GeneratorAFeedback => &self.generator_a_feedback,
+            ParameterId::GeneratorALevel => &self.generator_a_level,
+            ParameterId::GeneratorAOutput => &self.generator_a_output,
+            ParameterId::GeneratorADirect => &self.generator_a_direct,
+            ParameterId::GeneratorASyncSource => &self.generator_a_sync_source,
+            ParameterId::GeneratorARingSource => &self.generator_a_ring_source,
+            ParameterId::GeneratorAPhaseMode => &self.generator_a_phase_mode,
+            ParameterId::GeneratorAStartPhase => &self.generator_a_start_phase,
+            ParameterId::GeneratorAVelocitySensitivity => &self.generator_a_velocity_sensitivity,
+            ParameterId::GeneratorAKeyBreakpoint => &self.generator_a_key_breakpoint,
+            ParameterId::GeneratorAKeyLeftCurve => &self.generator_a_key_left_curve,
+            ParameterId::GeneratorAKeyRightCurve => &self.generator_a_key_right_curve,
+            ParameterId::GeneratorAKeyLeftDepth => &self.generator_a_key_left_depth,
+            ParameterId::GeneratorAKeyRightDepth => &self.generator_a_key_right_depth,
+            ParameterId::GeneratorAKeyRateScaling => &self.generator_a_key_rate_scaling,
             ParameterId::GeneratorBPitch => &self.generator_b_pitch,
+            ParameterId::GeneratorBFrequencyMode => &self.generator_b_frequency_mode,
+            ParameterId::GeneratorBRatioCoarse => &self.generator_b_ratio_coarse,
+            ParameterId::GeneratorBRatioFine => &self.generator_b_ratio_fine,
+            ParameterId::GeneratorBFixedFrequency => &self.generator_b_fixed_frequency,
+            ParameterId::GeneratorBDetune => &self.generator_b_detune,
             ParameterId::GeneratorBModIndex => &self.generator_b_mod_index,
+            ParameterId::GeneratorBAttack => &self.generator_b_attack,
+            ParameterId::GeneratorBDecay => &self.generator_b_decay,
+            ParameterId::GeneratorBSustain => &self.generator_b_sustain,
+            ParameterId::GeneratorBRelease => &self.generator_b_release,
+            ParameterId::GeneratorBWaveform => &self.generator_b_waveform,
+            ParameterId::GeneratorBPulseWidth => &self.generator_b_pulse_width,
+            ParameterId::GeneratorBWavetablePosition => &self.generator_b_wavetable_position,
+            ParameterId::GeneratorBFeedback => &self.generator_b_feedback,
+            ParameterId::GeneratorBLevel => &self.generator_b_level,
+            ParameterId::GeneratorBOutput => &self.generator_b_output,
+            ParameterId::GeneratorBDirect => &self.generator_b_direct,
+            ParameterId::GeneratorBSyncSource => &self.generator_b_sync_source,
+            ParameterId::GeneratorBRingSource => &self.generator_b_ring_source,
+            ParameterId::GeneratorBPhaseMode => &self.generator_b_phase_mode,
+            ParameterId::GeneratorBStartPhase => &self.generator_b_start_phase,
+            ParameterId::GeneratorBVelocitySensitivity => &self.generator_b_velocity_sensitivity,
+            ParameterId::GeneratorBKeyBreakpoint => &self.generator_b_key_breakpoint,
+            ParameterId::GeneratorBKeyLeftCurve => &self.generator_b_key_left_curve,
+            ParameterId::GeneratorBKeyRightCurve => &self.generator_b_key_right_curve,
+            ParameterId::GeneratorBKeyLeftDepth => &self.generator_b_key_left_depth,
+            ParameterId::GeneratorBKeyRightDepth => &self.generator_b_key_right_depth,
+            ParameterId::GeneratorBKeyRateScaling => &self.generator_b_key_rate_scaling,
             ParameterId::GeneratorCPitch => &self.generator_c_pitch,
+            ParameterId::GeneratorCFrequencyMode => &self.generator_c_frequency_mode,
+            ParameterId::GeneratorCRatioCoarse => &self.generator_c_ratio_coarse,
+            ParameterId::GeneratorCRatioFine => &self.generator_c_ratio_fine,
+            ParameterId::GeneratorCFixedFrequency => &self.generator_c_fixed_frequency,
+            ParameterId::GeneratorCDetune => &self.generator_c_detune,
             ParameterId::GeneratorCModIndex => &self.generator_c_mod_index,
+            ParameterId::GeneratorCAttack => &self.generator_c_attack,
+            ParameterId::GeneratorCDecay => &self.generator_c_decay,
+            ParameterId::GeneratorCSustain => &self.generator_c_sustain,
+            ParameterId::GeneratorCRelease => &self.generator_c_release,
+            ParameterId::GeneratorCWaveform => &self.generator_c_waveform,
+            ParameterId::GeneratorCPulseWidth => &self.generator_c_pulse_width,
+            ParameterId::GeneratorCWavetablePosition => &self.generator_c_wavetable_position,
+            ParameterId::GeneratorCFeedback => &self.generator_c_feedback,
+            ParameterId::GeneratorCLevel => &self.generator_c_level,
+            ParameterId::GeneratorCOutput => &self.generator_c_output,
+            ParameterId::GeneratorCDirect => &self.generator_c_direct,
+            ParameterId::GeneratorCSyncSource => &self.generator_c_sync_source,
+            ParameterId::GeneratorCRingSource => &self.generator_c_ring_source,
+            ParameterId::GeneratorCPhaseMode => &self.generator_c_phase_mode,
+            ParameterId::GeneratorCStartPhase => &self.generator_c_start_phase,
+            ParameterId::GeneratorCVelocitySensitivity => &self.generator_c_velocity_sensitivity,
+            ParameterId::GeneratorCKeyBreakpoint => &self.generator_c_key_breakpoint,
+            ParameterId::GeneratorCKeyLeftCurve => &self.generator_c_key_left_curve,
+            ParameterId::GeneratorCKeyRightCurve => &self.generator_c_key_right_curve,
+            ParameterId::GeneratorCKeyLeftDepth => &self.generator_c_key_left_depth,
+            ParameterId::GeneratorCKeyRightDepth => &self.generator_c_key_right_depth,
+            ParameterId::GeneratorCKeyRateScaling => &self.generator_c_key_rate_scaling,
             ParameterId::GeneratorDPitch => &self.generator_d_pitch,
+            ParameterId::GeneratorDFrequencyMode => &self.generator_d_frequency_mode,
+            ParameterId::GeneratorDRatioCoarse => &self.generator_d_ratio_coarse,
+            ParameterId::GeneratorDRatioFine => &self.generator_d_ratio_fine,
+            ParameterId::GeneratorDFixedFrequency => &self.generator_d_fixed_frequency,
+            ParameterId::GeneratorDDetune => &self.generator_d_detune,
             ParameterId::GeneratorDModIndex => &self.generator_d_mod_index,
+            ParameterId::GeneratorDAttack => &self.generator_d_attack,
+            ParameterId::GeneratorDDecay => &self.generator_d_decay,
+            ParameterId::GeneratorDSustain => &self.generator_d_sustain,
+            ParameterId::GeneratorDRelease => &self.generator_d_release,
+            ParameterId::GeneratorDWaveform => &self.generator_d_waveform,
+            ParameterId::GeneratorDPulseWidth => &self.generator_d_pulse_width,
+            ParameterId::GeneratorDWavetablePosition => &self.generator_d_wavetable_position,
+            ParameterId::GeneratorDFeedback => &self.generator_d_feedback,
+            ParameterId::GeneratorDLevel => &self.generator_d_level,
+            ParameterId::GeneratorDOutput => &self.generator_d_output,
+            ParameterId::GeneratorDDirect => &self.generator_d_direct,
+            ParameterId::GeneratorDSyncSource => &self.generator_d_sync_source,
+            ParameterId::GeneratorDRingSource => &self.generator_d_ring_source,
+            ParameterId::GeneratorDPhaseMode => &self.generator_d_phase_mode,
+            ParameterId::GeneratorDStartPhase => &self.generator_d_start_phase,
+            ParameterId::GeneratorDVelocitySensitivity => &self.generator_d_velocity_sensitivity,
+            ParameterId::GeneratorDKeyBreakpoint => &self.generator_d_key_breakpoint,
+            ParameterId::GeneratorDKeyLeftCurve => &self.generator_d_key_left_curve,
+            ParameterId::GeneratorDKeyRightCurve => &self.generator_d_key_right_curve,
+            ParameterId::GeneratorDKeyLeftDepth => &self.generator_d_key_left_depth,
+            ParameterId::GeneratorDKeyRightDepth => &self.generator_d_key_right_depth,
+            ParameterId::GeneratorDKeyRateScaling => &self.generator_d_key_rate_scaling,
+            ParameterId::GeneratorEPitch => &self.generator_e_pitch,
+            ParameterId::GeneratorEFrequencyMode => &self.generator_e_frequency_mode,
+            ParameterId::GeneratorERatioCoarse => &self.generator_e_ratio_coarse,
+            ParameterId::GeneratorERatioFine => &self.generator_e_ratio_fine,
+            ParameterId::GeneratorEFixedFrequency => &self.generator_e_fixed_frequency,
+            ParameterId::GeneratorEDetune => &self.generator_e_detune,
+            ParameterId::GeneratorEModIndex => &self.generator_e_mod_index,
+            ParameterId::GeneratorEAttack => &self.generator_e_attack,
+            ParameterId::GeneratorEDecay => &self.generator_e_decay,
+            ParameterId::GeneratorESustain => &self.generator_e_sustain,
+            ParameterId::GeneratorERelease => &self.generator_e_release,
+            ParameterId::GeneratorEWaveform => &self.generator_e_waveform,
+            ParameterId::GeneratorEPulseWidth => &self.generator_e_pulse_width,
+            ParameterId::GeneratorEWavetablePosition => &self.generator_e_wavetable_position,
+            ParameterId::GeneratorEFeedback => &self.generator_e_feedback,
+            ParameterId::GeneratorELevel => &self.generator_e_level,
+            ParameterId::GeneratorEOutput => &self.generator_e_output,
+            ParameterId::GeneratorEDirect => &self.generator_e_direct,
+            ParameterId::GeneratorESyncSource => &self.generator_e_sync_source,
+            ParameterId::GeneratorERingSource => &self.generator_e_ring_source,
+            ParameterId::GeneratorEPhaseMode => &self.generator_e_phase_mode,
+            ParameterId::GeneratorEStartPhase => &self.generator_e_start_phase,
+            ParameterId::GeneratorEVelocitySensitivity => &self.generator_e_velocity_sensitivity,
+            ParameterId::GeneratorEKeyBreakpoint => &self.generator_e_key_breakpoint,
+            ParameterId::GeneratorEKeyLeftCurve => &self.generator_e_key_left_curve,
+            ParameterId::GeneratorEKeyRightCurve => &self.generator_e_key_right_curve,
+            ParameterId::GeneratorEKeyLeftDepth => &self.generator_e_key_left_depth,
+            ParameterId::GeneratorEKeyRightDepth => &self.generator_e_key_right_depth,
+            ParameterId::GeneratorEKeyRateScaling => &self.generator_e_key_rate_scaling,
+            ParameterId::GeneratorFPitch => &self.generator_f_pitch,
+            ParameterId::GeneratorFFrequencyMode => &self.generator_f_frequency_mode,
+            ParameterId::GeneratorFRatioCoarse => &self.generator_f_ratio_coarse,
+            ParameterId::GeneratorFRatioFine => &self.generator_f_ratio_fine,
+            ParameterId::GeneratorFFixedFrequency => &self.generator_f_fixed_frequency,
+            ParameterId::GeneratorFDetune => &self.generator_f_detune,
+            ParameterId::GeneratorFModIndex => &self.generator_f_mod_index,
+            ParameterId::GeneratorFAttack => &self.generator_f_attack,
+            ParameterId::GeneratorFDecay => &self.generator_f_decay,
+            ParameterId::GeneratorFSustain => &self.generator_f_sustain,
+            ParameterId::GeneratorFRelease => &self.generator_f_release,
+            ParameterId::GeneratorFWaveform => &self.generator_f_waveform,
+            ParameterId::GeneratorFPulseWidth => &self.generator_f_pulse_width,
+            ParameterId::GeneratorFWavetablePosition => &self.generator_f_wavetable_position,
+            ParameterId::GeneratorFFeedback => &self.generator_f_feedback,
+            ParameterId::GeneratorFLevel => &self.generator_f_level,
+            ParameterId::GeneratorFOutput => &self.generator_f_output,
+            ParameterId::GeneratorFDirect => &self.generator_f_direct,
+            ParameterId::GeneratorFSyncSource => &self.generator_f_sync_source,
+            ParameterId::GeneratorFRingSource => &self.generator_f_ring_source,
+            ParameterId::GeneratorFPhaseMode => &self.generator_f_phase_mode,
+            ParameterId::GeneratorFStartPhase => &self.generator_f_start_phase,
+            ParameterId::GeneratorFVelocitySensitivity => &self.generator_f_velocity_sensitivity,
+            ParameterId::GeneratorFKeyBreakpoint => &self.generator_f_key_breakpoint,
+            ParameterId::GeneratorFKeyLeftCurve => &self.generator_f_key_left_curve,
+            ParameterId::GeneratorFKeyRightCurve => &self.generator_f_key_right_curve,
+            ParameterId::GeneratorFKeyLeftDepth => &self.generator_f_key_left_depth,
+            ParameterId::GeneratorFKeyRightDepth => &self.generator_f_key_right_depth,
+            ParameterId::GeneratorFKeyRateScaling => &self.generator_f_key_rate_scaling,
             ParameterId::GeneratorRouting => &self.generator_routing,
             ParameterId::PitchBendRange => &self.pitch_bend_range,
+            ParameterId::RoutingAToB => &self.routing_a_to_b,
+            ParameterId::RoutingAToC => &self.routing_a_to_c,
+            ParameterId::RoutingAToD => &self.routing_a_to_d,
+            ParameterId::RoutingAToE => &self.routing_a_to_e,
+            ParameterId::RoutingAToF => &self.routing_a_to_f,
+            ParameterId::RoutingBToA => &self.routing_b_to_a,
+            ParameterId::RoutingBToC => &self.routing_b_to_c,
+            ParameterId::RoutingBToD => &self.routing_b_to_d,
+            ParameterId::RoutingBToE => &self.routing_b_to_e,
+            ParameterId::RoutingBToF => &self.routing_b_to_f,
+            ParameterId::RoutingCToA => &self.routing_c_to_a,
+            ParameterId::RoutingCToB => &self.routing_c_to_b,
+            ParameterId::RoutingCToD => &self.routing_c_to_d,
+            ParameterId::RoutingCToE => &self.routing_c_to_e,
+            ParameterId::RoutingCToF => &self.routing_c_to_f,
+            ParameterId::RoutingDToA => &self.routing_d_to_a,
+            ParameterId::RoutingDToB => &self.routing_d_to_b,
+            ParameterId::RoutingDToC => &self.routing_d_to_c,
+            ParameterId::RoutingDToE => &self.routing_d_to_e,
+            ParameterId::RoutingDToF => &self.routing_d_to_f,
+            ParameterId::RoutingEToA => &self.routing_e_to_a,
+            ParameterId::RoutingEToB => &self.routing_e_to_b,
+            ParameterId::RoutingEToC => &self.routing_e_to_c,
+            ParameterId::RoutingEToD => &self.routing_e_to_d,
+            ParameterId::RoutingEToF => &self.routing_e_to_f,
+            ParameterId::RoutingFToA => &self.routing_f_to_a,
+            ParameterId::RoutingFToB => &self.routing_f_to_b,
+            ParameterId::RoutingFToC => &self.routing_f_to_c,
+            ParameterId::RoutingFToD => &self.routing_f_to_d,
+            ParameterId::RoutingFToE => &self.routing_f_to_e,
+            ParameterId::RoutingAOutput => &self.routing_a_output,
+            ParameterId::RoutingBOutput => &self.routing_b_output,
+            ParameterId::RoutingCOutput => &self.routing_c_output,
+            ParameterId::RoutingDOutput => &self.routing_d_output,
+            ParameterId::RoutingEOutput => &self.routing_e_output,
+            ParameterId::RoutingFOutput => &self.routing_f_output,
+            ParameterId::MixLevel => &self.mix_level,
+            ParameterId::NoiseType => &self.noise_type,
+            ParameterId::NoiseLevel => &self.noise_level,
+            ParameterId::NoiseToA => &self.noise_to_a,
+            ParameterId::NoiseToB => &self.noise_to_b,
+            ParameterId::NoiseToC => &self.noise_to_c,
+            ParameterId::NoiseToD => &self.noise_to_d,
+            ParameterId::NoiseToE => &self.noise_to_e,
+            ParameterId::NoiseToF => &self.noise_to_f,
+            ParameterId::SubOscillatorWaveform => &self.sub_oscillator_waveform,
+            ParameterId::SubOscillatorOctave => &self.sub_oscillator_octave,
+            ParameterId::SubOscillatorLevel => &self.sub_oscillator_level,
+            ParameterId::Oversampling => &self.oversampling,
             ParameterId::WaveshaperInputGain => &self.waveshaper_input_gain,
             ParameterId::WaveshaperOutputGain => &self.waveshaper_output_gain,
         }
@@ -273,6 +2048,13 @@ impl BaseliskPluginParameters
         String::from(self.get_parameter_handle(param).get_name())
     }
 
+    /// Look up a parameter by the name returned from get_parameter_name.
+    pub fn get_parameter_id_by_name(&self, name: &str) -> Option<ParameterId> {
+        (0..NUM_PARAMS)
+            .map(ParameterId::from)
+            .find(|param| self.get_parameter_handle(*param).get_name() == name)
+    }
+
     pub fn set_parameter(&self, param: ParameterId, value: defs::Sample) {
         self.get_parameter_handle(param).update_vst_param(value)
     }
@@ -610,6 +2392,30 @@ mod tests {
                 actual, expected, error_abs);
     }
 
+    #[test]
+    fn test_get_parameter_id_by_name() {
+        let params = BaseliskPluginParameters::default();
+        for id in 0..NUM_PARAMS {
+            let name = params.get_parameter_name(ParameterId::from(id));
+            let found = params.get_parameter_id_by_name(&name).unwrap();
+            assert_eq!(found as i32, id);
+        }
+        assert!(params.get_parameter_id_by_name("not a parameter").is_none());
+    }
+
+    #[test]
+    /// Parameters keep their indices as new ones are added,
+    /// so that automation saved by VST hosts still applies.
+    fn test_parameter_indices_are_stable() {
+        assert_eq!(ParameterId::from(0), ParameterId::AdsrAttack);
+        assert_eq!(ParameterId::from(4), ParameterId::DelayTimeLeft);
+        assert_eq!(ParameterId::from(10), ParameterId::FilterFrequency);
+        assert_eq!(ParameterId::from(21), ParameterId::GeneratorRouting);
+        assert_eq!(ParameterId::from(23), ParameterId::WaveshaperInputGain);
+        assert_eq!(ParameterId::from(24), ParameterId::WaveshaperOutputGain);
+        assert_eq!(ParameterId::from(25), ParameterId::AdsrAttackCurve);
+    }
+
     #[test]
     fn test_linear_parameter_map_by_real_value() {
         let parameter = Parameter::new_linear(
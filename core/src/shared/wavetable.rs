@@ -0,0 +1,492 @@
+use defs;
+use std::f64::consts::PI;
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+
+/// Number of samples in a single cycle of the least band-limited mip level.
+/// Frames of other lengths are resampled to this length.
+pub const TABLE_SIZE: usize = 2048;
+
+/// The frame size assumed when loading a wavetable, if none is given.
+pub const DEFAULT_FRAME_SIZE: usize = 2048;
+
+/// Mip levels hold half as many harmonics per octave, but are never shorter than
+/// this, so that linear interpolation between samples stays accurate.
+const MIN_TABLE_SIZE: usize = 256;
+
+/// One mip level per octave, from TABLE_SIZE / 2 harmonics down to the fundamental only.
+const NUM_MIP_LEVELS: usize = 11;
+
+/// Limit on the number of frames, to bound memory use and loading time.
+const MAX_FRAMES: usize = 256;
+
+/// A band-limited wavetable made up of one or more single-cycle frames.
+pub struct Wavetable {
+    // levels[level][frame] holds a single cycle of one frame at one mip level.
+    levels: Vec<Vec<Vec<defs::Sample>>>,
+}
+
+impl Wavetable {
+    /// Build a wavetable from raw samples, splitting them into frames of frame_size
+    /// samples. If there are fewer samples than frame_size, all of the samples are
+    /// used as a single cycle. Any incomplete frame at the end is ignored.
+    pub fn from_samples(samples: &[defs::Sample],
+                        frame_size: usize) -> Result<Self, String>
+    {
+        if frame_size == 0 {
+            return Err(String::from("Wavetable frame size must be greater than zero"))
+        }
+        if samples.is_empty() {
+            return Err(String::from("Wavetable contains no samples"))
+        }
+        let frames: Vec<&[defs::Sample]> = if samples.len() < frame_size {
+            vec![samples]
+        } else {
+            samples.chunks_exact(frame_size).collect()
+        };
+        if frames.len() > MAX_FRAMES {
+            return Err(format!("Wavetable has {} frames, but at most {} are supported",
+                               frames.len(), MAX_FRAMES))
+        }
+
+        let spectra: Vec<Vec<(f64, f64)>> = frames.iter()
+            .map(|frame| get_harmonics(frame))
+            .collect();
+
+        let levels = (0..NUM_MIP_LEVELS).map(|level| {
+            let max_harmonic = (TABLE_SIZE / 2) >> level;
+            let size = usize::max(TABLE_SIZE >> level, MIN_TABLE_SIZE);
+            spectra.iter()
+                .map(|harmonics| synthesize_cycle(harmonics, max_harmonic, size))
+                .collect()
+        }).collect();
+
+        Ok(Self { levels })
+    }
+
+    /// Build a wavetable from the contents of a WAV file.
+    /// Only the first channel of the file is used.
+    pub fn from_wav<R: Read>(reader: R, frame_size: usize) -> Result<Self, String>
+    {
+        let samples = read_wav_samples(reader)?;
+        Self::from_samples(&samples, frame_size)
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Get a sample from the wavetable.
+    /// position: which frame to play, from 0 (first frame) to 1 (last frame).
+    ///           Positions between frames crossfade between them.
+    /// phase: the current phase, 0 <= phase < 1
+    /// phase_step: the phase advanced per sample, used to select a mip level
+    ///             with no harmonics above the Nyquist frequency.
+    pub fn get_sample(&self,
+                      position: defs::Sample,
+                      phase: defs::Sample,
+                      phase_step: defs::Sample) -> defs::Sample
+    {
+        let level = defs::Sample::max(
+            (TABLE_SIZE as defs::Sample * phase_step.abs()).log2().ceil(), 0.0) as usize;
+        let frames = &self.levels[usize::min(level, NUM_MIP_LEVELS - 1)];
+
+        let frame_position = defs::Sample::max(
+            defs::Sample::min(position, 1.0), 0.0) * (frames.len() - 1) as defs::Sample;
+        let frame_index = frame_position as usize;
+        let frame_fraction = frame_position - frame_index as defs::Sample;
+
+        let sample = get_interpolated_sample(&frames[frame_index], phase);
+        if frame_fraction > 0.0 {
+            let next_sample = get_interpolated_sample(&frames[frame_index + 1], phase);
+            sample + (next_sample - sample) * frame_fraction
+        } else {
+            sample
+        }
+    }
+}
+
+/// Linearly interpolate a sample from a single cycle, at a phase from 0 to 1.
+fn get_interpolated_sample(cycle: &[defs::Sample], phase: defs::Sample) -> defs::Sample {
+    let size = cycle.len();
+    let position = (phase - phase.floor()) * size as defs::Sample;
+    let index = position as usize % size;
+    let fraction = position - position.floor();
+    let sample = cycle[index];
+    let next_sample = cycle[(index + 1) % size];
+    sample + (next_sample - sample) * fraction
+}
+
+/// Get the complex amplitudes of the harmonics of a single cycle, from the DC
+/// component up to the lower of the Nyquist frequency and TABLE_SIZE / 2.
+fn get_harmonics(cycle: &[defs::Sample]) -> Vec<(f64, f64)> {
+    let len = cycle.len();
+    let num_harmonics = usize::min(len / 2, TABLE_SIZE / 2) + 1;
+
+    if len.is_power_of_two() {
+        let mut re: Vec<f64> = cycle.iter().map(|sample| f64::from(*sample)).collect();
+        let mut im = vec![0.0; len];
+        fft(&mut re, &mut im, false);
+        (0..num_harmonics)
+            .map(|h| (re[h] / len as f64, im[h] / len as f64))
+            .collect()
+    } else {
+        // Cycles of other lengths are rare, and usually short,
+        // so a direct DFT is fast enough.
+        (0..num_harmonics).map(|h| {
+            let (re, im) = cycle.iter().enumerate().fold((0.0, 0.0), |(re, im), (n, sample)| {
+                let angle = -2.0 * PI * (h * n) as f64 / len as f64;
+                (re + f64::from(*sample) * angle.cos(), im + f64::from(*sample) * angle.sin())
+            });
+            (re / len as f64, im / len as f64)
+        }).collect()
+    }
+}
+
+/// Build a single cycle of the given size from harmonics 1 to max_harmonic.
+/// The DC component is removed, so that wavetables don't offset the frequency
+/// of the generators they modulate.
+fn synthesize_cycle(harmonics: &[(f64, f64)],
+                    max_harmonic: usize,
+                    size: usize) -> Vec<defs::Sample>
+{
+    let mut re = vec![0.0; size];
+    let mut im = vec![0.0; size];
+    for (h, (harmonic_re, harmonic_im)) in harmonics.iter().enumerate()
+        .take(max_harmonic + 1)
+        .skip(1)
+    {
+        if h >= size / 2 {
+            break
+        }
+        re[h] = *harmonic_re;
+        im[h] = *harmonic_im;
+        re[size - h] = *harmonic_re;
+        im[size - h] = -*harmonic_im;
+    }
+    fft(&mut re, &mut im, true);
+    re.iter().map(|sample| *sample as defs::Sample).collect()
+}
+
+/// In-place radix-2 FFT. The length of the buffers must be a power of two.
+/// The inverse transform is not scaled.
+fn fft(re: &mut [f64], im: &mut [f64], inverse: bool) {
+    let len = re.len();
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..len {
+        let mut bit = len >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut span = 2;
+    while span <= len {
+        let angle = sign * 2.0 * PI / span as f64;
+        for start in (0..len).step_by(span) {
+            for k in 0..span / 2 {
+                let (w_im, w_re) = (angle * k as f64).sin_cos();
+                let a = start + k;
+                let b = a + span / 2;
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        span <<= 1;
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from(bytes[offset]) | u16::from(bytes[offset + 1]) << 8
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from(read_u16(bytes, offset)) | u32::from(read_u16(bytes, offset + 2)) << 16
+}
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Read the samples of the first channel of a WAV file.
+/// Supports 8, 16, 24 and 32-bit integer, and 32-bit float, sample formats.
+fn read_wav_samples<R: Read>(mut reader: R) -> Result<Vec<defs::Sample>, String> {
+    let mut bytes = Vec::new();
+    if let Err(reason) = reader.read_to_end(&mut bytes) {
+        return Err(format!("Can't read WAV file: {}", reason))
+    }
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(String::from("Not a WAV file"))
+    }
+
+    // (format, channels, bits per sample)
+    let mut format: Option<(u16, usize, usize)> = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let chunk_len = read_u32(&bytes, offset + 4) as usize;
+        let chunk_start = offset + 8;
+        let chunk_end = usize::min(chunk_start + chunk_len, bytes.len());
+
+        if chunk_id == b"fmt " {
+            if chunk_end - chunk_start < 16 {
+                return Err(String::from("WAV file has an invalid format chunk"))
+            }
+            let mut tag = read_u16(&bytes, chunk_start);
+            if tag == WAVE_FORMAT_EXTENSIBLE && chunk_end - chunk_start >= 26 {
+                // The sub-format GUID starts with the format tag.
+                tag = read_u16(&bytes, chunk_start + 24);
+            }
+            let channels = read_u16(&bytes, chunk_start + 2) as usize;
+            let bits = read_u16(&bytes, chunk_start + 14) as usize;
+            format = Some((tag, channels, bits));
+        } else if chunk_id == b"data" {
+            let (tag, channels, bits) = match format {
+                Some(format) => format,
+                None => return Err(String::from("WAV file has no format chunk before its data")),
+            };
+            if channels == 0 {
+                return Err(String::from("WAV file has no channels"))
+            }
+            let sample_len = bits / 8;
+            let convert: fn(&[u8]) -> defs::Sample = match (tag, bits) {
+                (WAVE_FORMAT_PCM, 8) => |b| (defs::Sample::from(b[0]) - 128.0) / 128.0,
+                (WAVE_FORMAT_PCM, 16) =>
+                    |b| defs::Sample::from(read_u16(b, 0) as i16) / 32_768.0,
+                (WAVE_FORMAT_PCM, 24) =>
+                    |b| ((i32::from(b[0]) << 8 | i32::from(b[1]) << 16 | i32::from(b[2]) << 24) >> 8)
+                        as defs::Sample / 8_388_608.0,
+                (WAVE_FORMAT_PCM, 32) =>
+                    |b| read_u32(b, 0) as i32 as defs::Sample / 2_147_483_648.0,
+                (WAVE_FORMAT_IEEE_FLOAT, 32) => |b| f32::from_bits(read_u32(b, 0)),
+                _ => return Err(format!(
+                    "Unsupported WAV sample format (format {}, {} bits)", tag, bits)),
+            };
+            return Ok(bytes[chunk_start..chunk_end]
+                .chunks_exact(sample_len * channels)
+                .map(|block| convert(&block[0..sample_len]))
+                .collect())
+        }
+
+        // Chunks are padded to an even length.
+        offset = chunk_start + chunk_len + (chunk_len & 1);
+    }
+    Err(String::from("WAV file has no data chunk"))
+}
+
+/// Holds a wavetable for each generator. Wavetables are loaded from the
+/// command line thread, and picked up by generators on the audio thread.
+pub struct WavetableBank {
+    slots: Vec<Mutex<Option<Arc<Wavetable>>>>,
+    // Wavetables that have been replaced, which generators may still be using.
+    // The bank keeps a reference to each so that the audio thread never drops the
+    // last one, which would free the wavetable there.
+    retired: Mutex<Vec<Arc<Wavetable>>>,
+}
+
+impl WavetableBank {
+    pub fn new(num_slots: usize) -> Self {
+        Self {
+            slots: (0..num_slots).map(|_| Mutex::new(None)).collect(),
+            retired: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Set or clear the wavetable for a slot.
+    /// Replaced wavetables are freed by a later call, once no generator uses them.
+    pub fn set(&self, slot: usize, wavetable: Option<Wavetable>) -> Result<(), String> {
+        match self.slots.get(slot) {
+            Some(slot) => {
+                let previous = std::mem::replace(
+                    &mut *slot.lock().unwrap(), wavetable.map(Arc::new));
+                let mut retired = self.retired.lock().unwrap();
+                // Free wavetables that only the bank still refers to.
+                // These can't be picked up again, as they are no longer in a slot.
+                retired.retain(|wavetable| Arc::strong_count(wavetable) > 1);
+                retired.extend(previous);
+                Ok(())
+            },
+            None => Err(format!("Unknown wavetable slot {}", slot)),
+        }
+    }
+
+    /// Get the wavetable for a slot, without blocking.
+    /// Returns None if the slot is being written to, in which case the caller
+    /// should keep using the wavetable it already has.
+    pub fn try_get(&self, slot: usize) -> Option<Option<Arc<Wavetable>>> {
+        match self.slots[slot].try_lock() {
+            Ok(wavetable) => Some(wavetable.clone()),
+            Err(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use defs;
+    use std::sync::Arc;
+
+    fn _assert_close(actual: defs::Sample, expected: defs::Sample, tolerance: defs::Sample) {
+        let error_abs = defs::Sample::abs(actual - expected);
+        if error_abs > tolerance {
+            panic!("actual == {}, expected == {}, absolute error = {}",
+                   actual, expected, error_abs);
+        }
+    }
+
+    fn _sine_cycle(len: usize, amplitude: defs::Sample) -> Vec<defs::Sample> {
+        (0..len)
+            .map(|n| amplitude * (defs::TWOPI * n as defs::Sample / len as defs::Sample).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_fft_round_trip() {
+        let original: Vec<f64> = (0..16).map(|n| f64::from(n * n % 7) - 3.0).collect();
+        let mut re = original.clone();
+        let mut im = vec![0.0; 16];
+        fft(&mut re, &mut im, false);
+        fft(&mut re, &mut im, true);
+        for (actual, expected) in re.iter().zip(original.iter()) {
+            assert!((actual / 16.0 - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    /// A single sine cycle of any length plays back as a sine.
+    fn test_single_cycle_sine() {
+        for len in &[600, 2048, 4096] {
+            let wavetable = Wavetable::from_samples(&_sine_cycle(*len, 0.5), *len).unwrap();
+            assert_eq!(wavetable.num_frames(), 1);
+            for phase in &[0.0, 0.1, 0.25, 0.6, 0.75] {
+                _assert_close(wavetable.get_sample(0.0, *phase, 0.001),
+                              0.5 * (defs::TWOPI * phase).sin(), 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    /// At high frequencies, a saw wave is reduced to its fundamental.
+    fn test_mip_levels_remove_harmonics() {
+        let saw: Vec<defs::Sample> = (0..TABLE_SIZE)
+            .map(|n| 2.0 * n as defs::Sample / TABLE_SIZE as defs::Sample - 1.0)
+            .collect();
+        let wavetable = Wavetable::from_samples(&saw, TABLE_SIZE).unwrap();
+
+        // The fundamental of a saw has amplitude 2 / PI, and its phase is inverted
+        // relative to a sine.
+        let fundamental_amplitude = 2.0 / defs::PI;
+        for phase in &[0.0, 0.25, 0.75] {
+            _assert_close(wavetable.get_sample(0.0, *phase, 0.4),
+                          -fundamental_amplitude * (defs::TWOPI * phase).sin(), 1e-3);
+        }
+    }
+
+    #[test]
+    /// Positions between frames crossfade between them.
+    fn test_position_morphs_between_frames() {
+        let mut samples = _sine_cycle(TABLE_SIZE, 1.0);
+        samples.extend(_sine_cycle(TABLE_SIZE, -1.0));
+        samples.extend(vec![0.0; 100]); // Incomplete frame is ignored
+        let wavetable = Wavetable::from_samples(&samples, TABLE_SIZE).unwrap();
+        assert_eq!(wavetable.num_frames(), 2);
+
+        _assert_close(wavetable.get_sample(0.0, 0.25, 0.001), 1.0, 1e-3);
+        _assert_close(wavetable.get_sample(1.0, 0.25, 0.001), -1.0, 1e-3);
+        _assert_close(wavetable.get_sample(0.5, 0.25, 0.001), 0.0, 1e-3);
+        _assert_close(wavetable.get_sample(0.25, 0.25, 0.001), 0.5, 1e-3);
+    }
+
+    /// Build the bytes of a WAV file with one format chunk and one data chunk.
+    fn _wav_bytes(format: u16, channels: u16, bits: u16, data: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend(b"RIFF");
+        bytes.extend(&(36 + data.len() as u32).to_le_bytes());
+        bytes.extend(b"WAVE");
+        bytes.extend(b"fmt ");
+        bytes.extend(&16u32.to_le_bytes());
+        bytes.extend(&format.to_le_bytes());
+        bytes.extend(&channels.to_le_bytes());
+        bytes.extend(&44_100u32.to_le_bytes());
+        bytes.extend(&(44_100 * u32::from(channels * bits / 8)).to_le_bytes());
+        bytes.extend(&(channels * bits / 8).to_le_bytes());
+        bytes.extend(&bits.to_le_bytes());
+        bytes.extend(b"data");
+        bytes.extend(&(data.len() as u32).to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    #[test]
+    fn test_read_wav_16_bit_stereo() {
+        // Two frames: (0.5, 0.25), (-1.0, 0.0). Only the first channel is read.
+        let mut data = Vec::new();
+        for sample in &[16_384i16, 8_192, -32_768, 0] {
+            data.extend(&sample.to_le_bytes());
+        }
+        let samples = read_wav_samples(&_wav_bytes(1, 2, 16, &data)[..]).unwrap();
+        assert_eq!(samples, vec![0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_read_wav_24_bit() {
+        let data = [0x00, 0x00, 0x40, 0x00, 0x00, 0xC0];
+        let samples = read_wav_samples(&_wav_bytes(1, 1, 24, &data)[..]).unwrap();
+        assert_eq!(samples, vec![0.5, -0.5]);
+    }
+
+    #[test]
+    fn test_read_wav_float() {
+        let mut data = Vec::new();
+        for sample in &[0.75f32, -0.125] {
+            data.extend(&sample.to_bits().to_le_bytes());
+        }
+        let samples = read_wav_samples(&_wav_bytes(3, 1, 32, &data)[..]).unwrap();
+        assert_eq!(samples, vec![0.75, -0.125]);
+    }
+
+    #[test]
+    fn test_read_wav_errors() {
+        assert!(read_wav_samples(&b"not a wav file"[..]).is_err());
+        assert!(read_wav_samples(&_wav_bytes(2, 1, 16, &[0, 0])[..]).is_err());
+        assert!(Wavetable::from_wav(&_wav_bytes(1, 1, 16, &[])[..], DEFAULT_FRAME_SIZE).is_err());
+    }
+
+    #[test]
+    /// Replacing a wavetable that a generator is still using doesn't leave the
+    /// generator with the last reference, so it isn't freed on the audio thread.
+    fn test_bank_keeps_replaced_wavetables() {
+        let bank = WavetableBank::new(1);
+        let wavetable = || Wavetable::from_samples(&_sine_cycle(TABLE_SIZE, 1.0), TABLE_SIZE).ok();
+        bank.set(0, wavetable()).unwrap();
+
+        // A generator picks up the first wavetable, which is then replaced
+        let in_use = bank.try_get(0).unwrap().unwrap();
+        let weak = Arc::downgrade(&in_use);
+        bank.set(0, wavetable()).unwrap();
+        assert!(!Arc::ptr_eq(&bank.try_get(0).unwrap().unwrap(), &in_use));
+
+        // The generator swaps to the new wavetable, but the bank still holds the old one
+        drop(in_use);
+        assert!(weak.upgrade().is_some());
+
+        // It is freed by the next change
+        bank.set(0, None).unwrap();
+        assert!(weak.upgrade().is_none());
+        assert!(bank.try_get(0).unwrap().is_none());
+    }
+}
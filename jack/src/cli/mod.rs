@@ -7,9 +7,13 @@ use cli::tree::{
 };
 use cli::completer::Cli as Cli;
 use baselisk_core::shared::{
+    dx7,
     parameter::ParameterId,
+    wavetable::{Wavetable, DEFAULT_FRAME_SIZE},
     SharedState,
 };
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
 use std::str::{FromStr, SplitWhitespace};
 use std::sync::Arc;
 
@@ -60,122 +64,2564 @@ pub fn update_parameter_from_tokens(shared_state: &Arc<SharedState>,
     Ok(())
 }
 
+fn list_bindings(shared_state: &Arc<SharedState>) -> Result<(), String>
+{
+    let bindings = shared_state.modmatrix.get_bindings();
+    if bindings.is_empty() {
+        println!("No MIDI CC bindings");
+    }
+    for (number, param) in bindings {
+        println!("CC {:3}: {}",
+                 number,
+                 shared_state.parameters.get_parameter_name(param));
+    }
+    Ok(())
+}
+
+fn unbind_from_tokens(shared_state: &Arc<SharedState>,
+                      token_iter: &mut SplitWhitespace) -> Result<(), String>
+{
+    let cc_number: u8 = parse_from_next_token(token_iter)?;
+    match shared_state.modmatrix.unbind_controller(cc_number) {
+        Some(_) => Ok(()),
+        None => Err(format!("CC {} is not bound to a parameter", cc_number)),
+    }
+}
+
+/// Replace the current MIDI CC bindings with those in a controller map file.
+pub fn load_controller_map(shared_state: &Arc<SharedState>,
+                           file_path: &str) -> Result<(), String>
+{
+    let file = match File::open(file_path) {
+        Ok(file) => file,
+        Err(reason) => return Err(format!("Can't open '{}': {}", file_path, reason)),
+    };
+    shared_state.modmatrix.load_controller_map(BufReader::new(file))
+}
+
+/// Write the current MIDI CC bindings to a controller map file.
+pub fn save_controller_map(shared_state: &Arc<SharedState>,
+                           file_path: &str) -> Result<(), String>
+{
+    let file = match File::create(file_path) {
+        Ok(file) => file,
+        Err(reason) => return Err(format!("Can't create '{}': {}", file_path, reason)),
+    };
+    match shared_state.modmatrix.save_controller_map(&mut BufWriter::new(file)) {
+        Ok(_) => {
+            println!("Saved MIDI CC bindings to '{}'", file_path);
+            Ok(())
+        },
+        Err(reason) => Err(format!("Can't write '{}': {}", file_path, reason)),
+    }
+}
+
+/// Load a wavetable for a generator from a WAV file.
+/// Expects a file path, optionally followed by the number of samples per frame.
+fn load_wavetable_from_tokens(shared_state: &Arc<SharedState>,
+                              generator: usize,
+                              token_iter: &mut SplitWhitespace) -> Result<(), String>
+{
+    let file_path: String = parse_from_next_token(token_iter)?;
+    let frame_size = match token_iter.next() {
+        Some(token) => match token.parse::<usize>() {
+            Ok(frame_size) => frame_size,
+            Err(_) => return Err(format!("Couldn't parse token '{}'!", token)),
+        },
+        None => DEFAULT_FRAME_SIZE,
+    };
+    let file = match File::open(&file_path) {
+        Ok(file) => file,
+        Err(reason) => return Err(format!("Can't open '{}': {}", file_path, reason)),
+    };
+    let wavetable = Wavetable::from_wav(BufReader::new(file), frame_size)?;
+    println!("Loaded wavetable with {} frame(s) from '{}'", wavetable.num_frames(), file_path);
+    shared_state.wavetables.set(generator, Some(wavetable))
+}
+
+fn import_dx7_voice_from_tokens(shared_state: &Arc<SharedState>,
+                                token_iter: &mut SplitWhitespace) -> Result<(), String>
+{
+    let file_path: String = parse_from_next_token(token_iter)?;
+    let voice_number: usize = parse_from_next_token(token_iter)?;
+    if !(1..=dx7::NUM_VOICES).contains(&voice_number) {
+        return Err(format!("Voice number must be between 1 and {}", dx7::NUM_VOICES))
+    }
+    let file = match File::open(&file_path) {
+        Ok(file) => file,
+        Err(reason) => return Err(format!("Can't open '{}': {}", file_path, reason)),
+    };
+    let voices = dx7::read_bank(BufReader::new(file))?;
+    let voice = &voices[voice_number - 1];
+    let ignored_features = voice.apply(&shared_state.parameters);
+    println!("Imported voice {} '{}' from '{}'", voice_number, voice.get_name(), file_path);
+    for feature in ignored_features.iter() {
+        println!("Not imported: {}", feature);
+    }
+    Ok(())
+}
+
 fn build_tree() -> Tree
 {
     let mut root = Node::new_with_children();
+    {
+        let bindings = root.add_child("bindings", Node::new_with_children());
+
+        bindings.add_child("list", Node::new_dispatch_event(
+            |_token_iter, shared_state| {
+                list_bindings(shared_state)
+            },
+            None,
+        ));
+
+        bindings.add_child("unbind", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                unbind_from_tokens(shared_state, token_iter)
+            },
+            Some(String::from("<controller>")),
+        ));
+
+        bindings.add_child("save", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                let file_path: String = parse_from_next_token(token_iter)?;
+                save_controller_map(shared_state, &file_path)
+            },
+            Some(String::from("<file>")),
+        ));
+
+        bindings.add_child("load", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                let file_path: String = parse_from_next_token(token_iter)?;
+                load_controller_map(shared_state, &file_path)
+            },
+            Some(String::from("<file>")),
+        ));
+
+        bindings.add_child("clear", Node::new_dispatch_event(
+            |_token_iter, shared_state| {
+                shared_state.modmatrix.clear_bindings();
+                println!("Cleared all MIDI CC bindings");
+                Ok(())
+            },
+            None,
+        ));
+    }
+    {
+        let learn = root.add_child("learn", Node::new_with_children());
+
+        learn.add_child("cancel", Node::new_dispatch_event(
+            |_token_iter, shared_state| {
+                match shared_state.modmatrix.cancel_learn() {
+                    Some(_) => Ok(()),
+                    None => Err(String::from("No MIDI learn in progress")),
+                }
+            },
+            None,
+        ));
+    }
+    {
+        let import = root.add_child("import", Node::new_with_children());
+
+        import.add_child("dx7", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                import_dx7_voice_from_tokens(shared_state, token_iter)
+            },
+            Some(String::from("<file> <voice>")),
+        ));
+    }
     {
         root.add_child("generator_routing", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::GeneratorRouting,
+                    ParameterId::GeneratorRouting,
+                    &mut token_iter)
+            },
+            Some(String::from("<algo_name>")),
+        ));
+
+    }
+    {
+        let routing_a = root.add_child("routing_a", Node::new_with_children());
+
+        routing_a.add_child("to_b", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingAToB,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_a.add_child("to_c", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingAToC,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_a.add_child("to_d", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingAToD,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_a.add_child("to_e", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingAToE,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_a.add_child("to_f", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingAToF,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_a.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingAOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+    }
+    {
+        let routing_b = root.add_child("routing_b", Node::new_with_children());
+
+        routing_b.add_child("to_a", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingBToA,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_b.add_child("to_c", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingBToC,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_b.add_child("to_d", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingBToD,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_b.add_child("to_e", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingBToE,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_b.add_child("to_f", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingBToF,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_b.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingBOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+    }
+    {
+        let routing_c = root.add_child("routing_c", Node::new_with_children());
+
+        routing_c.add_child("to_a", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingCToA,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_c.add_child("to_b", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingCToB,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_c.add_child("to_d", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingCToD,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_c.add_child("to_e", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingCToE,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_c.add_child("to_f", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingCToF,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_c.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingCOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+    }
+    {
+        let routing_d = root.add_child("routing_d", Node::new_with_children());
+
+        routing_d.add_child("to_a", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingDToA,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_d.add_child("to_b", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingDToB,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_d.add_child("to_c", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingDToC,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_d.add_child("to_e", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingDToE,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_d.add_child("to_f", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingDToF,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_d.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingDOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+    }
+    {
+        let routing_e = root.add_child("routing_e", Node::new_with_children());
+
+        routing_e.add_child("to_a", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingEToA,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_e.add_child("to_b", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingEToB,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_e.add_child("to_c", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingEToC,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_e.add_child("to_d", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingEToD,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_e.add_child("to_f", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingEToF,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_e.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingEOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+    }
+    {
+        let routing_f = root.add_child("routing_f", Node::new_with_children());
+
+        routing_f.add_child("to_a", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingFToA,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_f.add_child("to_b", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingFToB,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_f.add_child("to_c", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingFToC,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_f.add_child("to_d", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingFToD,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_f.add_child("to_e", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingFToE,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        routing_f.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::RoutingFOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+    }
+    {
+        let mix = root.add_child("mix", Node::new_with_children());
+
+        mix.add_child("level", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::MixLevel,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+    }
+    {
+        let noise = root.add_child("noise", Node::new_with_children());
+
+        noise.add_child("type", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::NoiseType,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        noise.add_child("level", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::NoiseLevel,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        noise.add_child("to_a", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::NoiseToA,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        noise.add_child("to_b", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::NoiseToB,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        noise.add_child("to_c", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::NoiseToC,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        noise.add_child("to_d", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::NoiseToD,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        noise.add_child("to_e", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::NoiseToE,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        noise.add_child("to_f", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::NoiseToF,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+    }
+    {
+        let sub_oscillator = root.add_child("sub_oscillator", Node::new_with_children());
+
+        sub_oscillator.add_child("waveform", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::SubOscillatorWaveform,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        sub_oscillator.add_child("octave", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::SubOscillatorOctave,
+                    &mut token_iter)
+            },
+            Some(String::from("<octaves>")),
+        ));
+
+        sub_oscillator.add_child("level", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::SubOscillatorLevel,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+    }
+    {
+        root.add_child("oversampling", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::Oversampling,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+    }
+    {
+        root.add_child("pitchbend", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::PitchBendRange,
+                    &mut token_iter)
+            },
+            Some(String::from("<semitones>")),
+        ));
+    }
+    {
+        let generator_a = root.add_child("generator_a", Node::new_with_children());
+
+        generator_a.add_child("pitch", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAPitch,
+                    &mut token_iter)
+            },
+            Some(String::from("<semitones>")),
+        ));
+
+        generator_a.add_child("frequencymode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAFrequencyMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_a.add_child("ratiocoarse", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorARatioCoarse,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_a.add_child("ratiofine", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorARatioFine,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_a.add_child("fixedfrequency", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAFixedFrequency,
+                    &mut token_iter)
+            },
+            Some(String::from("<hz>")),
+        ));
+
+        generator_a.add_child("detune", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorADetune,
+                    &mut token_iter)
+            },
+            Some(String::from("<cents>")),
+        ));
+
+        generator_a.add_child("modindex", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAModIndex,
+                    &mut token_iter)
+            },
+            Some(String::from("<index>")),
+        ));
+
+        generator_a.add_child("attack", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAAttack,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_a.add_child("decay", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorADecay,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_a.add_child("sustain", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorASustain,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_a.add_child("release", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorARelease,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_a.add_child("waveform", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAWaveform,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_a.add_child("pulsewidth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAPulseWidth,
+                    &mut token_iter)
+            },
+            Some(String::from("<width>")),
+        ));
+
+        generator_a.add_child("feedback", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAFeedback,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_a.add_child("level", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorALevel,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_a.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_a.add_child("direct", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorADirect,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_a.add_child("sync", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorASyncSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_a.add_child("ring", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorARingSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_a.add_child("phasemode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAPhaseMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_a.add_child("startphase", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAStartPhase,
+                    &mut token_iter)
+            },
+            Some(String::from("<phase>")),
+        ));
+
+        generator_a.add_child("velocity", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAVelocitySensitivity,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_a.add_child("keybreakpoint", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAKeyBreakpoint,
+                    &mut token_iter)
+            },
+            Some(String::from("<note>")),
+        ));
+
+        generator_a.add_child("keyleftcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAKeyLeftCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_a.add_child("keyrightcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAKeyRightCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_a.add_child("keyleftdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAKeyLeftDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_a.add_child("keyrightdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAKeyRightDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_a.add_child("keyratescaling", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAKeyRateScaling,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        let wavetable = generator_a.add_child("wavetable", Node::new_with_children());
+
+        wavetable.add_child("load", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                load_wavetable_from_tokens(shared_state, 0, token_iter)
+            },
+            Some(String::from("<file> [frame size]")),
+        ));
+
+        wavetable.add_child("clear", Node::new_dispatch_event(
+            |_token_iter, shared_state| {
+                shared_state.wavetables.set(0, None)
+            },
+            None,
+        ));
+
+        wavetable.add_child("position", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorAWavetablePosition,
+                    &mut token_iter)
+            },
+            Some(String::from("<position>")),
+        ));
+    }
+    {
+        let generator_b = root.add_child("generator_b", Node::new_with_children());
+
+        generator_b.add_child("pitch", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBPitch,
+                    &mut token_iter)
+            },
+            Some(String::from("<semitones>")),
+        ));
+
+        generator_b.add_child("frequencymode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBFrequencyMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_b.add_child("ratiocoarse", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBRatioCoarse,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_b.add_child("ratiofine", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBRatioFine,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_b.add_child("fixedfrequency", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBFixedFrequency,
+                    &mut token_iter)
+            },
+            Some(String::from("<hz>")),
+        ));
+
+        generator_b.add_child("detune", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBDetune,
+                    &mut token_iter)
+            },
+            Some(String::from("<cents>")),
+        ));
+
+        generator_b.add_child("modindex", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBModIndex,
+                    &mut token_iter)
+            },
+            Some(String::from("<index>")),
+        ));
+
+        generator_b.add_child("attack", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBAttack,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_b.add_child("decay", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBDecay,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_b.add_child("sustain", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBSustain,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_b.add_child("release", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBRelease,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_b.add_child("waveform", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBWaveform,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_b.add_child("pulsewidth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBPulseWidth,
+                    &mut token_iter)
+            },
+            Some(String::from("<width>")),
+        ));
+
+        generator_b.add_child("feedback", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBFeedback,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_b.add_child("level", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBLevel,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_b.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_b.add_child("direct", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBDirect,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_b.add_child("sync", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBSyncSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_b.add_child("ring", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBRingSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_b.add_child("phasemode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBPhaseMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_b.add_child("startphase", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBStartPhase,
+                    &mut token_iter)
+            },
+            Some(String::from("<phase>")),
+        ));
+
+        generator_b.add_child("velocity", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBVelocitySensitivity,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_b.add_child("keybreakpoint", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBKeyBreakpoint,
+                    &mut token_iter)
+            },
+            Some(String::from("<note>")),
+        ));
+
+        generator_b.add_child("keyleftcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBKeyLeftCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_b.add_child("keyrightcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBKeyRightCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_b.add_child("keyleftdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBKeyLeftDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_b.add_child("keyrightdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBKeyRightDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_b.add_child("keyratescaling", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBKeyRateScaling,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        let wavetable = generator_b.add_child("wavetable", Node::new_with_children());
+
+        wavetable.add_child("load", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                load_wavetable_from_tokens(shared_state, 1, token_iter)
+            },
+            Some(String::from("<file> [frame size]")),
+        ));
+
+        wavetable.add_child("clear", Node::new_dispatch_event(
+            |_token_iter, shared_state| {
+                shared_state.wavetables.set(1, None)
+            },
+            None,
+        ));
+
+        wavetable.add_child("position", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorBWavetablePosition,
+                    &mut token_iter)
+            },
+            Some(String::from("<position>")),
+        ));
+    }
+    {
+        let generator_c = root.add_child("generator_c", Node::new_with_children());
+
+        generator_c.add_child("pitch", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCPitch,
+                    &mut token_iter)
+            },
+            Some(String::from("<semitones>")),
+        ));
+
+        generator_c.add_child("frequencymode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCFrequencyMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_c.add_child("ratiocoarse", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCRatioCoarse,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_c.add_child("ratiofine", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCRatioFine,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_c.add_child("fixedfrequency", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCFixedFrequency,
+                    &mut token_iter)
+            },
+            Some(String::from("<hz>")),
+        ));
+
+        generator_c.add_child("detune", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCDetune,
+                    &mut token_iter)
+            },
+            Some(String::from("<cents>")),
+        ));
+
+        generator_c.add_child("modindex", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCModIndex,
+                    &mut token_iter)
+            },
+            Some(String::from("<index>")),
+        ));
+
+        generator_c.add_child("attack", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCAttack,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_c.add_child("decay", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCDecay,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_c.add_child("sustain", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCSustain,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_c.add_child("release", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCRelease,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_c.add_child("waveform", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCWaveform,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_c.add_child("pulsewidth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCPulseWidth,
+                    &mut token_iter)
+            },
+            Some(String::from("<width>")),
+        ));
+
+        generator_c.add_child("feedback", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCFeedback,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_c.add_child("level", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCLevel,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_c.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_c.add_child("direct", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCDirect,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_c.add_child("sync", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCSyncSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_c.add_child("ring", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCRingSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_c.add_child("phasemode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCPhaseMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_c.add_child("startphase", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCStartPhase,
+                    &mut token_iter)
+            },
+            Some(String::from("<phase>")),
+        ));
+
+        generator_c.add_child("velocity", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCVelocitySensitivity,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_c.add_child("keybreakpoint", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCKeyBreakpoint,
+                    &mut token_iter)
+            },
+            Some(String::from("<note>")),
+        ));
+
+        generator_c.add_child("keyleftcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCKeyLeftCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_c.add_child("keyrightcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCKeyRightCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_c.add_child("keyleftdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCKeyLeftDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_c.add_child("keyrightdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCKeyRightDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_c.add_child("keyratescaling", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCKeyRateScaling,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        let wavetable = generator_c.add_child("wavetable", Node::new_with_children());
+
+        wavetable.add_child("load", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                load_wavetable_from_tokens(shared_state, 2, token_iter)
+            },
+            Some(String::from("<file> [frame size]")),
+        ));
+
+        wavetable.add_child("clear", Node::new_dispatch_event(
+            |_token_iter, shared_state| {
+                shared_state.wavetables.set(2, None)
+            },
+            None,
+        ));
+
+        wavetable.add_child("position", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorCWavetablePosition,
+                    &mut token_iter)
+            },
+            Some(String::from("<position>")),
+        ));
+    }
+    {
+        let generator_d = root.add_child("generator_d", Node::new_with_children());
+
+        generator_d.add_child("pitch", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDPitch,
+                    &mut token_iter)
+            },
+            Some(String::from("<semitones>")),
+        ));
+
+        generator_d.add_child("frequencymode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDFrequencyMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_d.add_child("ratiocoarse", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDRatioCoarse,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_d.add_child("ratiofine", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDRatioFine,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_d.add_child("fixedfrequency", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDFixedFrequency,
+                    &mut token_iter)
+            },
+            Some(String::from("<hz>")),
+        ));
+
+        generator_d.add_child("detune", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDDetune,
+                    &mut token_iter)
+            },
+            Some(String::from("<cents>")),
+        ));
+
+        generator_d.add_child("modindex", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDModIndex,
+                    &mut token_iter)
+            },
+            Some(String::from("<index>")),
+        ));
+
+        generator_d.add_child("attack", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDAttack,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_d.add_child("decay", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDDecay,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_d.add_child("sustain", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDSustain,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_d.add_child("release", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDRelease,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_d.add_child("waveform", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDWaveform,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_d.add_child("pulsewidth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDPulseWidth,
+                    &mut token_iter)
+            },
+            Some(String::from("<width>")),
+        ));
+
+        generator_d.add_child("feedback", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDFeedback,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_d.add_child("level", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDLevel,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_d.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_d.add_child("direct", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDDirect,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_d.add_child("sync", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDSyncSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_d.add_child("ring", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDRingSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_d.add_child("phasemode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDPhaseMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_d.add_child("startphase", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDStartPhase,
+                    &mut token_iter)
+            },
+            Some(String::from("<phase>")),
+        ));
+
+        generator_d.add_child("velocity", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDVelocitySensitivity,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_d.add_child("keybreakpoint", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDKeyBreakpoint,
+                    &mut token_iter)
+            },
+            Some(String::from("<note>")),
+        ));
+
+        generator_d.add_child("keyleftcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDKeyLeftCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_d.add_child("keyrightcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDKeyRightCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_d.add_child("keyleftdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDKeyLeftDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_d.add_child("keyrightdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDKeyRightDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_d.add_child("keyratescaling", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDKeyRateScaling,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        let wavetable = generator_d.add_child("wavetable", Node::new_with_children());
+
+        wavetable.add_child("load", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                load_wavetable_from_tokens(shared_state, 3, token_iter)
+            },
+            Some(String::from("<file> [frame size]")),
+        ));
+
+        wavetable.add_child("clear", Node::new_dispatch_event(
+            |_token_iter, shared_state| {
+                shared_state.wavetables.set(3, None)
+            },
+            None,
+        ));
+
+        wavetable.add_child("position", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorDWavetablePosition,
+                    &mut token_iter)
+            },
+            Some(String::from("<position>")),
+        ));
+    }
+    {
+        let generator_e = root.add_child("generator_e", Node::new_with_children());
+
+        generator_e.add_child("pitch", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEPitch,
+                    &mut token_iter)
+            },
+            Some(String::from("<semitones>")),
+        ));
+
+        generator_e.add_child("frequencymode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEFrequencyMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_e.add_child("ratiocoarse", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorERatioCoarse,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_e.add_child("ratiofine", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorERatioFine,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_e.add_child("fixedfrequency", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEFixedFrequency,
+                    &mut token_iter)
+            },
+            Some(String::from("<hz>")),
+        ));
+
+        generator_e.add_child("detune", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEDetune,
+                    &mut token_iter)
+            },
+            Some(String::from("<cents>")),
+        ));
+
+        generator_e.add_child("modindex", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEModIndex,
+                    &mut token_iter)
+            },
+            Some(String::from("<index>")),
+        ));
+
+        generator_e.add_child("attack", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEAttack,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_e.add_child("decay", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEDecay,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_e.add_child("sustain", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorESustain,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_e.add_child("release", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorERelease,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_e.add_child("waveform", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEWaveform,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_e.add_child("pulsewidth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEPulseWidth,
+                    &mut token_iter)
+            },
+            Some(String::from("<width>")),
+        ));
+
+        generator_e.add_child("feedback", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEFeedback,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_e.add_child("level", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorELevel,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_e.add_child("output", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEOutput,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_e.add_child("direct", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEDirect,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_e.add_child("sync", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorESyncSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_e.add_child("ring", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorERingSource,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_e.add_child("phasemode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEPhaseMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_e.add_child("startphase", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEStartPhase,
+                    &mut token_iter)
+            },
+            Some(String::from("<phase>")),
+        ));
+
+        generator_e.add_child("velocity", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEVelocitySensitivity,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_e.add_child("keybreakpoint", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEKeyBreakpoint,
+                    &mut token_iter)
+            },
+            Some(String::from("<note>")),
+        ));
+
+        generator_e.add_child("keyleftcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEKeyLeftCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_e.add_child("keyrightcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEKeyRightCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_e.add_child("keyleftdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEKeyLeftDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_e.add_child("keyrightdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEKeyRightDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_e.add_child("keyratescaling", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEKeyRateScaling,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        let wavetable = generator_e.add_child("wavetable", Node::new_with_children());
+
+        wavetable.add_child("load", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                load_wavetable_from_tokens(shared_state, 4, token_iter)
+            },
+            Some(String::from("<file> [frame size]")),
+        ));
+
+        wavetable.add_child("clear", Node::new_dispatch_event(
+            |_token_iter, shared_state| {
+                shared_state.wavetables.set(4, None)
+            },
+            None,
+        ));
+
+        wavetable.add_child("position", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorEWavetablePosition,
+                    &mut token_iter)
+            },
+            Some(String::from("<position>")),
+        ));
+    }
+    {
+        let generator_f = root.add_child("generator_f", Node::new_with_children());
+
+        generator_f.add_child("pitch", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFPitch,
+                    &mut token_iter)
+            },
+            Some(String::from("<semitones>")),
+        ));
+
+        generator_f.add_child("frequencymode", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFFrequencyMode,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_f.add_child("ratiocoarse", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFRatioCoarse,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_f.add_child("ratiofine", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFRatioFine,
+                    &mut token_iter)
+            },
+            Some(String::from("<ratio>")),
+        ));
+
+        generator_f.add_child("fixedfrequency", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFFixedFrequency,
+                    &mut token_iter)
+            },
+            Some(String::from("<hz>")),
+        ));
+
+        generator_f.add_child("detune", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFDetune,
+                    &mut token_iter)
+            },
+            Some(String::from("<cents>")),
+        ));
+
+        generator_f.add_child("modindex", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFModIndex,
+                    &mut token_iter)
+            },
+            Some(String::from("<index>")),
+        ));
+
+        generator_f.add_child("attack", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFAttack,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_f.add_child("decay", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFDecay,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_f.add_child("sustain", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFSustain,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        generator_f.add_child("release", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFRelease,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        generator_f.add_child("waveform", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFWaveform,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_f.add_child("pulsewidth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFPulseWidth,
                     &mut token_iter)
             },
-            Some(String::from("<algo_name>")),
+            Some(String::from("<width>")),
         ));
 
-    }
-    {
-        root.add_child("pitchbend", Node::new_dispatch_event(
+        generator_f.add_child("feedback", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::PitchBendRange,
+                    ParameterId::GeneratorFFeedback,
                     &mut token_iter)
             },
-            Some(String::from("<semitones>")),
+            Some(String::from("<amount>")),
         ));
-    }
-    {
-        let generator_a = root.add_child("generator_a", Node::new_with_children());
 
-        generator_a.add_child("pitch", Node::new_dispatch_event(
+        generator_f.add_child("level", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::GeneratorAPitch,
+                    ParameterId::GeneratorFLevel,
                     &mut token_iter)
             },
-            Some(String::from("<semitones>")),
+            Some(String::from("<level>")),
         ));
 
-        generator_a.add_child("modindex", Node::new_dispatch_event(
+        generator_f.add_child("output", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::GeneratorAModIndex,
+                    ParameterId::GeneratorFOutput,
                     &mut token_iter)
             },
-            Some(String::from("<index>")),
+            Some(String::from("<level>")),
         ));
-    }
-    {
-        let generator_b = root.add_child("generator_b", Node::new_with_children());
 
-        generator_b.add_child("pitch", Node::new_dispatch_event(
+        generator_f.add_child("direct", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::GeneratorBPitch,
+                    ParameterId::GeneratorFDirect,
                     &mut token_iter)
             },
-            Some(String::from("<semitones>")),
+            Some(String::from("<level>")),
         ));
 
-        generator_b.add_child("modindex", Node::new_dispatch_event(
+        generator_f.add_child("sync", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::GeneratorBModIndex,
+                    ParameterId::GeneratorFSyncSource,
                     &mut token_iter)
             },
-            Some(String::from("<index>")),
+            Some(String::from("<str>")),
         ));
-    }
-    {
-        let generator_c = root.add_child("generator_c", Node::new_with_children());
 
-        generator_c.add_child("pitch", Node::new_dispatch_event(
+        generator_f.add_child("ring", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::GeneratorCPitch,
+                    ParameterId::GeneratorFRingSource,
                     &mut token_iter)
             },
-            Some(String::from("<semitones>")),
+            Some(String::from("<str>")),
         ));
 
-        generator_c.add_child("modindex", Node::new_dispatch_event(
+        generator_f.add_child("phasemode", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::GeneratorCModIndex,
+                    ParameterId::GeneratorFPhaseMode,
                     &mut token_iter)
             },
-            Some(String::from("<index>")),
+            Some(String::from("<str>")),
         ));
-    }
-    {
-        let generator_d = root.add_child("generator_d", Node::new_with_children());
 
-        generator_d.add_child("pitch", Node::new_dispatch_event(
+        generator_f.add_child("startphase", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::GeneratorDPitch,
+                    ParameterId::GeneratorFStartPhase,
                     &mut token_iter)
             },
-            Some(String::from("<semitones>")),
+            Some(String::from("<phase>")),
         ));
 
-        generator_d.add_child("modindex", Node::new_dispatch_event(
+        generator_f.add_child("velocity", Node::new_dispatch_event(
             |mut token_iter, shared_state| {
                 update_parameter_from_tokens(
                     shared_state,
-                    ParameterId::GeneratorDModIndex,
+                    ParameterId::GeneratorFVelocitySensitivity,
                     &mut token_iter)
             },
-            Some(String::from("<index>")),
+            Some(String::from("<amount>")),
+        ));
+
+        generator_f.add_child("keybreakpoint", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFKeyBreakpoint,
+                    &mut token_iter)
+            },
+            Some(String::from("<note>")),
+        ));
+
+        generator_f.add_child("keyleftcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFKeyLeftCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_f.add_child("keyrightcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFKeyRightCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        generator_f.add_child("keyleftdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFKeyLeftDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_f.add_child("keyrightdepth", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFKeyRightDepth,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        generator_f.add_child("keyratescaling", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFKeyRateScaling,
+                    &mut token_iter)
+            },
+            Some(String::from("<amount>")),
+        ));
+
+        let wavetable = generator_f.add_child("wavetable", Node::new_with_children());
+
+        wavetable.add_child("load", Node::new_dispatch_event(
+            |token_iter, shared_state| {
+                load_wavetable_from_tokens(shared_state, 5, token_iter)
+            },
+            Some(String::from("<file> [frame size]")),
+        ));
+
+        wavetable.add_child("clear", Node::new_dispatch_event(
+            |_token_iter, shared_state| {
+                shared_state.wavetables.set(5, None)
+            },
+            None,
+        ));
+
+        wavetable.add_child("position", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::GeneratorFWavetablePosition,
+                    &mut token_iter)
+            },
+            Some(String::from("<position>")),
         ));
     }
     {
@@ -221,6 +2667,119 @@ fn build_tree() -> Tree
             Some(String::from("<duration>")),
         ));
 
+        adsr.add_child("attackcurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::AdsrAttackCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<curve>")),
+        ));
+
+        adsr.add_child("decaycurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::AdsrDecayCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<curve>")),
+        ));
+
+        adsr.add_child("releasecurve", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::AdsrReleaseCurve,
+                    &mut token_iter)
+            },
+            Some(String::from("<curve>")),
+        ));
+
+    }
+    {
+        root.add_child("amp_envelope", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::AmpEnvelope,
+                    &mut token_iter)
+            },
+            Some(String::from("<envelope_name>")),
+        ));
+    }
+    {
+        let dahdsr = root.add_child("dahdsr", Node::new_with_children());
+        dahdsr.add_child("delay", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::DahdsrDelay,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        dahdsr.add_child("attack", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::DahdsrAttack,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        dahdsr.add_child("hold", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::DahdsrHold,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        dahdsr.add_child("decay", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::DahdsrDecay,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        dahdsr.add_child("sustain", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::DahdsrSustain,
+                    &mut token_iter)
+            },
+            Some(String::from("<level>")),
+        ));
+
+        dahdsr.add_child("release", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::DahdsrRelease,
+                    &mut token_iter)
+            },
+            Some(String::from("<duration>")),
+        ));
+
+        dahdsr.add_child("loop", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::DahdsrLoop,
+                    &mut token_iter)
+            },
+            Some(String::from("<stage_name>")),
+        ));
     }
     {
         let delay = root.add_child("delay", Node::new_with_children());
@@ -324,6 +2883,96 @@ fn build_tree() -> Tree
             },
             Some(String::from("<q>")),
         ));
+
+        filter.add_child("envelope", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::FilterEnvelope,
+                    &mut token_iter)
+            },
+            Some(String::from("<envelope_name>")),
+        ));
+
+        filter.add_child("keytracking", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::FilterKeyTracking,
+                    &mut token_iter)
+            },
+            Some(String::from("<proportion>")),
+        ));
+
+        filter.add_child("keytrackingcenter", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::FilterKeyTrackingCenter,
+                    &mut token_iter)
+            },
+            Some(String::from("<note>")),
+        ));
+
+        filter.add_child("type", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::FilterType,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        filter.add_child("gain", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::FilterGain,
+                    &mut token_iter)
+            },
+            Some(String::from("<db>")),
+        ));
+
+        filter.add_child("model", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::FilterModel,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        filter.add_child("slope", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::FilterSlope,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
+
+        filter.add_child("drive", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::FilterDrive,
+                    &mut token_iter)
+            },
+            Some(String::from("<db>")),
+        ));
+
+        filter.add_child("feedbacksaturation", Node::new_dispatch_event(
+            |mut token_iter, shared_state| {
+                update_parameter_from_tokens(
+                    shared_state,
+                    ParameterId::FilterFeedbackSaturation,
+                    &mut token_iter)
+            },
+            Some(String::from("<str>")),
+        ));
     }
     {
         let waveshaper = root.add_child("waveshaper", Node::new_with_children());
@@ -89,6 +89,12 @@ fn main() {
              .help("Load a patch from a text file")
              .takes_value(true))
 
+        .arg(clap::Arg::with_name("controllermap")
+             .short("c")
+             .long("controllermap")
+             .help("Load MIDI CC bindings from a controller map file")
+             .takes_value(true))
+
         .arg(clap::Arg::with_name("timing-dump")
              .long("timing-dump")
              .help("Output timing information to stderr"))
@@ -105,6 +111,14 @@ fn main() {
 
     // Initialize the audio interface
     connect_and_run(&mut engine, || {
+        // If a controller map is specified, load it before any patch,
+        // so that bindings made by the patch take precedence.
+        if let Some(file_path) = matches.value_of("controllermap") {
+            if let Err(reason) = cli::load_controller_map(&shared_state, file_path) {
+                println!("Error: {}", reason);
+            }
+        }
+
         let mut cli = cli::new(shared_state);
 
         // If a patchfile is specified, load and process it now.